@@ -1,11 +1,130 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
+use tracing::warn;
+use crate::audio_system::{RecordChannels, WavBitDepth};
+use crate::meeting_detector::{DetectionSettings, MeetingAction, MeetingApp};
+use crate::path_manager::RecordingLayout;
+use crate::services::audio_converter::{AudioFormat, ConversionSettings};
+use crate::services::transcription_service::{TranscriptionBackend, TranscriptionBackendKind};
+use crate::constants::{DEFAULT_FILENAME_TEMPLATE, DEFAULT_INPUT_GAIN, DEFAULT_MEETING_END_GRACE_SECS, DEFAULT_NOISE_GATE_THRESHOLD_DB, DEFAULT_PLAYBACK_VOLUME, DEFAULT_PREROLL_SECS, DEFAULT_SILENCE_THRESHOLD_DB, DEFAULT_MP3_BITRATE_KBPS, DEFAULT_CONVERSION_BITRATE_KBPS, DEFAULT_CONVERSION_SAMPLE_RATE, DEFAULT_CONVERSION_CHANNELS, KEYCHAIN_SERVICE, KEYCHAIN_API_KEY_ACCOUNT, KEYCHAIN_UPLOAD_API_KEY_ACCOUNT, DEFAULT_LOCAL_API_PORT, MIN_RECORD_SAMPLE_RATE, MAX_RECORD_SAMPLE_RATE};
+use crate::error::AppError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub web_app_url: String,
+    /// API key; stored in the OS keychain and never written to config.json
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    /// Whether an API key is stored in the OS keychain
+    pub api_key_configured: bool,
+    /// Name of the input device to record from (None = system default)
+    pub input_device: Option<String>,
+    /// Name of the output device to play recordings on (None = system default)
+    pub output_device: Option<String>,
+    /// Volume recordings are played back at (0.0-1.0), independent of the OS volume
+    pub playback_volume: f32,
+    /// Channel layout recordings are captured in; `stereo` is also kept through conversion
+    pub record_channels: RecordChannels,
+    /// Sample format recordings are captured in; 24-bit and float keep more dynamic range in kept WAVs
+    pub wav_bit_depth: WavBitDepth,
+    /// Sample rate (Hz) captured audio is resampled to before it is written, so recordings from
+    /// different devices share one rate (None = keep the input device's native rate)
+    pub record_sample_rate: Option<u32>,
+    /// Multiplier applied to microphone samples while recording (1.0 = unchanged)
+    pub input_gain: f32,
+    /// Attenuate background noise between words while recording (off by default)
+    pub noise_gate_enabled: bool,
+    /// Rolling RMS level in dBFS below which the noise gate closes
+    pub noise_gate_threshold: f32,
+    /// Keep the microphone open while idle so recordings include the seconds before record was pressed
+    pub preroll_enabled: bool,
+    /// Seconds of audio the pre-roll buffer keeps
+    pub preroll_secs: u32,
+    /// Seconds to count down before a manually started recording begins (0 = start immediately)
+    pub start_delay_secs: u32,
+    /// Name of new recordings, without the extension. Placeholders: `{date}` (YYYYMMDD),
+    /// `{time}` (HHMMSS), `{app}` (meeting app), `{title}` (meeting title) and `{counter}`
+    /// (lowest number not already taken). Falls back to the default pattern when the
+    /// template uses a value that isn't available, such as `{app}` outside a meeting.
+    pub filename_template: String,
+    /// Subfolders new recordings are sorted into (flat by default; existing files are not moved)
+    pub recording_layout: RecordingLayout,
+    /// Automatically stop recordings after this many minutes (None = unlimited)
+    pub max_recording_minutes: Option<u32>,
+    /// Stop auto-recorded meetings after this many minutes even if the meeting still looks active,
+    /// guarding against detection that never reports the end (None = unlimited)
+    pub max_meeting_recording_minutes: Option<u32>,
+    /// Watch for meetings (polling running processes and browser tabs); off = manual recording only
+    pub meeting_detection_enabled: bool,
+    /// Let meeting detection read browser tabs (the active tab's URL on macOS, window titles on Linux);
+    /// off = only meeting app processes and microphone use are checked
+    pub scan_browser_urls: bool,
+    /// Extra URL substrings that should be treated as meetings (e.g. a self-hosted Jitsi)
+    pub custom_meeting_patterns: Vec<String>,
+    /// Seconds a meeting must go undetected before it is considered ended
+    pub meeting_end_grace_secs: u64,
+    /// Meeting apps to detect; apps left out of the set are ignored by the detector
+    pub detect_apps: HashSet<MeetingApp>,
+    /// What to do when a meeting is detected (notify, record automatically, or nothing)
+    pub meeting_action: MeetingAction,
+    /// Legacy auto-record toggle; migrated to `meeting_action` on load
+    #[serde(skip_serializing)]
+    pub auto_record_meetings: Option<bool>,
+    /// Backend used for transcription (remote web app or local whisper.cpp)
+    pub transcription_backend: TranscriptionBackendKind,
+    /// GGML model file used by the local Whisper backend
+    pub whisper_model_path: Option<PathBuf>,
+    /// Default transcription language (ISO code like "en"; None or "auto" = detect)
+    pub default_language: Option<String>,
+    /// Split recordings into chunks of this many minutes for transcription (0 = send the whole file)
+    pub transcription_chunk_minutes: u32,
+    /// Transcribe recordings as soon as they are converted; off = only when requested
+    pub auto_transcribe: bool,
+    /// Delete recordings older than this many days, checked at startup and hourly (None = keep everything)
+    pub retention_days: Option<u32>,
+    /// Where recordings are stored (None = app data directory)
+    pub recordings_dir: Option<PathBuf>,
+    /// Number of recordings returned by `get_recent_recordings` (0 = unlimited)
+    pub max_visible_recordings: usize,
+    /// URL recordings are POSTed to by `upload_recording` (None = uploads disabled)
+    pub upload_endpoint: Option<String>,
+    /// Credential sent to the upload endpoint; kept separate from the transcription API key since the
+    /// endpoint may be a third-party server. Stored in the OS keychain and never written to config.json
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_api_key: Option<String>,
+    /// Whether an upload API key is stored in the OS keychain
+    pub upload_api_key_configured: bool,
+    /// Format recordings are converted to when they stop
+    pub default_recording_format: AudioFormat,
+    /// Bitrate used when converting to MP3
+    pub mp3_bitrate_kbps: u32,
+    /// Opus bitrate in kbps used when converting recordings
+    pub conversion_bitrate: u32,
+    /// Sample rate (Hz) recordings are converted to
+    pub conversion_sample_rate: u32,
+    /// Channel count recordings are converted to (1 = mono, 2 = stereo)
+    pub conversion_channels: u16,
+    /// Normalize recording loudness during conversion (off by default; changes what transcription hears)
+    pub auto_normalize: bool,
+    /// Strip leading/trailing silence when a recording stops (opt-in; trimmed audio is not kept)
+    pub auto_trim_silence: bool,
+    /// Level in dBFS below which audio counts as silence for trimming
+    pub silence_threshold_db: f32,
+    /// Keep the WAV next to the converted file instead of deleting it (uses much more disk space)
+    pub keep_original_wav: bool,
+    /// Discard a new recording whose audio is byte-identical to one already saved
+    pub skip_duplicate_recordings: bool,
+    /// Keep the main window hidden at startup (reopened from the tray); set once the first launch has shown it
+    pub start_hidden: bool,
+    /// Serve the local HTTP control API (start/stop recording from scripts); off by default
+    pub enable_local_api: bool,
+    /// Port the local API listens on (127.0.0.1 only)
+    pub local_api_port: u16,
+    /// Bearer token local API requests must send; generated when the API is first enabled
+    pub local_api_token: Option<String>,
 }
 
 impl Default for AppConfig {
@@ -13,16 +132,173 @@ impl Default for AppConfig {
         Self {
             web_app_url: "http://localhost:3001".to_string(), // Changed to API server port
             api_key: None,
+            api_key_configured: false,
+            input_device: None,
+            output_device: None,
+            record_channels: RecordChannels::DeviceNative,
+            wav_bit_depth: WavBitDepth::Int16,
+            record_sample_rate: None,
+            input_gain: DEFAULT_INPUT_GAIN,
+            playback_volume: DEFAULT_PLAYBACK_VOLUME,
+            noise_gate_enabled: false,
+            noise_gate_threshold: DEFAULT_NOISE_GATE_THRESHOLD_DB,
+            preroll_enabled: false,
+            preroll_secs: DEFAULT_PREROLL_SECS,
+            start_delay_secs: 0,
+            filename_template: DEFAULT_FILENAME_TEMPLATE.to_string(),
+            recording_layout: RecordingLayout::Flat,
+            max_recording_minutes: None,
+            max_meeting_recording_minutes: None,
+            meeting_detection_enabled: true,
+            custom_meeting_patterns: Vec::new(),
+            meeting_end_grace_secs: DEFAULT_MEETING_END_GRACE_SECS,
+            scan_browser_urls: true,
+            detect_apps: MeetingApp::known_apps(),
+            meeting_action: MeetingAction::Prompt,
+            auto_record_meetings: None,
+            transcription_backend: TranscriptionBackendKind::Remote,
+            whisper_model_path: None,
+            default_language: None,
+            transcription_chunk_minutes: 0,
+            auto_transcribe: true,
+            retention_days: None,
+            recordings_dir: None,
+            max_visible_recordings: 0,
+            upload_endpoint: None,
+            upload_api_key: None,
+            upload_api_key_configured: false,
+            default_recording_format: AudioFormat::Opus,
+            mp3_bitrate_kbps: DEFAULT_MP3_BITRATE_KBPS,
+            conversion_bitrate: DEFAULT_CONVERSION_BITRATE_KBPS,
+            conversion_sample_rate: DEFAULT_CONVERSION_SAMPLE_RATE,
+            conversion_channels: DEFAULT_CONVERSION_CHANNELS,
+            auto_normalize: false,
+            auto_trim_silence: false,
+            silence_threshold_db: DEFAULT_SILENCE_THRESHOLD_DB,
+            keep_original_wav: false,
+            skip_duplicate_recordings: false,
+            start_hidden: false,
+            enable_local_api: false,
+            local_api_port: DEFAULT_LOCAL_API_PORT,
+            local_api_token: None,
         }
     }
 }
 
 impl AppConfig {
+    /// Generate a local API token if the API is enabled without one; returns whether one was created
+    pub fn ensure_local_api_token(&mut self) -> bool {
+        if !self.enable_local_api || self.local_api_token.as_deref().is_some_and(|token| !token.is_empty()) {
+            return false;
+        }
+        self.local_api_token = Some(uuid::Uuid::new_v4().simple().to_string());
+        true
+    }
+    
     /// Get the transcription API endpoint URL
     pub fn transcribe_endpoint(&self) -> String {
         format!("{}/api/transcribe", self.web_app_url.trim_end_matches('/'))
     }
     
+    /// Validate a user-entered web app URL and normalize it for saving
+    ///
+    /// Only http/https URLs are accepted. Trailing slashes, query strings and fragments are
+    /// dropped since API paths such as `/api/transcribe` are appended to the result.
+    pub fn normalize_web_app_url(input: &str) -> Result<String, AppError> {
+        let input = input.trim();
+        let mut url = url::Url::parse(input)
+            .map_err(|e| AppError::Config(format!("Invalid web app URL '{}': {}", input, e)))?;
+        
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err(AppError::Config(format!(
+                "Web app URL must start with http:// or https:// (got '{}')", input
+            )));
+        }
+        
+        url.set_query(None);
+        url.set_fragment(None);
+        let path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&path);
+        
+        Ok(url.as_str().trim_end_matches('/').to_string())
+    }
+    
+    /// Validate a user-entered upload URL
+    ///
+    /// Only http/https URLs are accepted. Unlike the web app URL, the path and query string are kept
+    /// as entered since signed and presigned upload URLs carry their credentials there; only the
+    /// fragment, which is never sent, is dropped.
+    pub fn normalize_upload_url(input: &str) -> Result<String, AppError> {
+        let input = input.trim();
+        let mut url = url::Url::parse(input)
+            .map_err(|e| AppError::Config(format!("Invalid upload URL '{}': {}", input, e)))?;
+        
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err(AppError::Config(format!(
+                "Upload URL must start with http:// or https:// (got '{}')", input
+            )));
+        }
+        
+        url.set_fragment(None);
+        Ok(url.to_string())
+    }
+    
+    /// Resolve a backend kind into a fully configured transcription backend
+    pub fn resolve_transcription_backend(&self, kind: TranscriptionBackendKind) -> Result<TranscriptionBackend, String> {
+        match kind {
+            TranscriptionBackendKind::Remote => Ok(TranscriptionBackend::Remote {
+                url: self.transcribe_endpoint(),
+                api_key: self.api_key.clone(),
+            }),
+            TranscriptionBackendKind::LocalWhisper => self.whisper_model_path.clone()
+                .map(|model_path| TranscriptionBackend::LocalWhisper { model_path })
+                .ok_or_else(|| "No Whisper model configured for local transcription".to_string()),
+        }
+    }
+    
+    /// Meeting detector settings derived from this config
+    pub fn detection_settings(&self) -> DetectionSettings {
+        DetectionSettings {
+            custom_meeting_patterns: self.custom_meeting_patterns.clone(),
+            meeting_end_grace_secs: self.meeting_end_grace_secs,
+            detect_apps: self.detect_apps.clone(),
+            scan_browser_urls: self.scan_browser_urls,
+        }
+    }
+    
+    /// Noise gate threshold to apply while recording, or None when the gate is off
+    pub fn noise_gate(&self) -> Option<f32> {
+        self.noise_gate_enabled.then_some(self.noise_gate_threshold)
+    }
+    
+    /// Recording sample rate, or None (the device rate) if the configured one is out of range
+    pub fn record_sample_rate(&self) -> Option<u32> {
+        self.record_sample_rate.filter(|rate| (MIN_RECORD_SAMPLE_RATE..=MAX_RECORD_SAMPLE_RATE).contains(rate))
+    }
+    
+    /// Conversion settings from this config, falling back to the defaults if they are out of range
+    pub fn conversion_settings(&self) -> ConversionSettings {
+        let settings = ConversionSettings {
+            bitrate_kbps: self.conversion_bitrate,
+            sample_rate: self.conversion_sample_rate,
+            // Stereo recordings stay stereo instead of being downmixed
+            channels: if self.record_channels == RecordChannels::Stereo { 2 } else { self.conversion_channels },
+            normalize_loudness: self.auto_normalize,
+            keep_source: self.keep_original_wav,
+        };
+        match settings.validate() {
+            Ok(()) => settings,
+            Err(e) => {
+                eprintln!("Invalid conversion settings in config ({}), using defaults", e);
+                ConversionSettings {
+                    normalize_loudness: self.auto_normalize,
+                    keep_source: self.keep_original_wav,
+                    ..ConversionSettings::default()
+                }
+            }
+        }
+    }
+    
     /// Load config from app data directory or create default
     pub async fn load(app_handle: &AppHandle) -> Result<Self, String> {
         let config_path = Self::get_config_path(app_handle)?;
@@ -31,8 +307,30 @@ impl AppConfig {
             let config_content = tokio::fs::read_to_string(&config_path).await
                 .map_err(|e| format!("Failed to read config file: {}", e))?;
             
-            serde_json::from_str(&config_content)
-                .map_err(|e| format!("Failed to parse config file: {}", e))
+            let mut config: Self = serde_json::from_str(&config_content)
+                .map_err(|e| format!("Failed to parse config file: {}", e))?;
+            
+            if config.api_key.is_some() {
+                // Older versions stored the key in plaintext; move it into the keychain
+                if config.migrate_legacy_api_key(|config, key| config.set_api_key(Some(key))) {
+                    config.save(app_handle).await?;
+                }
+            } else if config.api_key_configured {
+                config.api_key = Self::read_keychain(KEYCHAIN_API_KEY_ACCOUNT);
+            }
+            if config.upload_api_key_configured {
+                config.upload_api_key = Self::read_keychain(KEYCHAIN_UPLOAD_API_KEY_ACCOUNT);
+            }
+            
+            if let Some(auto_record) = config.auto_record_meetings.take() {
+                // Older versions only had an on/off auto-record toggle
+                if auto_record {
+                    config.meeting_action = MeetingAction::AutoRecord;
+                }
+                config.save(app_handle).await?;
+            }
+            
+            Ok(config)
         } else {
             // Create default config
             let default_config = Self::default();
@@ -41,6 +339,11 @@ impl AppConfig {
         }
     }
     
+    /// Whether a config file has been written yet (false on the very first launch)
+    pub fn exists(app_handle: &AppHandle) -> bool {
+        Self::get_config_path(app_handle).map(|path| path.exists()).unwrap_or(false)
+    }
+    
     /// Save config to app data directory
     pub async fn save(&self, app_handle: &AppHandle) -> Result<(), String> {
         let config_path = Self::get_config_path(app_handle)?;
@@ -51,7 +354,7 @@ impl AppConfig {
                 .map_err(|e| format!("Failed to create config directory: {}", e))?;
         }
         
-        let config_content = serde_json::to_string_pretty(self)
+        let config_content = serde_json::to_string_pretty(&self.persisted())
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
         
         tokio::fs::write(&config_path, config_content).await
@@ -61,6 +364,79 @@ impl AppConfig {
         Ok(())
     }
     
+    // Move a plaintext key from an older config into the keychain with `store`, returning whether it moved.
+    // On failure the key stays in memory unconfigured, so `persisted` keeps writing it until a retry succeeds.
+    fn migrate_legacy_api_key(&mut self, store: impl FnOnce(&mut Self, String) -> Result<(), String>) -> bool {
+        let Some(legacy_key) = self.api_key.take() else {
+            return false;
+        };
+        match store(self, legacy_key.clone()) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to migrate API key to keychain: {}", e);
+                self.api_key = Some(legacy_key);
+                self.api_key_configured = false;
+                false
+            }
+        }
+    }
+    
+    /// The config as written to disk. Keys in the keychain are only persisted as markers; a legacy
+    /// plaintext key whose keychain migration failed is kept so it isn't lost before the next retry.
+    fn persisted(&self) -> Self {
+        Self {
+            api_key: self.api_key.clone().filter(|_| !self.api_key_configured),
+            upload_api_key: self.upload_api_key.clone().filter(|_| !self.upload_api_key_configured),
+            ..self.clone()
+        }
+    }
+    
+    /// Store (or with `None`, delete) the API key in the OS keychain
+    pub fn set_api_key(&mut self, api_key: Option<String>) -> Result<(), String> {
+        Self::write_keychain(KEYCHAIN_API_KEY_ACCOUNT, api_key.as_deref())?;
+        self.api_key_configured = api_key.is_some();
+        self.api_key = api_key;
+        Ok(())
+    }
+    
+    /// Store (or with `None`, delete) the upload API key in the OS keychain
+    pub fn set_upload_api_key(&mut self, api_key: Option<String>) -> Result<(), String> {
+        Self::write_keychain(KEYCHAIN_UPLOAD_API_KEY_ACCOUNT, api_key.as_deref())?;
+        self.upload_api_key_configured = api_key.is_some();
+        self.upload_api_key = api_key;
+        Ok(())
+    }
+    
+    fn write_keychain(account: &str, secret: Option<&str>) -> Result<(), String> {
+        let entry = Self::keychain_entry(account)?;
+        match secret {
+            Some(secret) => entry.set_password(secret)
+                .map_err(|e| format!("Failed to store API key in keychain: {}", e)),
+            None => match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(e) => Err(format!("Failed to delete API key from keychain: {}", e)),
+            },
+        }
+    }
+    
+    /// Read a key back from the OS keychain
+    fn read_keychain(account: &str) -> Option<String> {
+        let entry = Self::keychain_entry(account).ok()?;
+        match entry.get_password() {
+            Ok(key) => Some(key),
+            Err(keyring::Error::NoEntry) => None,
+            Err(e) => {
+                warn!("Failed to read API key from keychain: {}", e);
+                None
+            }
+        }
+    }
+    
+    fn keychain_entry(account: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, account)
+            .map_err(|e| format!("Failed to open keychain entry: {}", e))
+    }
+    
     /// Get the config file path
     fn get_config_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
         let app_data_dir = app_handle.path().app_data_dir()
@@ -68,4 +444,95 @@ impl AppConfig {
         
         Ok(app_data_dir.join("config.json"))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stereo_recordings_are_converted_in_stereo() {
+        let config = AppConfig { record_channels: RecordChannels::Stereo, ..AppConfig::default() };
+        assert_eq!(config.conversion_settings().channels, 2);
+        assert_eq!(AppConfig::default().conversion_settings().channels, DEFAULT_CONVERSION_CHANNELS);
+    }
+
+    #[test]
+    fn test_normalize_accepts_http_and_https() {
+        assert_eq!(AppConfig::normalize_web_app_url("http://localhost:3001").unwrap(), "http://localhost:3001");
+        assert_eq!(AppConfig::normalize_web_app_url(" https://example.com/ ").unwrap(), "https://example.com");
+    }
+
+    #[test]
+    fn test_normalize_trims_repeated_trailing_slashes() {
+        assert_eq!(AppConfig::normalize_web_app_url("https://example.com/app///").unwrap(), "https://example.com/app");
+    }
+
+    #[test]
+    fn test_normalize_rejects_missing_scheme() {
+        for input in ["localhost:3000", "example.com", "ftp://example.com", ""] {
+            let result = AppConfig::normalize_web_app_url(input);
+            assert!(matches!(result, Err(AppError::Config(_))), "Should reject '{}'", input);
+        }
+    }
+
+    #[test]
+    fn test_normalize_upload_url_keeps_path_and_query() {
+        assert_eq!(
+            AppConfig::normalize_upload_url(" https://bucket.example.com/uploads/a.opus?X-Amz-Signature=abc&X-Amz-Expires=600#frag ").unwrap(),
+            "https://bucket.example.com/uploads/a.opus?X-Amz-Signature=abc&X-Amz-Expires=600"
+        );
+        let error = AppConfig::normalize_upload_url("ftp://example.com/upload").unwrap_err().to_string();
+        assert!(error.contains("Upload URL"), "{}", error);
+    }
+    
+    #[test]
+    fn test_normalize_drops_query_strings_and_fragments() {
+        assert_eq!(
+            AppConfig::normalize_web_app_url("https://example.com/base/?token=abc#settings").unwrap(),
+            "https://example.com/base"
+        );
+    }
+    
+    #[test]
+    fn test_out_of_range_record_sample_rate_falls_back_to_device_rate() {
+        let config = |rate| AppConfig { record_sample_rate: rate, ..AppConfig::default() };
+        assert_eq!(config(Some(48000)).record_sample_rate(), Some(48000));
+        assert_eq!(config(Some(0)).record_sample_rate(), None);
+        assert_eq!(config(Some(MAX_RECORD_SAMPLE_RATE + 1)).record_sample_rate(), None);
+        assert_eq!(config(None).record_sample_rate(), None);
+    }
+
+    #[test]
+    fn test_local_api_token_is_generated_once_when_enabled() {
+        let mut config = AppConfig::default();
+        assert!(!config.ensure_local_api_token());
+        assert_eq!(config.local_api_token, None);
+        
+        config.enable_local_api = true;
+        assert!(config.ensure_local_api_token());
+        let token = config.local_api_token.clone().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(!config.ensure_local_api_token());
+        assert_eq!(config.local_api_token, Some(token));
+    }
+
+    #[test]
+    fn test_failed_api_key_migration_keeps_the_plaintext_key() {
+        let mut config = AppConfig { api_key: Some("sk-legacy".to_string()), ..AppConfig::default() };
+        assert!(!config.migrate_legacy_api_key(|_, _| Err("no secret service".to_string())));
+        assert_eq!(config.api_key.as_deref(), Some("sk-legacy"));
+        assert!(!config.api_key_configured);
+        // A later save (e.g. from another setter) must not drop the only copy
+        assert_eq!(config.persisted().api_key.as_deref(), Some("sk-legacy"));
+        
+        // Once the retry succeeds only the marker is persisted
+        assert!(config.migrate_legacy_api_key(|config, key| {
+            config.api_key = Some(key);
+            config.api_key_configured = true;
+            Ok(())
+        }));
+        assert_eq!(config.persisted().api_key, None);
+        assert!(config.persisted().api_key_configured);
+    }
+}
@@ -1,24 +1,483 @@
-use crossbeam_channel::{Receiver, Sender, unbounded};
+use crossbeam_channel::{Receiver, Sender, bounded, unbounded};
+use tauri::Manager;
+use tokio::sync::oneshot;
 use std::thread::{self, JoinHandle};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
 use crate::events::EventEmitter;
+use crate::services::audio_converter::{AudioConverter, AudioFormat};
 use crate::constants::*;
-use crate::error::{AppError, Result};
-use tracing::{info, error};
+use crate::error::{AppError, ErrorPayload, Result};
+use tracing::{info, warn, error};
 
 // Audio recording imports
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, StreamConfig};
 use hound::{WavSpec, WavWriter, SampleFormat};
+use rubato::{FftFixedIn, Resampler};
 
 /// Commands for audio thread management
 #[derive(Debug)]
 pub enum AudioCommand {
-    StartRecording { file_path: PathBuf },
-    StopRecording,
-    StartPlayback { file_path: PathBuf, app_handle: tauri::AppHandle },
+    /// Start recording; `started` reports whether the input stream was built and started
+    StartRecording {
+        file_path: PathBuf,
+        device_name: Option<String>,
+        app_handle: tauri::AppHandle,
+        started: oneshot::Sender<std::result::Result<(), String>>,
+    },
+    /// Stop recording; `finalized` receives the outcome once the WAV file has been fully
+    /// written, or `None` if the writer did not report back
+    StopRecording { finalized: oneshot::Sender<Option<FinalizedRecording>> },
+    StartPlayback { file_path: PathBuf, device_name: Option<String>, app_handle: tauri::AppHandle, playback_rate: Arc<AtomicU32>, options: PlaybackOptions },
     StopPlayback,
+    PausePlayback,
+    ResumePlayback,
+    /// Keep buffering recent microphone audio while idle (None = stop and release the microphone)
+    SetPreroll { settings: Option<PrerollSettings> },
+}
+
+/// Playback options carried on `AudioCommand::StartPlayback`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlaybackOptions {
+    /// Repeat the file (or segment) until stopped instead of finishing
+    pub loop_playback: bool,
+    /// Start of the segment to play (None = start of the file)
+    pub start_seconds: Option<f64>,
+    /// End of the segment to play (None = end of the file)
+    pub end_seconds: Option<f64>,
+}
+
+/// Position of the current playback, shared from the output callback back to `AppState`
+#[derive(Debug, Default)]
+pub struct PlaybackProgress {
+    /// Current read position in frames
+    frame: AtomicUsize,
+    /// Length of the playing file in frames
+    total_frames: AtomicUsize,
+    sample_rate: AtomicU32,
+}
+
+impl PlaybackProgress {
+    fn start(&self, sample_rate: u32, total_frames: usize, frame: usize) {
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+        self.total_frames.store(total_frames, Ordering::Relaxed);
+        self.frame.store(frame, Ordering::Relaxed);
+    }
+
+    fn frames_to_ms(&self, frames: usize) -> u64 {
+        match self.sample_rate.load(Ordering::Relaxed) {
+            0 => 0,
+            sample_rate => frames as u64 * 1000 / u64::from(sample_rate),
+        }
+    }
+
+    /// Current playback position in milliseconds
+    pub fn position_ms(&self) -> u64 {
+        let frame = self.frame.load(Ordering::Relaxed).min(self.total_frames.load(Ordering::Relaxed));
+        self.frames_to_ms(frame)
+    }
+
+    /// Length of the playing file in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        self.frames_to_ms(self.total_frames.load(Ordering::Relaxed))
+    }
+}
+
+impl PlaybackOptions {
+    /// Frame range `[start, end)` covered by the segment, clamped to the file bounds
+    fn frame_range(&self, sample_rate: u32, total_frames: usize) -> (usize, usize) {
+        let to_frame = |seconds: f64| {
+            let frame = (seconds * f64::from(sample_rate)).round();
+            if frame.is_nan() { 0 } else { (frame.max(0.0) as usize).min(total_frames) }
+        };
+        let start = self.start_seconds.map_or(0, to_frame);
+        let end = self.end_seconds.map_or(total_frames, to_frame).max(start);
+        (start, end)
+    }
+}
+
+/// Handle to the WAV writer thread of an active recording
+#[derive(Debug)]
+struct WriterHandle {
+    // Channel feeding captured samples to the writer thread
+    sample_sender: Sender<f32>,
+    // Receives the recorded duration in milliseconds once the WAV file is finalized
+    finished: Receiver<u64>,
+    // Samples the input callback dropped because the channel was full
+    dropped_samples: Arc<AtomicU64>,
+    // Channel count and sample rate of the input stream, which a replacement stream must match
+    input_channels: u16,
+    input_sample_rate: u32,
+    // App handle of the recording, used to report stream failures
+    app_handle: tauri::AppHandle,
+    // Whether the input stream was already restarted after an error
+    restarted: bool,
+}
+
+/// Channel layout new recordings are written with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordChannels {
+    /// Downmix every input channel to one
+    Mono,
+    /// Two channels; mono inputs are duplicated, extra input channels are dropped
+    Stereo,
+    /// Whatever the input device delivers
+    #[default]
+    DeviceNative,
+}
+
+impl RecordChannels {
+    /// Channel count written to the WAV file for an input device with `device_channels`
+    pub fn output_channels(self, device_channels: u16) -> u16 {
+        match self {
+            RecordChannels::Mono => 1,
+            RecordChannels::Stereo => 2,
+            RecordChannels::DeviceNative => device_channels.max(1),
+        }
+    }
+}
+
+/// Sample format new recordings are written with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WavBitDepth {
+    /// 16-bit integer PCM
+    #[default]
+    Int16,
+    /// 24-bit integer PCM
+    Int24,
+    /// 32-bit float, the samples exactly as captured
+    Float32,
+}
+
+impl WavBitDepth {
+    /// Bits per sample and sample format of the WAV header
+    fn spec_format(self) -> (u16, SampleFormat) {
+        match self {
+            WavBitDepth::Int16 => (16, SampleFormat::Int),
+            WavBitDepth::Int24 => (24, SampleFormat::Int),
+            WavBitDepth::Float32 => (32, SampleFormat::Float),
+        }
+    }
+}
+
+/// Write one sample in [-1.0, 1.0] in the sample format of `spec`
+fn write_pcm_sample<W: std::io::Write + std::io::Seek>(writer: &mut WavWriter<W>, spec: WavSpec, sample: f32) -> hound::Result<()> {
+    match (spec.sample_format, spec.bits_per_sample) {
+        (SampleFormat::Float, _) => writer.write_sample(sample),
+        (SampleFormat::Int, 16) => writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16),
+        (SampleFormat::Int, bits) => {
+            let full_scale = ((1i64 << (bits.clamp(1, 32) - 1)) - 1) as f32;
+            writer.write_sample((sample.clamp(-1.0, 1.0) * full_scale) as i32)
+        }
+    }
+}
+
+/// Map one interleaved input frame onto `out_channels` channels, appending to `out`
+fn remix_frame(frame: &[f32], out_channels: usize, out: &mut Vec<f32>) {
+    match (frame.len(), out_channels) {
+        (input, output) if input == output => out.extend_from_slice(frame),
+        (_, 1) => out.push(frame.iter().sum::<f32>() / frame.len().max(1) as f32),
+        (1, output) => out.extend(std::iter::repeat_n(frame[0], output)),
+        (input, output) if input > output => out.extend_from_slice(&frame[..output]),
+        (_, output) => {
+            // More outputs than inputs: repeat the last input channel
+            out.extend_from_slice(frame);
+            let last = frame.last().copied().unwrap_or(0.0);
+            out.extend(std::iter::repeat_n(last, output - frame.len()));
+        }
+    }
+}
+
+/// Attenuates frames toward silence while their rolling RMS stays below a threshold.
+/// The gain follows a short attack/release envelope so opening and closing doesn't click.
+#[derive(Debug, Clone)]
+pub(crate) struct NoiseGate {
+    threshold: f32,
+    // Per-frame smoothing coefficients
+    rms_coeff: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    mean_square: f32,
+    gain: f32,
+}
+
+impl NoiseGate {
+    fn new(threshold_db: f32, sample_rate: u32) -> Self {
+        let coeff = |ms: f32| 1.0 - (-1000.0 / (ms * sample_rate.max(1) as f32)).exp();
+        Self {
+            threshold: 10f32.powf(threshold_db / 20.0),
+            rms_coeff: coeff(NOISE_GATE_RMS_WINDOW_MS),
+            attack_coeff: coeff(NOISE_GATE_ATTACK_MS),
+            release_coeff: coeff(NOISE_GATE_RELEASE_MS),
+            mean_square: 0.0,
+            // Start open so the first words aren't faded in
+            gain: 1.0,
+        }
+    }
+
+    /// Gate one interleaved frame in place
+    fn process(&mut self, frame: &mut [f32]) {
+        let frame_square = frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32;
+        self.mean_square += (frame_square - self.mean_square) * self.rms_coeff;
+        
+        let (target, coeff) = if self.mean_square.sqrt() >= self.threshold {
+            (1.0, self.attack_coeff)
+        } else {
+            (0.0, self.release_coeff)
+        };
+        self.gain += (target - self.gain) * coeff;
+        frame.iter_mut().for_each(|sample| *sample *= self.gain);
+    }
+}
+
+/// Remix interleaved `samples` from `input_channels` to `output_channels`, pass them through
+/// the noise gate if one is set, and write them in the writer's sample format, returning the
+/// number of samples written. `scratch` is reused between calls.
+pub(crate) fn write_remixed<W: std::io::Write + std::io::Seek>(
+    writer: &mut WavWriter<W>,
+    samples: &[f32],
+    input_channels: u16,
+    output_channels: u16,
+    gate: &mut Option<NoiseGate>,
+    scratch: &mut Vec<f32>,
+) -> hound::Result<u64> {
+    let spec = writer.spec();
+    let mut written = 0;
+    for frame in samples.chunks_exact(usize::from(input_channels.max(1))) {
+        scratch.clear();
+        remix_frame(frame, usize::from(output_channels), scratch);
+        if let Some(gate) = gate.as_mut() {
+            gate.process(scratch);
+        }
+        for &sample in scratch.iter() {
+            write_pcm_sample(writer, spec, sample)?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// Converts interleaved audio to another sample rate as it arrives, in fixed-size chunks
+struct StreamResampler {
+    inner: FftFixedIn<f32>,
+    channels: usize,
+    input_rate: u64,
+    output_rate: u64,
+    // Frames waiting for a full chunk, one buffer per channel
+    pending: Vec<Vec<f32>>,
+    // Leading output frames still to drop so the output isn't shifted by the resampler's latency
+    delay: usize,
+    frames_in: u64,
+    frames_out: u64,
+}
+
+impl StreamResampler {
+    fn new(input_rate: u32, output_rate: u32, channels: u16) -> Result<Self> {
+        let channels = usize::from(channels.max(1));
+        let inner = FftFixedIn::new(input_rate as usize, output_rate as usize, RESAMPLER_CHUNK_FRAMES, 2, channels)
+            .map_err(|e| AppError::Audio(format!("Failed to create resampler: {}", e)))?;
+        Ok(Self {
+            delay: inner.output_delay(),
+            pending: vec![Vec::with_capacity(inner.input_frames_next()); channels],
+            inner,
+            channels,
+            input_rate: u64::from(input_rate),
+            output_rate: u64::from(output_rate),
+            frames_in: 0,
+            frames_out: 0,
+        })
+    }
+
+    /// Resample interleaved `samples`, appending the interleaved output available so far to `out`
+    fn process(&mut self, samples: &[f32], out: &mut Vec<f32>) -> Result<()> {
+        for frame in samples.chunks_exact(self.channels) {
+            for (pending, &sample) in self.pending.iter_mut().zip(frame) {
+                pending.push(sample);
+            }
+            self.frames_in += 1;
+            if self.pending[0].len() >= self.inner.input_frames_next() {
+                let output = self.inner.process(&self.pending, None)
+                    .map_err(|e| AppError::Audio(format!("Failed to resample audio: {}", e)))?;
+                self.pending.iter_mut().for_each(Vec::clear);
+                self.emit(&output, u64::MAX, out);
+            }
+        }
+        Ok(())
+    }
+
+    /// Resample the buffered remainder, ending the output at exactly the resampled length of the input
+    fn flush(&mut self, out: &mut Vec<f32>) -> Result<()> {
+        let total = (self.frames_in * self.output_rate).div_ceil(self.input_rate);
+        let mut pending = Some(std::mem::take(&mut self.pending));
+        while self.frames_out < total {
+            // The first call takes the partial chunk; later ones feed silence to drain the latency
+            let output = self.inner.process_partial(pending.take().as_deref(), None)
+                .map_err(|e| AppError::Audio(format!("Failed to resample audio: {}", e)))?;
+            if output.first().is_none_or(Vec::is_empty) {
+                break;
+            }
+            self.emit(&output, total, out);
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self, output: &[Vec<f32>], limit: u64, out: &mut Vec<f32>) {
+        let frames = output.first().map_or(0, Vec::len);
+        for index in 0..frames {
+            if self.delay > 0 {
+                self.delay -= 1;
+                continue;
+            }
+            if self.frames_out >= limit {
+                return;
+            }
+            out.extend(output.iter().map(|channel| channel[index]));
+            self.frames_out += 1;
+        }
+    }
+}
+
+/// Write interleaved captured `samples` to the WAV: remixed to its channel layout, resampled to its
+/// rate if `resampler` is set, then gated. Returns the number of samples written.
+fn write_captured<W: std::io::Write + std::io::Seek>(
+    writer: &mut WavWriter<W>,
+    samples: &[f32],
+    input_channels: u16,
+    resampler: &mut Option<StreamResampler>,
+    gate: &mut Option<NoiseGate>,
+    remixed: &mut Vec<f32>,
+    resampled: &mut Vec<f32>,
+) -> Result<u64> {
+    let output_channels = writer.spec().channels;
+    let written = match resampler.as_mut() {
+        None => write_remixed(writer, samples, input_channels, output_channels, gate, remixed),
+        Some(resampler) => {
+            // Remix first so fewer channels are resampled when downmixing
+            resampled.clear();
+            for frame in samples.chunks_exact(usize::from(input_channels.max(1))) {
+                remixed.clear();
+                remix_frame(frame, usize::from(output_channels), remixed);
+                resampler.process(remixed, resampled)?;
+            }
+            write_remixed(writer, resampled, output_channels, output_channels, gate, remixed)
+        }
+    };
+    written.map_err(|e| AppError::Audio(e.to_string()))
+}
+
+/// Settings for the pre-roll stream that keeps the last few seconds of microphone audio while idle
+#[derive(Debug, Clone)]
+pub struct PrerollSettings {
+    pub seconds: u32,
+    pub device_name: Option<String>,
+    /// Buffer shared with `AppState`, drained into the WAV when a recording starts
+    pub buffer: Arc<PrerollBuffer>,
+}
+
+/// Rolling buffer of the most recent interleaved input samples
+#[derive(Debug, Default)]
+pub struct PrerollBuffer {
+    ring: Mutex<Option<PrerollRing>>,
+}
+
+/// Preallocated ring the input callback writes to without locking or allocating
+#[derive(Debug, Clone)]
+struct PrerollRing {
+    // Bounded channel holding at most `seconds` of audio; a multiple of `channels` so frames stay aligned
+    sender: Sender<f32>,
+    // Used by the callback to discard the oldest frame when the ring is full
+    receiver: Receiver<f32>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl PrerollRing {
+    /// Append captured samples, discarding the oldest frames beyond the capacity
+    fn push(&self, data: &[f32]) {
+        let capacity = self.sender.capacity().unwrap_or(usize::MAX);
+        for frame in data.chunks(usize::from(self.channels)) {
+            if self.sender.len() + frame.len() > capacity {
+                frame.iter().for_each(|_| { let _ = self.receiver.try_recv(); });
+            }
+            frame.iter().for_each(|&sample| { let _ = self.sender.try_send(sample); });
+        }
+    }
+}
+
+impl PrerollBuffer {
+    /// Empty the buffer and size it for `seconds` of audio in the given format, returning the
+    /// ring for the input callback to fill (None when `seconds` is 0)
+    fn reset(&self, sample_rate: u32, channels: u16, seconds: u32) -> Option<PrerollRing> {
+        let capacity = seconds as usize * sample_rate as usize * usize::from(channels);
+        let ring = (capacity > 0).then(|| {
+            let (sender, receiver) = bounded(capacity);
+            PrerollRing { sender, receiver, sample_rate, channels }
+        });
+        *self.ring.lock().unwrap() = ring.clone();
+        ring
+    }
+
+    /// Drain the buffered audio; empty if it was captured in a different format
+    pub fn take(&self, sample_rate: u32, channels: u16) -> Vec<f32> {
+        let ring = self.ring.lock().unwrap();
+        let Some(ring) = ring.as_ref() else { return Vec::new() };
+        let samples: Vec<f32> = ring.receiver.try_iter().collect();
+        if ring.sample_rate == sample_rate && ring.channels == channels {
+            samples
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Start an input stream that feeds `settings.buffer` until dropped
+fn start_preroll_stream(settings: &PrerollSettings) -> Result<cpal::Stream> {
+    let (device, config) = get_audio_device_and_config(settings.device_name.as_deref())?;
+    let ring = settings.buffer.reset(config.sample_rate.0, config.channels.max(1), settings.seconds);
+    
+    let stream = device.build_input_stream(
+        &config,
+        move |data: &[f32], _: &cpal::InputCallbackInfo| {
+            if let Some(ring) = &ring {
+                ring.push(data);
+            }
+        },
+        |err| eprintln!("Pre-roll stream error: {}", err),
+        None,
+    ).map_err(|e| AppError::Audio(format!("Failed to build pre-roll stream: {}", e)))?;
+    stream.play().map_err(|e| AppError::Audio(format!("Failed to start pre-roll stream: {}", e)))?;
+    
+    Ok(stream)
+}
+
+/// Outcome of a recording reported once its WAV file is finalized
+#[derive(Debug, Clone, Copy)]
+pub struct FinalizedRecording {
+    /// Length of the audio actually written
+    pub duration_ms: u64,
+    /// Samples dropped because the writer thread fell behind
+    pub dropped_samples: u64,
+}
+
+/// Payload of the `recording-degraded` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingDegraded {
+    /// Total samples dropped so far in this recording
+    pub dropped_samples: u64,
+}
+
+/// Payload of the `recording-interrupted` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingInterrupted {
+    pub error: String,
+    /// True if recording continued on the default input device
+    pub recovered: bool,
 }
 
 /// Audio recorder state - only stores thread-safe data
@@ -32,6 +491,8 @@ pub struct RecorderState {
     current_file_path: Option<PathBuf>,
     // Recording status
     is_recording: bool,
+    // Pre-roll to (re)start whenever the audio thread is initialized
+    preroll: Option<PrerollSettings>,
 }
 
 impl RecorderState {
@@ -41,6 +502,7 @@ impl RecorderState {
             audio_thread: None,
             current_file_path: None,
             is_recording: false,
+            preroll: None,
         }
     }
 
@@ -60,6 +522,28 @@ impl RecorderState {
         self.command_sender = Some(command_sender);
         self.audio_thread = Some(audio_thread);
         
+        if let Some(preroll) = self.preroll.clone() {
+            self.send_command(AudioCommand::SetPreroll { settings: Some(preroll) })?;
+        }
+        
+        Ok(())
+    }
+
+    /// Enable or disable the pre-roll buffer, starting the audio thread if needed
+    pub fn set_preroll(&mut self, preroll: Option<PrerollSettings>) -> Result<()> {
+        self.preroll = preroll.clone();
+        match preroll {
+            Some(_) if !self.is_initialized() => self.initialize(),
+            settings if self.is_initialized() => self.send_command(AudioCommand::SetPreroll { settings }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Bring the pre-roll back after `cleanup`, if it is enabled
+    pub fn resume_preroll(&mut self) -> Result<()> {
+        if self.preroll.is_some() {
+            self.initialize()?;
+        }
         Ok(())
     }
 
@@ -100,12 +584,101 @@ impl RecorderState {
     }
 }
 
+/// Information about an available audio device
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioDeviceInfo {
+    pub name: String,
+    pub default_sample_rate: u32,
+    pub channels: u16,
+    pub is_default: bool,
+}
+
+/// Enumerate the input devices (microphones) exposed by the default host
+pub fn list_input_devices() -> Result<Vec<AudioDeviceInfo>> {
+    let host = cpal::default_host();
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+    
+    let devices = host.input_devices()
+        .map_err(|e| AppError::Audio(format!("Failed to enumerate input devices: {}", e)))?;
+    
+    let mut device_infos = Vec::new();
+    for device in devices {
+        let Ok(name) = device.name() else { continue };
+        // Skip devices that can't report an input configuration
+        let Ok(config) = device.default_input_config() else { continue };
+        
+        device_infos.push(AudioDeviceInfo {
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+            default_sample_rate: config.sample_rate().0,
+            channels: config.channels(),
+        });
+    }
+    
+    Ok(device_infos)
+}
+
+/// Enumerate the output devices (speakers, headphones) exposed by the default host
+pub fn list_output_devices() -> Result<Vec<AudioDeviceInfo>> {
+    let host = cpal::default_host();
+    let default_name = host.default_output_device().and_then(|d| d.name().ok());
+    
+    let devices = host.output_devices()
+        .map_err(|e| AppError::Audio(format!("Failed to enumerate output devices: {}", e)))?;
+    
+    let mut device_infos = Vec::new();
+    for device in devices {
+        let Ok(name) = device.name() else { continue };
+        // Skip devices that can't report an output configuration
+        let Ok(config) = device.default_output_config() else { continue };
+        
+        device_infos.push(AudioDeviceInfo {
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+            default_sample_rate: config.sample_rate().0,
+            channels: config.channels(),
+        });
+    }
+    
+    Ok(device_infos)
+}
+
+/// Find an output device by name, falling back to the default output device
+fn find_output_device(host: &cpal::Host, device_name: Option<&str>) -> Option<Device> {
+    if let Some(name) = device_name {
+        let named_device = host.output_devices().ok()
+            .and_then(|mut devices| devices.find(|d| d.name().map(|n| n == name).unwrap_or(false)));
+        
+        if named_device.is_some() {
+            return named_device;
+        }
+        warn!("Output device '{}' not found, falling back to default", name);
+    }
+    
+    host.default_output_device()
+}
+
+/// Find an input device by name, falling back to the default input device
+fn find_input_device(host: &cpal::Host, device_name: Option<&str>) -> Option<Device> {
+    if let Some(name) = device_name {
+        let named_device = host.input_devices().ok()
+            .and_then(|mut devices| devices.find(|d| d.name().map(|n| n == name).unwrap_or(false)));
+        
+        if named_device.is_some() {
+            return named_device;
+        }
+        warn!("Input device '{}' not found, falling back to default", name);
+    }
+    
+    host.default_input_device()
+}
+
 /// Helper function to get audio device and config
-fn get_audio_device_and_config() -> Result<(Device, StreamConfig)> {
+fn get_audio_device_and_config(device_name: Option<&str>) -> Result<(Device, StreamConfig)> {
     let host = cpal::default_host();
     
-    // Try to get default input device (microphone)
-    let device = host.default_input_device()
+    // Use the selected input device (microphone), or the default one
+    let device = find_input_device(&host, device_name)
         .ok_or_else(|| AppError::Audio("No input device available".to_string()))?;
     
     // Get the default input configuration
@@ -115,24 +688,120 @@ fn get_audio_device_and_config() -> Result<(Device, StreamConfig)> {
     Ok((device, config.into()))
 }
 
+/// Name of the input device recordings would use (the selected one, or the default)
+pub fn resolve_input_device_name(device_name: Option<&str>) -> Result<String> {
+    let (device, _) = get_audio_device_and_config(device_name)?;
+    device.name().map_err(|e| AppError::Audio(format!("Failed to read input device name: {}", e)))
+}
+
+/// Input device recordings would use, with the default configuration they'd be captured in
+pub fn describe_input_device(device_name: Option<&str>) -> Result<AudioDeviceInfo> {
+    let (device, config) = get_audio_device_and_config(device_name)?;
+    let default_name = cpal::default_host().default_input_device().and_then(|d| d.name().ok());
+    let name = device.name().map_err(|e| AppError::Audio(format!("Failed to read input device name: {}", e)))?;
+    Ok(AudioDeviceInfo {
+        is_default: default_name.as_deref() == Some(name.as_str()),
+        name,
+        default_sample_rate: config.sample_rate.0,
+        channels: config.channels,
+    })
+}
+
+/// Name of the audio host (backend) in use, e.g. "CoreAudio" or "ALSA"
+pub fn host_name() -> String {
+    cpal::default_host().id().name().to_string()
+}
+
+/// Whether an input device name belongs to a known virtual loopback driver that captures system audio
+pub fn is_loopback_device_name(name: &str) -> bool {
+    const LOOPBACK_MARKERS: [&str; 7] = ["blackhole", "loopback", "soundflower", "stereo mix", "monitor of", "cable output", "voicemeeter"];
+    let name = name.to_lowercase();
+    LOOPBACK_MARKERS.iter().any(|marker| name.contains(marker))
+}
+
+/// Capture briefly from the input device and report whether any non-silent audio arrived.
+/// Without microphone permission macOS delivers only zeros, so this doubles as a permission check.
+pub fn probe_input_signal(device_name: Option<&str>) -> Result<bool> {
+    let (device, config) = get_audio_device_and_config(device_name)?;
+    let heard_signal = Arc::new(AtomicBool::new(false));
+    let heard_signal_clone = heard_signal.clone();
+    
+    let stream = device.build_input_stream(
+        &config,
+        move |data: &[f32], _: &cpal::InputCallbackInfo| {
+            if data.iter().any(|&sample| sample != 0.0) {
+                heard_signal_clone.store(true, Ordering::Relaxed);
+            }
+        },
+        |err| eprintln!("Audio probe stream error: {}", err),
+        None,
+    ).map_err(|e| AppError::Audio(format!("Failed to build audio stream: {}", e)))?;
+    stream.play().map_err(|e| AppError::Audio(format!("Failed to start audio stream: {}", e)))?;
+    
+    thread::sleep(Duration::from_millis(INPUT_PROBE_DURATION_MS));
+    drop(stream);
+    
+    Ok(heard_signal.load(Ordering::Relaxed))
+}
+
 /// Audio manager that runs in a separate thread and handles the cpal stream
 pub fn audio_manager_thread(command_receiver: Receiver<AudioCommand>) {
     let mut current_stream: Option<cpal::Stream> = None;
-    let mut current_writer_sender: Option<Sender<f32>> = None;
+    let mut current_writer: Option<WriterHandle> = None;
+    // Idle input stream filling the pre-roll buffer
+    let mut preroll_stream: Option<cpal::Stream> = None;
+    // Input stream error callbacks report here; only the first pending error matters
+    let (stream_error_sender, stream_error_receiver) = bounded::<String>(1);
     
-    while let Ok(command) = command_receiver.recv() {
+    loop {
+        let command = crossbeam_channel::select! {
+            recv(command_receiver) -> command => match command {
+                Ok(command) => command,
+                Err(_) => break,
+            },
+            recv(stream_error_receiver) -> error => {
+                if let Ok(error) = error {
+                    handle_stream_error(&mut current_stream, &mut current_writer, error, &stream_error_sender);
+                }
+                continue;
+            }
+        };
+        
         match command {
-            AudioCommand::StartRecording { file_path } => {
-                handle_start_recording(&mut current_stream, &mut current_writer_sender, &file_path);
+            AudioCommand::StartRecording { file_path, device_name, app_handle, started } => {
+                // Release the microphone; the buffered pre-roll is picked up by the new recording
+                drop(preroll_stream.take());
+                handle_start_recording(&mut current_stream, &mut current_writer, &file_path, device_name.as_deref(), app_handle, started, stream_error_sender.clone());
             }
-            AudioCommand::StopRecording => {
-                handle_stop_recording(&mut current_stream, &mut current_writer_sender);
+            AudioCommand::StopRecording { finalized } => {
+                handle_stop_recording(&mut current_stream, &mut current_writer, finalized);
             }
-            AudioCommand::StartPlayback { file_path, app_handle } => {
-                handle_start_playback(&mut current_stream, &mut current_writer_sender, &file_path, app_handle);
+            AudioCommand::StartPlayback { file_path, device_name, app_handle, playback_rate, options } => {
+                handle_start_playback(&mut current_stream, &mut current_writer, &file_path, device_name.as_deref(), app_handle, playback_rate, options);
             }
             AudioCommand::StopPlayback => {
-                handle_stop_playback(&mut current_stream, &mut current_writer_sender);
+                handle_stop_playback(&mut current_stream, &mut current_writer);
+            }
+            AudioCommand::PausePlayback => {
+                handle_pause_playback(&current_stream);
+            }
+            AudioCommand::ResumePlayback => {
+                handle_resume_playback(&current_stream);
+            }
+            AudioCommand::SetPreroll { settings } => {
+                drop(preroll_stream.take());
+                match settings {
+                    // A recording already holds the microphone; the next audio thread picks this up
+                    Some(_) if current_writer.is_some() => {}
+                    Some(settings) => match start_preroll_stream(&settings) {
+                        Ok(stream) => {
+                            info!("Pre-roll buffering the last {} seconds of audio", settings.seconds);
+                            preroll_stream = Some(stream);
+                        }
+                        Err(e) => warn!("Failed to start pre-roll: {}", e),
+                    },
+                    None => info!("Pre-roll disabled"),
+                }
             }
         }
     }
@@ -140,96 +809,218 @@ pub fn audio_manager_thread(command_receiver: Receiver<AudioCommand>) {
 
 fn handle_start_recording(
     current_stream: &mut Option<cpal::Stream>,
-    current_writer_sender: &mut Option<Sender<f32>>,
-    file_path: &PathBuf
+    current_writer: &mut Option<WriterHandle>,
+    file_path: &PathBuf,
+    device_name: Option<&str>,
+    app_handle: tauri::AppHandle,
+    started: oneshot::Sender<std::result::Result<(), String>>,
+    stream_error_sender: Sender<String>
 ) {
     // Stop any existing recording
     if let Some(stream) = current_stream.take() {
         drop(stream);
     }
-    if let Some(sender) = current_writer_sender.take() {
-        drop(sender);
+    if let Some(writer) = current_writer.take() {
+        drop(writer);
     }
     
     // Start new recording
-    match start_audio_recording(file_path) {
-        Ok((stream, writer_sender)) => {
+    match start_audio_recording(file_path, device_name, app_handle.clone(), stream_error_sender) {
+        Ok((stream, writer)) => {
             *current_stream = Some(stream);
-            *current_writer_sender = Some(writer_sender);
+            *current_writer = Some(writer);
             info!("Started recording to: {}", file_path.display());
+            let _ = started.send(Ok(()));
         }
         Err(e) => {
             error!("Failed to start recording: {}", e);
+            EventEmitter::recording_error(&app_handle, &ErrorPayload::from(AppError::Audio(e.to_string())));
+            let _ = started.send(Err(e.to_string()));
         }
     }
 }
 
 fn handle_stop_recording(
     current_stream: &mut Option<cpal::Stream>,
-    current_writer_sender: &mut Option<Sender<f32>>
+    current_writer: &mut Option<WriterHandle>,
+    finalized: oneshot::Sender<Option<FinalizedRecording>>
 ) {
     // Stop recording by dropping the stream and sender
     if let Some(stream) = current_stream.take() {
         drop(stream);
     }
-    if let Some(sender) = current_writer_sender.take() {
-        drop(sender);
-        // Give writer thread time to finalize the WAV file
-        std::thread::sleep(std::time::Duration::from_millis(WRITER_CLEANUP_DELAY_MS));
+    let mut outcome = None;
+    if let Some(writer) = current_writer.take() {
+        drop(writer.sample_sender);
+        // Wait for the writer thread to flush and finalize the WAV file
+        match writer.finished.recv_timeout(Duration::from_millis(WAV_FINALIZE_TIMEOUT_MS)) {
+            Ok(duration_ms) => {
+                let dropped_samples = writer.dropped_samples.load(Ordering::Relaxed);
+                if dropped_samples > 0 {
+                    warn!("Recording dropped {} samples because the writer fell behind", dropped_samples);
+                }
+                outcome = Some(FinalizedRecording { duration_ms, dropped_samples });
+            }
+            Err(_) => error!("Timed out waiting for WAV writer to finalize"),
+        }
     }
     info!("Stopped audio recording");
+    let _ = finalized.send(outcome);
+}
+
+fn handle_stream_error(
+    current_stream: &mut Option<cpal::Stream>,
+    current_writer: &mut Option<WriterHandle>,
+    error: String,
+    stream_error_sender: &Sender<String>
+) {
+    // Errors arriving after the recording stopped are stale
+    let Some(writer) = current_writer.as_mut() else { return };
+    error!("Recording stream error: {}", error);
+    
+    if let Some(stream) = current_stream.take() {
+        drop(stream);
+    }
+    
+    // Try once to continue the same file on the default device (e.g. after a headset unplug)
+    if !writer.restarted {
+        writer.restarted = true;
+        match restart_on_default_device(writer, stream_error_sender.clone()) {
+            Ok(stream) => {
+                *current_stream = Some(stream);
+                info!("Recording resumed on the default input device");
+                EventEmitter::recording_interrupted(&writer.app_handle, &RecordingInterrupted { error, recovered: true });
+                return;
+            }
+            Err(e) => warn!("Failed to restart recording on the default device: {}", e),
+        }
+    }
+    
+    EventEmitter::recording_interrupted(&writer.app_handle, &RecordingInterrupted { error, recovered: false });
+    
+    // Stop through the normal path so the WAV is finalized and the partial recording is kept
+    let app_handle = writer.app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app_handle.try_state::<crate::AppState>() else { return };
+        if let Err(e) = crate::services::stop_recording(state, app_handle.clone()).await {
+            error!("Failed to stop interrupted recording: {}", e);
+        }
+        EventEmitter::recording_state_changed(&app_handle);
+    });
+}
+
+/// Build a new input stream on the default device feeding the existing WAV writer
+fn restart_on_default_device(writer: &WriterHandle, stream_error_sender: Sender<String>) -> Result<cpal::Stream> {
+    let (device, config) = get_audio_device_and_config(None)?;
+    if config.sample_rate.0 != writer.input_sample_rate || config.channels != writer.input_channels {
+        return Err(AppError::Audio(format!(
+            "Default device format ({} Hz, {} channels) does not match the recording",
+            config.sample_rate.0, config.channels
+        )));
+    }
+    
+    let stream = build_recording_stream(
+        &device,
+        &config,
+        writer.sample_sender.clone(),
+        writer.dropped_samples.clone(),
+        writer.app_handle.clone(),
+        stream_error_sender,
+    )?;
+    stream.play().map_err(|e| format!("Failed to start audio stream: {}", e))?;
+    Ok(stream)
 }
 
 fn handle_start_playback(
     current_stream: &mut Option<cpal::Stream>,
-    current_writer_sender: &mut Option<Sender<f32>>,
-    file_path: &PathBuf,
-    app_handle: tauri::AppHandle
+    current_writer: &mut Option<WriterHandle>,
+    file_path: &Path,
+    device_name: Option<&str>,
+    app_handle: tauri::AppHandle,
+    playback_rate: Arc<AtomicU32>,
+    options: PlaybackOptions
 ) {
     // Stop any existing stream
     if let Some(stream) = current_stream.take() {
         drop(stream);
     }
-    if let Some(sender) = current_writer_sender.take() {
-        drop(sender);
+    if let Some(writer) = current_writer.take() {
+        drop(writer);
     }
     
     // Start playback
-    match start_audio_playback(file_path, app_handle) {
+    let app_handle_clone = app_handle.clone();
+    match start_audio_playback(file_path, device_name, app_handle, playback_rate, options) {
         Ok(stream) => {
             *current_stream = Some(stream);
         }
         Err(e) => {
             error!("Failed to start playback: {}", e);
+            EventEmitter::playback_error(&app_handle_clone, &ErrorPayload::from(e));
         }
     }
 }
 
 fn handle_stop_playback(
     current_stream: &mut Option<cpal::Stream>,
-    current_writer_sender: &mut Option<Sender<f32>>
+    current_writer: &mut Option<WriterHandle>
 ) {
     // Stop playback by dropping the stream
     if let Some(stream) = current_stream.take() {
         drop(stream);
     }
-    if let Some(sender) = current_writer_sender.take() {
-        drop(sender);
+    if let Some(writer) = current_writer.take() {
+        drop(writer);
+    }
+}
+
+fn handle_pause_playback(current_stream: &Option<cpal::Stream>) {
+    // Pausing keeps the stream (and its sample position) alive so playback can resume
+    if let Some(stream) = current_stream {
+        if let Err(e) = stream.pause() {
+            error!("Failed to pause playback: {}", e);
+        }
+    }
+}
+
+fn handle_resume_playback(current_stream: &Option<cpal::Stream>) {
+    if let Some(stream) = current_stream {
+        if let Err(e) = stream.play() {
+            error!("Failed to resume playback: {}", e);
+        }
     }
 }
 
 /// Helper function to start audio recording (returns the stream and writer sender)
-fn start_audio_recording(file_path: &PathBuf) -> Result<(cpal::Stream, Sender<f32>)> {
+fn start_audio_recording(
+    file_path: &PathBuf,
+    device_name: Option<&str>,
+    app_handle: tauri::AppHandle,
+    stream_error_sender: Sender<String>
+) -> Result<(cpal::Stream, WriterHandle)> {
     // Get audio device and config first to match sample rate
-    let (device, config) = get_audio_device_and_config()?;
+    let (device, config) = get_audio_device_and_config(device_name)?;
     info!("Using audio device sample rate: {} Hz, channels: {}", config.sample_rate.0, config.channels);
     
-    // Setup WAV writer specification matching device config
+    // Setup WAV writer specification with the configured channel layout, bit depth and sample rate
+    // (the device's rate unless a recording rate is set)
+    let (record_channels, bit_depth, record_sample_rate) = app_handle.try_state::<crate::AppState>()
+        .map(|state| (*state.record_channels.lock().unwrap(), *state.wav_bit_depth.lock().unwrap(), *state.record_sample_rate.lock().unwrap()))
+        .unwrap_or_default();
+    let input_channels = config.channels.max(1);
+    let input_sample_rate = config.sample_rate.0;
+    let (bits_per_sample, sample_format) = bit_depth.spec_format();
     let spec = WavSpec {
-        channels: config.channels as u16,
-        sample_rate: config.sample_rate.0,
-        bits_per_sample: 16,
-        sample_format: SampleFormat::Int,
+        channels: record_channels.output_channels(input_channels),
+        sample_rate: record_sample_rate.unwrap_or(input_sample_rate),
+        bits_per_sample,
+        sample_format,
+    };
+    let mut resampler = if spec.sample_rate != input_sample_rate {
+        info!("Resampling recording from {} Hz to {} Hz", input_sample_rate, spec.sample_rate);
+        Some(StreamResampler::new(input_sample_rate, spec.sample_rate, spec.channels)?)
+    } else {
+        None
     };
 
     // Create WAV writer
@@ -237,105 +1028,300 @@ fn start_audio_recording(file_path: &PathBuf) -> Result<(cpal::Stream, Sender<f3
         .map_err(|e| format!("Failed to create WAV writer: {}", e))?;
     let writer = Arc::new(Mutex::new(Some(writer)));
 
-    // Create channel for audio data
-    let (sender, receiver) = unbounded::<f32>();
+    // Create a bounded channel for audio data so a stalled disk can't grow memory without
+    // limit, plus one to signal finalization
+    let (sender, receiver) = bounded::<f32>(RECORDING_BUFFER_SAMPLES);
+    let dropped_samples = Arc::new(AtomicU64::new(0));
+    let (finished_sender, finished_receiver) = bounded::<u64>(1);
+
+    // Optional noise gate, applied to every frame written
+    let mut gate = app_handle.try_state::<crate::AppState>()
+        .and_then(|state| *state.noise_gate_threshold_db.lock().unwrap())
+        .map(|threshold_db| NoiseGate::new(threshold_db, spec.sample_rate));
+    
+    // Write the pre-roll captured while idle, if any, ahead of the live audio
+    let mut remixed = Vec::with_capacity(usize::from(spec.channels));
+    let mut resampled = Vec::new();
+    let mut samples_written: u64 = 0;
+    let preroll = app_handle.try_state::<crate::AppState>()
+        .map(|state| {
+            // Captured before the gain was applied
+            let gain = f32::from_bits(state.input_gain.load(Ordering::Relaxed));
+            let mut samples = state.preroll_buffer.take(input_sample_rate, input_channels);
+            samples.iter_mut().for_each(|sample| *sample = apply_gain(*sample, gain));
+            samples
+        })
+        .unwrap_or_default();
+    if !preroll.is_empty() {
+        if let Some(writer) = writer.lock().unwrap().as_mut() {
+            samples_written = write_captured(writer, &preroll, input_channels, &mut resampler, &mut gate, &mut remixed, &mut resampled)
+                .map_err(|e| format!("Failed to write pre-roll audio: {}", e))?;
+        }
+        info!("Prepended {} ms of pre-roll audio", samples_written / u64::from(spec.channels) * 1000 / u64::from(spec.sample_rate));
+    }
 
     // Spawn writer thread
     let writer_clone = writer.clone();
     thread::spawn(move || {
+        // Samples arrive interleaved; collect whole frames so they can be remixed to the WAV layout
+        let mut frame = Vec::with_capacity(usize::from(input_channels));
         while let Ok(sample) = receiver.recv() {
-            // Convert f32 sample to i16 for WAV file
-            let amplitude = i16::MAX as f32;
-            let sample_i16 = (sample.clamp(-1.0, 1.0) * amplitude) as i16;
+            frame.push(sample);
+            if frame.len() < usize::from(input_channels) {
+                continue;
+            }
             
             if let Some(writer) = writer_clone.lock().unwrap().as_mut() {
-                if let Err(e) = writer.write_sample(sample_i16) {
-                    eprintln!("Failed to write audio sample: {}", e);
-                    break;
+                match write_captured(writer, &frame, input_channels, &mut resampler, &mut gate, &mut remixed, &mut resampled) {
+                    Ok(written) => samples_written += written,
+                    Err(e) => {
+                        eprintln!("Failed to write audio sample: {}", e);
+                        break;
+                    }
                 }
             }
+            frame.clear();
         }
         
-        // Finalize the file when channel closes
-        if let Some(writer) = writer_clone.lock().unwrap().take() {
+        // Finalize the file when channel closes, after the audio still buffered in the resampler
+        if let Some(mut writer) = writer_clone.lock().unwrap().take() {
+            if let Some(resampler) = resampler.as_mut() {
+                resampled.clear();
+                let flushed = resampler.flush(&mut resampled)
+                    .and_then(|()| write_remixed(&mut writer, &resampled, spec.channels, spec.channels, &mut gate, &mut remixed)
+                        .map_err(|e| AppError::Audio(e.to_string())));
+                match flushed {
+                    Ok(written) => samples_written += written,
+                    Err(e) => eprintln!("Failed to write resampled audio: {}", e),
+                }
+            }
             if let Err(e) = writer.finalize() {
                 eprintln!("Failed to finalize WAV file: {}", e);
             }
         }
+        // Duration comes from the audio actually written, not wall-clock time
+        let frames = samples_written / spec.channels as u64;
+        let _ = finished_sender.send(frames * 1000 / spec.sample_rate as u64);
     });
 
-    // Create audio stream
-    let sender_clone = sender.clone();
+    let stream = build_recording_stream(&device, &config, sender.clone(), dropped_samples.clone(), app_handle.clone(), stream_error_sender)?;
+
+    // Start the stream
+    stream.play().map_err(|e| format!("Failed to start audio stream: {}", e))?;
+    
+    Ok((stream, WriterHandle {
+        sample_sender: sender,
+        finished: finished_receiver,
+        dropped_samples,
+        input_channels,
+        input_sample_rate,
+        app_handle,
+        restarted: false,
+    }))
+}
+
+/// Build an input stream that feeds samples to the writer thread and emits input levels
+///
+/// The callback never blocks: buffers that don't fit in the channel are dropped whole (keeping
+/// interleaved channels aligned), counted in `dropped_samples` and reported as `recording-degraded`.
+fn build_recording_stream(
+    device: &Device,
+    config: &StreamConfig,
+    sample_sender: Sender<f32>,
+    dropped_samples: Arc<AtomicU64>,
+    app_handle: tauri::AppHandle,
+    stream_error_sender: Sender<String>
+) -> Result<cpal::Stream> {
+    let sender_clone = sample_sender;
+    let input_gain = app_handle.try_state::<crate::AppState>()
+        .map(|state| state.input_gain.clone())
+        .unwrap_or_else(|| Arc::new(AtomicU32::new(DEFAULT_INPUT_GAIN.to_bits())));
+    let capture_paused = app_handle.try_state::<crate::AppState>()
+        .map(|state| state.capture_paused.clone())
+        .unwrap_or_default();
+    let mut peak_level = 0.0f32;
+    let mut last_level_emit = Instant::now();
+    let mut last_degraded_emit: Option<Instant> = None;
     let stream = device.build_input_stream(
-        &config,
+        config,
         move |data: &[f32], _: &cpal::InputCallbackInfo| {
-            // Send audio data to writer thread
-            for &sample in data.iter() {
-                if sender_clone.send(sample).is_err() {
-                    break;
+            // Audio captured while paused is discarded, so the file only holds active time
+            if capture_paused.load(Ordering::Relaxed) {
+                return;
+            }
+            let gain = f32::from_bits(input_gain.load(Ordering::Relaxed));
+            
+            // Send audio data to writer thread, dropping the buffer if the writer is behind
+            let free = sender_clone.capacity().map_or(usize::MAX, |capacity| capacity.saturating_sub(sender_clone.len()));
+            if free >= data.len() {
+                for &sample in data.iter() {
+                    if sender_clone.try_send(apply_gain(sample, gain)).is_err() {
+                        break;
+                    }
                 }
+            } else {
+                let total = dropped_samples.fetch_add(data.len() as u64, Ordering::Relaxed) + data.len() as u64;
+                let throttled = last_degraded_emit
+                    .is_some_and(|last| last.elapsed() < Duration::from_millis(RECORDING_DEGRADED_EMIT_INTERVAL_MS));
+                if !throttled {
+                    EventEmitter::recording_degraded(&app_handle, &RecordingDegraded { dropped_samples: total });
+                    last_degraded_emit = Some(Instant::now());
+                }
+            }
+            
+            // Track the peak amplitude and emit it to the frontend at a throttled rate
+            let buffer_peak = data.iter().fold(0.0f32, |peak, &sample| peak.max(apply_gain(sample, gain).abs()));
+            peak_level = peak_level.max(buffer_peak);
+            if last_level_emit.elapsed() >= Duration::from_millis(AUDIO_LEVEL_EMIT_INTERVAL_MS) {
+                EventEmitter::audio_level(&app_handle, peak_level.clamp(0.0, 1.0));
+                peak_level = 0.0;
+                last_level_emit = Instant::now();
             }
         },
-        |err| {
+        move |err| {
             eprintln!("Audio stream error: {}", err);
+            // Hand the error to the audio manager thread; a full channel means one is already pending
+            let _ = stream_error_sender.try_send(err.to_string());
         },
         None,
     ).map_err(|e| format!("Failed to build audio stream: {}", e))?;
+    
+    Ok(stream)
+}
 
-    // Start the stream
-    stream.play().map_err(|e| format!("Failed to start audio stream: {}", e))?;
+/// Amplify a captured sample by the input gain, clipping to the valid range
+fn apply_gain(sample: f32, gain: f32) -> f32 {
+    (sample * gain).clamp(-1.0, 1.0)
+}
+
+/// Load a recording as interleaved f32 samples, decoding compressed formats with FFmpeg
+fn load_playback_samples(file_path: &Path, app_handle: &tauri::AppHandle) -> Result<(Vec<f32>, WavSpec)> {
+    if AudioFormat::from_path(file_path).is_none() {
+        return read_wav_samples(file_path);
+    }
     
-    Ok((stream, sender))
+    let decoded_path = AudioConverter::decode_to_wav(file_path, app_handle)
+        .map_err(|e| format!("Failed to decode {} for playback: {}", file_path.display(), e))?;
+    let result = read_wav_samples(&decoded_path);
+    let _ = std::fs::remove_file(&decoded_path);
+    result
+}
+
+/// Read a WAV file of any integer bit depth or 32-bit float into samples normalized to [-1.0, 1.0]
+fn read_wav_samples(file_path: &Path) -> Result<(Vec<f32>, WavSpec)> {
+    let mut reader = hound::WavReader::open(file_path)
+        .map_err(|e| format!("Failed to open WAV file: {}", e))?;
+    let spec = reader.spec();
+    
+    let samples = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>()
+            .map(|s| s.unwrap_or(0.0))
+            .collect(),
+        SampleFormat::Int => {
+            let full_scale = (1i64 << (spec.bits_per_sample.clamp(1, 32) - 1)) as f32;
+            reader.samples::<i32>()
+                .map(|s| s.unwrap_or(0) as f32 / full_scale)
+                .collect()
+        }
+    };
+    
+    Ok((samples, spec))
+}
+
+/// Linearly interpolate one interleaved frame between `index` and `next_index`, replacing `out`
+fn interpolate_frame(samples: &[f32], channels: usize, index: usize, next_index: usize, fraction: f32, out: &mut Vec<f32>) {
+    out.clear();
+    for channel in 0..channels {
+        let current = samples[index * channels + channel];
+        let next = samples[next_index * channels + channel];
+        out.push(current + (next - current) * fraction);
+    }
 }
 
 /// Helper function to start audio playback (returns the playback stream)
-fn start_audio_playback(file_path: &PathBuf, app_handle: tauri::AppHandle) -> Result<cpal::Stream> {
-    // Get audio device and config for output
+fn start_audio_playback(
+    file_path: &Path,
+    device_name: Option<&str>,
+    app_handle: tauri::AppHandle,
+    playback_rate: Arc<AtomicU32>,
+    options: PlaybackOptions
+) -> Result<cpal::Stream> {
+    // Use the selected output device, or the default one
     let host = cpal::default_host();
-    let device = host.default_output_device()
+    let device = find_output_device(&host, device_name)
         .ok_or_else(|| "No output device available".to_string())?;
     
-    // Read the WAV file to get its configuration
-    let mut reader = hound::WavReader::open(file_path)
-        .map_err(|e| format!("Failed to open WAV file: {}", e))?;
-    
-    let wav_spec = reader.spec();
+    // Decode the whole file up front
+    let (samples, wav_spec) = load_playback_samples(file_path, &app_handle)?;
     
-    // Create output config matching the WAV file
+    // Play at the WAV's rate but with the device's channel layout, remixing each frame
+    let output_channels = device.default_output_config()
+        .map(|config| config.channels())
+        .unwrap_or(wav_spec.channels)
+        .max(1);
     let config = cpal::StreamConfig {
-        channels: wav_spec.channels,
+        channels: output_channels,
         sample_rate: cpal::SampleRate(wav_spec.sample_rate),
         buffer_size: cpal::BufferSize::Default,
     };
     
-    // Read all samples from WAV file
-    let samples: Vec<f32> = reader.samples::<i16>()
-        .map(|s| s.unwrap_or(0) as f32 / i16::MAX as f32)
-        .collect();
-    
     let samples = Arc::new(samples);
-    let sample_index = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let channels = usize::from(wav_spec.channels.max(1));
+    let output_channels = usize::from(output_channels);
+    let total_frames = samples.len() / channels;
+    let (start_frame, end_frame) = options.frame_range(wav_spec.sample_rate, total_frames);
+    let loop_playback = options.loop_playback;
     let playback_finished = Arc::new(std::sync::atomic::AtomicBool::new(false));
     
+    // Report the position back to `AppState` so the UI can restore its scrubber
+    let progress = app_handle.try_state::<crate::AppState>()
+        .map(|state| state.playback_progress.clone())
+        .unwrap_or_default();
+    let playback_volume = app_handle.try_state::<crate::AppState>()
+        .map(|state| state.playback_volume.clone())
+        .unwrap_or_else(|| Arc::new(AtomicU32::new(DEFAULT_PLAYBACK_VOLUME.to_bits())));
+    progress.start(wav_spec.sample_rate, total_frames, start_frame);
+    
     // Create output stream
     let samples_clone = samples.clone();
-    let sample_index_clone = sample_index.clone();
     let playback_finished_clone = playback_finished.clone();
     let app_handle_clone = app_handle.clone();
+    // Current read position in frames; fractional when playing at a non-1.0 rate
+    let mut position = start_frame as f64;
+    let mut source_frame = Vec::with_capacity(channels);
+    let mut remixed = Vec::with_capacity(output_channels);
     
     let stream = device.build_output_stream(
         &config,
         move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-            for frame in data.iter_mut() {
-                let index = sample_index_clone.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                if index < samples_clone.len() {
-                    *frame = samples_clone[index];
+            let rate = f64::from(f32::from_bits(playback_rate.load(Ordering::Relaxed)));
+            let volume = f32::from_bits(playback_volume.load(Ordering::Relaxed));
+            
+            for frame in data.chunks_mut(output_channels) {
+                if loop_playback && end_frame > start_frame && position >= end_frame as f64 {
+                    // Wrap around without a gap, keeping the fractional offset
+                    position = start_frame as f64 + (position - start_frame as f64) % (end_frame - start_frame) as f64;
+                }
+                
+                let index = position as usize;
+                if index < end_frame {
+                    // Linearly interpolate between neighbouring frames
+                    let fraction = (position - index as f64) as f32;
+                    let next_index = (index + 1).min(end_frame - 1);
+                    interpolate_frame(&samples_clone, channels, index, next_index, fraction, &mut source_frame);
+                    remixed.clear();
+                    remix_frame(&source_frame, output_channels, &mut remixed);
+                    for (output, sample) in frame.iter_mut().zip(&remixed) {
+                        *output = sample * volume;
+                    }
+                    position += rate;
+                    progress.frame.store(position as usize, Ordering::Relaxed);
                 } else {
-                    *frame = 0.0; // Silence when playback is done
+                    frame.fill(0.0); // Silence when playback is done
                     
                     // Check if this is the first time we've finished
-                    if !playback_finished_clone.load(std::sync::atomic::Ordering::Relaxed) {
-                        playback_finished_clone.store(true, std::sync::atomic::Ordering::Relaxed);
+                    if !playback_finished_clone.load(Ordering::Relaxed) {
+                        playback_finished_clone.store(true, Ordering::Relaxed);
                         // Emit event to frontend that playback finished
                         EventEmitter::playback_finished(&app_handle_clone);
                     }
@@ -352,4 +1338,224 @@ fn start_audio_playback(file_path: &PathBuf, app_handle: tauri::AppHandle) -> Re
     stream.play().map_err(|e| format!("Failed to start playback: {}", e))?;
     
     Ok(stream)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_wav(path: &Path, spec: WavSpec, write: impl FnOnce(&mut WavWriter<std::io::BufWriter<std::fs::File>>)) {
+        let mut writer = WavWriter::create(path, spec).unwrap();
+        write(&mut writer);
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_playback_frame_range_clamps_to_file_bounds() {
+        let range = |start_seconds, end_seconds| {
+            PlaybackOptions { loop_playback: false, start_seconds, end_seconds }.frame_range(1000, 5000)
+        };
+        assert_eq!(range(None, None), (0, 5000));
+        assert_eq!(range(Some(1.0), Some(2.5)), (1000, 2500));
+        assert_eq!(range(Some(-3.0), Some(60.0)), (0, 5000));
+        assert_eq!(range(Some(4.0), Some(1.0)), (4000, 4000));
+    }
+
+    #[test]
+    fn test_noise_gate_fades_quiet_input_without_jumps() {
+        let mut gate = NoiseGate::new(-40.0, 1000);
+        
+        // Loud input (-6 dBFS) stays untouched
+        let mut frame = [0.5];
+        for _ in 0..100 {
+            frame = [0.5];
+            gate.process(&mut frame);
+        }
+        assert!((frame[0] - 0.5).abs() < 1e-3);
+        
+        // Quiet input (-60 dBFS) fades out gradually rather than cutting to zero
+        let mut gains = Vec::new();
+        for _ in 0..1000 {
+            let mut frame = [0.001];
+            gate.process(&mut frame);
+            gains.push(frame[0] / 0.001);
+        }
+        assert!(gains.windows(2).all(|pair| pair[0] - pair[1] < 0.05), "gain must not jump");
+        assert!(gains[0] > 0.9);
+        assert!(*gains.last().unwrap() < 0.01);
+    }
+
+    #[test]
+    fn test_is_loopback_device_name() {
+        assert!(is_loopback_device_name("BlackHole 2ch"));
+        assert!(is_loopback_device_name("Stereo Mix (Realtek High Definition Audio)"));
+        assert!(is_loopback_device_name("Monitor of Built-in Audio Analog Stereo"));
+        assert!(!is_loopback_device_name("MacBook Pro Microphone"));
+    }
+
+    #[test]
+    fn test_apply_gain_clips() {
+        assert_eq!(apply_gain(0.25, 1.0), 0.25);
+        assert_eq!(apply_gain(0.25, 2.0), 0.5);
+        assert_eq!(apply_gain(0.75, 2.0), 1.0);
+        assert_eq!(apply_gain(-0.75, 2.0), -1.0);
+    }
+
+    #[test]
+    fn test_preroll_buffer_keeps_latest_frames() {
+        let buffer = PrerollBuffer::default();
+        assert!(buffer.take(10, 2).is_empty(), "nothing is kept before the buffer is sized");
+        assert!(buffer.reset(10, 2, 0).is_none());
+        
+        // One second of 2-channel audio at 2 Hz = 4 samples
+        let ring = buffer.reset(2, 2, 1).unwrap();
+        ring.push(&[0.1, 0.2, 0.3, 0.4]);
+        ring.push(&[0.5, 0.6]);
+        assert_eq!(buffer.take(2, 2), vec![0.3, 0.4, 0.5, 0.6]);
+        assert!(buffer.take(2, 2).is_empty());
+        
+        // A buffer larger than the ring keeps only its latest frames
+        ring.push(&[1.0, 1.1, 1.2, 1.3, 1.4, 1.5]);
+        assert_eq!(buffer.take(2, 2), vec![1.2, 1.3, 1.4, 1.5]);
+        
+        // Audio in another format can't be prepended
+        ring.push(&[0.1, 0.2]);
+        assert!(buffer.take(48000, 2).is_empty());
+    }
+
+    #[test]
+    fn test_remix_frame_between_layouts() {
+        let remix = |frame: &[f32], out_channels| {
+            let mut out = Vec::new();
+            remix_frame(frame, out_channels, &mut out);
+            out
+        };
+        assert_eq!(remix(&[0.5, -0.25], 2), vec![0.5, -0.25]);
+        assert_eq!(remix(&[0.5, -0.25], 1), vec![0.125]);
+        assert_eq!(remix(&[0.3], 2), vec![0.3, 0.3]);
+        assert_eq!(remix(&[0.1, 0.2, 0.3, 0.4], 2), vec![0.1, 0.2]);
+        assert_eq!(RecordChannels::DeviceNative.output_channels(4), 4);
+        assert_eq!(RecordChannels::Stereo.output_channels(1), 2);
+    }
+
+    #[test]
+    fn test_interpolated_playback_frame_remixes_to_device_channels() {
+        // Mono file: frames 0.0, 1.0
+        let samples = [0.0, 1.0];
+        let mut frame = Vec::new();
+        interpolate_frame(&samples, 1, 0, 1, 0.25, &mut frame);
+        assert_eq!(frame, vec![0.25]);
+        
+        // Played on a stereo device, the mono sample feeds both channels
+        let mut remixed = Vec::new();
+        remix_frame(&frame, 2, &mut remixed);
+        assert_eq!(remixed, vec![0.25, 0.25]);
+        
+        // Stereo file interpolates each channel independently
+        let samples = [0.0, 1.0, 1.0, 0.0];
+        interpolate_frame(&samples, 2, 0, 1, 0.5, &mut frame);
+        assert_eq!(frame, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_playback_progress_reports_milliseconds() {
+        let progress = PlaybackProgress::default();
+        assert_eq!(progress.position_ms(), 0);
+        
+        progress.start(16000, 48000, 8000);
+        assert_eq!(progress.position_ms(), 500);
+        assert_eq!(progress.duration_ms(), 3000);
+        
+        // Positions past the end (e.g. mid-wrap) are clamped
+        progress.frame.store(50000, Ordering::Relaxed);
+        assert_eq!(progress.position_ms(), 3000);
+    }
+
+    #[test]
+    fn test_read_wav_samples_normalizes_24_bit_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("24bit.wav");
+        let spec = WavSpec { channels: 1, sample_rate: 48000, bits_per_sample: 24, sample_format: SampleFormat::Int };
+        write_wav(&path, spec, |writer| {
+            writer.write_sample(1i32 << 22).unwrap();
+            writer.write_sample(-(1i32 << 23)).unwrap();
+        });
+        
+        let (samples, read_spec) = read_wav_samples(&path).unwrap();
+        assert_eq!(read_spec.bits_per_sample, 24);
+        assert_eq!(samples, vec![0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_read_wav_samples_reads_float_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("float.wav");
+        let spec = WavSpec { channels: 2, sample_rate: 44100, bits_per_sample: 32, sample_format: SampleFormat::Float };
+        write_wav(&path, spec, |writer| {
+            writer.write_sample(0.25f32).unwrap();
+            writer.write_sample(-0.75f32).unwrap();
+        });
+        
+        let (samples, _) = read_wav_samples(&path).unwrap();
+        assert_eq!(samples, vec![0.25, -0.75]);
+    }
+
+    #[test]
+    fn test_write_remixed_round_trips_each_bit_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        for bit_depth in [WavBitDepth::Int16, WavBitDepth::Int24, WavBitDepth::Float32] {
+            let path = dir.path().join(format!("{:?}.wav", bit_depth));
+            let (bits_per_sample, sample_format) = bit_depth.spec_format();
+            let spec = WavSpec { channels: 1, sample_rate: 48000, bits_per_sample, sample_format };
+            write_wav(&path, spec, |writer| {
+                write_remixed(writer, &[0.5, -0.25, 1.5], 1, 1, &mut None, &mut Vec::new()).unwrap();
+            });
+            
+            let (samples, read_spec) = read_wav_samples(&path).unwrap();
+            assert_eq!(read_spec.bits_per_sample, bits_per_sample);
+            let expected = if bit_depth == WavBitDepth::Float32 { [0.5, -0.25, 1.5] } else { [0.5, -0.25, 1.0] };
+            for (sample, expected) in samples.iter().zip(expected) {
+                assert!((sample - expected).abs() < 1e-4, "{:?}: {} != {}", bit_depth, sample, expected);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_write_remixed_keeps_stereo_channels_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = WavSpec { channels: 2, sample_rate: 48000, bits_per_sample: 32, sample_format: SampleFormat::Float };
+        
+        // Stereo capture stays as is; a mono capture is duplicated into both channels
+        let stereo_path = dir.path().join("stereo.wav");
+        write_wav(&stereo_path, spec, |writer| {
+            assert_eq!(write_remixed(writer, &[0.5, -0.25, 0.1, 0.2], 2, 2, &mut None, &mut Vec::new()).unwrap(), 4);
+            assert_eq!(write_remixed(writer, &[0.3], 1, 2, &mut None, &mut Vec::new()).unwrap(), 2);
+        });
+        
+        let (samples, read_spec) = read_wav_samples(&stereo_path).unwrap();
+        assert_eq!(read_spec.channels, 2);
+        assert_eq!(samples, vec![0.5, -0.25, 0.1, 0.2, 0.3, 0.3]);
+    }
+    
+    #[test]
+    fn test_stream_resampler_keeps_length_and_timing() {
+        // One second of a 440 Hz tone on the left channel, silence on the right, at 44.1 kHz
+        let tone = |frame: usize, rate: f32| (2.0 * std::f32::consts::PI * 440.0 * frame as f32 / rate).sin() * 0.5;
+        let input: Vec<f32> = (0..44100).flat_map(|frame| [tone(frame, 44100.0), 0.0]).collect();
+        
+        let mut resampler = StreamResampler::new(44100, 48000, 2).unwrap();
+        let mut output = Vec::new();
+        // Arbitrary piece sizes, as delivered by the input callback
+        for piece in input.chunks(2 * 1000) {
+            resampler.process(piece, &mut output).unwrap();
+        }
+        resampler.flush(&mut output).unwrap();
+        
+        assert_eq!(output.len(), 2 * 48000);
+        for frame in (1000..47000).step_by(97) {
+            let left = output[2 * frame];
+            assert!((left - tone(frame, 48000.0)).abs() < 0.01, "frame {}: {} != {}", frame, left, tone(frame, 48000.0));
+            assert!(output[2 * frame + 1].abs() < 0.01);
+        }
+    }
+}
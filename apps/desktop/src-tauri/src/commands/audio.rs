@@ -0,0 +1,91 @@
+use tauri::{State, AppHandle};
+use crate::AppState;
+use crate::audio_system::{AudioDeviceInfo, RecordChannels, WavBitDepth};
+use crate::services;
+use crate::services::system_service::{AudioDiagnostics, SystemStatus};
+use crate::services::permission_service::MicrophonePermission;
+
+#[tauri::command]
+pub async fn list_input_devices() -> Result<Vec<AudioDeviceInfo>, String> {
+    services::list_input_devices().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_input_device(state: State<'_, AppState>, app_handle: AppHandle, name: String) -> Result<(), String> {
+    services::set_input_device(state, app_handle, name).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_output_devices() -> Result<Vec<AudioDeviceInfo>, String> {
+    services::list_output_devices().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_output_device(state: State<'_, AppState>, app_handle: AppHandle, name: String) -> Result<(), String> {
+    services::set_output_device(state, app_handle, name).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_record_channels(state: State<'_, AppState>, app_handle: AppHandle, channels: RecordChannels) -> Result<(), String> {
+    services::set_record_channels(state, app_handle, channels).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_wav_bit_depth(state: State<'_, AppState>, app_handle: AppHandle, bit_depth: WavBitDepth) -> Result<(), String> {
+    services::set_wav_bit_depth(state, app_handle, bit_depth).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_record_sample_rate(state: State<'_, AppState>, app_handle: AppHandle, sample_rate: Option<u32>) -> Result<(), String> {
+    services::set_record_sample_rate(state, app_handle, sample_rate).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_input_gain(state: State<'_, AppState>, app_handle: AppHandle, gain: f32) -> Result<(), String> {
+    services::set_input_gain(state, app_handle, gain).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_noise_gate(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool, threshold_db: Option<f32>) -> Result<(), String> {
+    services::set_noise_gate(state, app_handle, enabled, threshold_db).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_preroll(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool, seconds: Option<u32>) -> Result<(), String> {
+    services::set_preroll(state, app_handle, enabled, seconds).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn is_ffmpeg_available(state: State<'_, AppState>) -> Result<bool, String> {
+    services::is_ffmpeg_available(state).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_system_status(state: State<'_, AppState>, app_handle: AppHandle) -> Result<SystemStatus, String> {
+    services::get_system_status(state, app_handle).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_audio_diagnostics(state: State<'_, AppState>, app_handle: AppHandle) -> Result<AudioDiagnostics, String> {
+    services::get_audio_diagnostics(state, app_handle).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_recent_logs(app_handle: AppHandle, lines: usize) -> Result<Vec<String>, String> {
+    services::get_recent_logs(app_handle, lines).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn open_log_folder(app_handle: AppHandle) -> Result<(), String> {
+    services::open_log_folder(app_handle).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn microphone_permission_status() -> Result<MicrophonePermission, String> {
+    services::microphone_permission_status().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn request_microphone_permission() -> Result<MicrophonePermission, String> {
+    services::request_microphone_permission().await.map_err(|e| e.to_string())
+}
@@ -1,4 +1,6 @@
-use tauri::AppHandle;
+use tauri::{AppHandle, State};
+use tracing::{info, warn};
+use crate::AppState;
 use crate::app_config::AppConfig;
 
 #[tauri::command]
@@ -14,8 +16,8 @@ pub async fn update_config(
 ) -> Result<AppConfig, String> {
     let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
     
-    config.web_app_url = web_app_url;
-    config.api_key = api_key;
+    config.web_app_url = AppConfig::normalize_web_app_url(&web_app_url).map_err(|e| e.to_string())?;
+    config.set_api_key(api_key)?;
     
     config.save(&app_handle).await?;
     
@@ -28,10 +30,63 @@ pub async fn update_config(
 }
 
 #[tauri::command]
-pub async fn reset_config(app_handle: AppHandle) -> Result<AppConfig, String> {
-    let config = AppConfig::default();
+pub async fn set_start_hidden(app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.start_hidden = enabled;
+    config.save(&app_handle).await
+}
+
+#[tauri::command]
+pub async fn set_local_api(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool, port: Option<u16>) -> Result<AppConfig, String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    if let Some(port) = port {
+        if port == 0 {
+            return Err("Local API port must be between 1 and 65535".to_string());
+        }
+        config.local_api_port = port;
+    }
+    config.enable_local_api = enabled;
+    config.ensure_local_api_token();
+    
+    // Start (or stop) the server first so a port that can't be bound isn't saved as enabled
+    crate::local_api::apply(&state, &app_handle, &config).await?;
+    config.save(&app_handle).await?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn reset_config(state: State<'_, AppState>, app_handle: AppHandle) -> Result<AppConfig, String> {
+    let mut config = AppConfig::default();
+    if let Err(e) = config.set_api_key(None) {
+        warn!("{}", e);
+    }
+    if let Err(e) = config.set_upload_api_key(None) {
+        warn!("{}", e);
+    }
     config.save(&app_handle).await?;
     
-    println!("Reset config to defaults");
+    // Apply the defaults to running components
+    *state.input_device.lock().unwrap() = config.input_device.clone();
+    *state.output_device.lock().unwrap() = config.output_device.clone();
+    *state.record_channels.lock().unwrap() = config.record_channels;
+    *state.wav_bit_depth.lock().unwrap() = config.wav_bit_depth;
+    *state.record_sample_rate.lock().unwrap() = config.record_sample_rate();
+    *state.noise_gate_threshold_db.lock().unwrap() = config.noise_gate();
+    state.input_gain.store(config.input_gain.to_bits(), std::sync::atomic::Ordering::Relaxed);
+    state.playback_volume.store(config.playback_volume.to_bits(), std::sync::atomic::Ordering::Relaxed);
+    state.meeting_detector.update_settings(config.detection_settings());
+    *state.meeting_action.lock().unwrap() = config.meeting_action;
+    *state.recordings_dir.lock().unwrap() = config.recordings_dir.clone();
+    if let Err(e) = crate::services::apply_meeting_detection(&state, &app_handle, config.meeting_detection_enabled) {
+        warn!("Failed to restart meeting detection: {}", e);
+    }
+    if let Err(e) = crate::services::apply_preroll(&state, config.preroll_enabled, config.preroll_secs) {
+        warn!("Failed to stop pre-roll: {}", e);
+    }
+    if let Err(e) = crate::local_api::apply(&state, &app_handle, &config).await {
+        warn!("Failed to stop local API: {}", e);
+    }
+    
+    info!("Reset config to defaults");
     Ok(config)
 }
\ No newline at end of file
@@ -1,10 +1,11 @@
-use tauri::State;
-use crate::{AppState, meeting_detector::MeetingState};
+use std::collections::HashSet;
+use tauri::{State, AppHandle};
+use crate::{AppState, meeting_detector::{DetectionProbe, MeetingAction, MeetingApp, MeetingState}};
 use crate::services;
 
 #[tauri::command]
-pub async fn start_meeting_detection(state: State<'_, AppState>) -> Result<(), String> {
-    services::start_meeting_detection(state).await
+pub async fn start_meeting_detection(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
+    services::start_meeting_detection(state, app_handle).await
 }
 
 #[tauri::command]
@@ -12,7 +13,47 @@ pub async fn stop_meeting_detection(state: State<'_, AppState>) -> Result<(), St
     services::stop_meeting_detection(state).await
 }
 
+#[tauri::command]
+pub async fn set_meeting_detection_enabled(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    services::set_meeting_detection_enabled(state, app_handle, enabled).await
+}
+
 #[tauri::command]
 pub async fn get_meeting_state(state: State<'_, AppState>) -> Result<MeetingState, String> {
     services::get_meeting_state(state).await
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn is_meeting_detection_active(state: State<'_, AppState>) -> Result<bool, String> {
+    services::is_meeting_detection_active(state).await
+}
+
+#[tauri::command]
+pub async fn probe_meeting_detection(state: State<'_, AppState>) -> Result<DetectionProbe, String> {
+    services::probe_meeting_detection(state).await
+}
+
+#[tauri::command]
+pub async fn set_custom_meeting_patterns(state: State<'_, AppState>, app_handle: AppHandle, patterns: Vec<String>) -> Result<(), String> {
+    services::set_custom_meeting_patterns(state, app_handle, patterns).await
+}
+
+#[tauri::command]
+pub async fn set_meeting_end_grace(state: State<'_, AppState>, app_handle: AppHandle, seconds: u64) -> Result<(), String> {
+    services::set_meeting_end_grace(state, app_handle, seconds).await
+}
+
+#[tauri::command]
+pub async fn set_scan_browser_urls(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    services::set_scan_browser_urls(state, app_handle, enabled).await
+}
+
+#[tauri::command]
+pub async fn set_detect_apps(state: State<'_, AppState>, app_handle: AppHandle, apps: HashSet<MeetingApp>) -> Result<(), String> {
+    services::set_detect_apps(state, app_handle, apps).await
+}
+
+#[tauri::command]
+pub async fn set_meeting_action(state: State<'_, AppState>, app_handle: AppHandle, action: MeetingAction) -> Result<(), String> {
+    services::set_meeting_action(state, app_handle, action).await
+}
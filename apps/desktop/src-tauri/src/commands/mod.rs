@@ -2,8 +2,10 @@ pub mod recording;
 pub mod meeting;
 pub mod transcription;
 pub mod config;
+pub mod audio;
 
 pub use recording::*;
 pub use meeting::*;
 pub use transcription::*;
-pub use config::{get_config, update_config, reset_config};
\ No newline at end of file
+pub use audio::*;
+pub use config::{get_config, update_config, reset_config, set_start_hidden, set_local_api};
\ No newline at end of file
@@ -1,68 +1,294 @@
+use std::path::PathBuf;
 use tauri::{State, AppHandle};
-use crate::{AppState, Recording};
+use crate::{AppState, DeleteOutcome, PlaybackInfo, Recording, RecordingDetail, RecordingInfo, RecordingMatch, RecordingSource, RecordingsAudit, RecordingsPage, SortOrder};
 use crate::services;
+use crate::error::ErrorPayload;
+use crate::services::audio_converter::AudioFormat;
+use crate::audio_system::PlaybackOptions;
+use crate::path_manager::RecordingLayout;
 
 #[tauri::command]
-pub async fn start_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
-    services::start_recording(state, app_handle).await.map_err(|e| e.to_string())
+pub async fn start_recording(state: State<'_, AppState>, app_handle: AppHandle, start_delay_secs: Option<u32>) -> Result<(), ErrorPayload> {
+    services::start_recording(state, app_handle, start_delay_secs, RecordingSource::Manual).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn pause_recording(state: State<'_, AppState>) -> Result<(), String> {
-    services::pause_recording(state).await.map_err(|e| e.to_string())
+pub async fn pause_recording(state: State<'_, AppState>) -> Result<(), ErrorPayload> {
+    services::pause_recording(state).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn resume_recording(state: State<'_, AppState>) -> Result<(), String> {
-    services::resume_recording(state).await.map_err(|e| e.to_string())
+pub async fn resume_recording(state: State<'_, AppState>) -> Result<(), ErrorPayload> {
+    services::resume_recording(state).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn stop_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Recording, String> {
-    services::stop_recording(state, app_handle).await.map_err(|e| e.to_string())
+pub async fn stop_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Recording, ErrorPayload> {
+    services::stop_recording(state, app_handle).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn get_recent_recordings(state: State<'_, AppState>) -> Result<Vec<Recording>, String> {
-    services::get_recent_recordings(state).await.map_err(|e| e.to_string())
+pub async fn cancel_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), ErrorPayload> {
+    services::cancel_recording(state, app_handle).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn get_recording_state(state: State<'_, AppState>) -> Result<String, String> {
-    services::get_recording_state(state).await.map_err(|e| e.to_string())
+pub async fn get_recordings_page(state: State<'_, AppState>, app_handle: AppHandle, offset: usize, limit: usize, sort: Option<SortOrder>) -> Result<RecordingsPage, ErrorPayload> {
+    services::get_recordings_page(state, app_handle, offset, limit, sort.unwrap_or_default()).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn toggle_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String, String> {
-    services::toggle_recording(state, app_handle).await.map_err(|e| e.to_string())
+pub async fn get_recent_recordings(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Vec<Recording>, ErrorPayload> {
+    services::get_recent_recordings(state, app_handle).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn play_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<(), String> {
-    services::play_recording(state, app_handle, recording_id).await.map_err(|e| e.to_string())
+pub async fn get_recording_state(state: State<'_, AppState>) -> Result<String, ErrorPayload> {
+    services::get_recording_state(state).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn stop_playback(state: State<'_, AppState>) -> Result<(), String> {
-    services::stop_playback(state).await.map_err(|e| e.to_string())
+pub async fn get_recording_info(state: State<'_, AppState>) -> Result<RecordingInfo, ErrorPayload> {
+    services::get_recording_info(state).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn get_playback_state(state: State<'_, AppState>) -> Result<String, String> {
-    services::get_playback_state(state).await.map_err(|e| e.to_string())
+pub async fn toggle_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String, ErrorPayload> {
+    services::toggle_recording(state, app_handle).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn delete_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<(), String> {
-    services::delete_recording(state, app_handle, recording_id).await.map_err(|e| e.to_string())
+pub async fn play_recording(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    recording_id: String,
+    r#loop: Option<bool>,
+    start_seconds: Option<f64>,
+    end_seconds: Option<f64>,
+) -> Result<(), ErrorPayload> {
+    let options = PlaybackOptions { loop_playback: r#loop.unwrap_or(false), start_seconds, end_seconds };
+    services::play_recording(state, app_handle, recording_id, options).await.map_err(ErrorPayload::from)
 }
 
 #[tauri::command]
-pub async fn load_recordings_from_disk(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
-    services::load_recordings_from_disk(state, app_handle).await.map_err(|e| e.to_string())
+pub async fn stop_playback(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), ErrorPayload> {
+    services::stop_playback(state).await.map_err(ErrorPayload::from)?;
+    let _ = crate::tray::refresh_tray_menu(&app_handle);
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn open_recordings_folder(app_handle: AppHandle) -> Result<(), String> {
-    services::open_recordings_folder(app_handle).await.map_err(|e| e.to_string())
-}
\ No newline at end of file
+pub async fn pause_playback(state: State<'_, AppState>) -> Result<(), ErrorPayload> {
+    services::pause_playback(state).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn resume_playback(state: State<'_, AppState>) -> Result<(), ErrorPayload> {
+    services::resume_playback(state).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_playback_speed(state: State<'_, AppState>, rate: f32) -> Result<(), ErrorPayload> {
+    services::set_playback_speed(state, rate).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_playback_volume(state: State<'_, AppState>, app_handle: AppHandle, volume: f32) -> Result<(), ErrorPayload> {
+    services::set_playback_volume(state, app_handle, volume).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn get_playback_state(state: State<'_, AppState>) -> Result<String, ErrorPayload> {
+    services::get_playback_state(state).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn get_playback_info(state: State<'_, AppState>) -> Result<Option<PlaybackInfo>, ErrorPayload> {
+    services::get_playback_info(state).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn delete_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<(), ErrorPayload> {
+    services::delete_recording(state, app_handle, recording_id).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn get_total_storage_used(state: State<'_, AppState>) -> Result<u64, ErrorPayload> {
+    services::get_total_storage_used(state).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn delete_recordings(state: State<'_, AppState>, app_handle: AppHandle, recording_ids: Vec<String>) -> Result<Vec<DeleteOutcome>, ErrorPayload> {
+    services::delete_recordings(state, app_handle, recording_ids).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn get_recording_detail(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<RecordingDetail, ErrorPayload> {
+    services::get_recording_detail(state, app_handle, recording_id).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn find_duplicates(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Vec<Vec<String>>, ErrorPayload> {
+    services::find_duplicates(state, app_handle).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_skip_duplicate_recordings(app_handle: AppHandle, enabled: bool) -> Result<(), ErrorPayload> {
+    services::set_skip_duplicate_recordings(app_handle, enabled).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn search_recordings(state: State<'_, AppState>, app_handle: AppHandle, query: String) -> Result<Vec<RecordingMatch>, ErrorPayload> {
+    services::search_recordings(state, app_handle, query).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn add_note(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, timestamp_ms: u64, text: String) -> Result<Recording, ErrorPayload> {
+    services::add_note(state, app_handle, recording_id, timestamp_ms, text).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn delete_note(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, index: usize) -> Result<Recording, ErrorPayload> {
+    services::delete_note(state, app_handle, recording_id, index).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn duplicate_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Recording, ErrorPayload> {
+    services::duplicate_recording(state, app_handle, recording_id).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn trim_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, start_seconds: f64, end_seconds: f64, output_title: String) -> Result<Recording, ErrorPayload> {
+    services::trim_recording(state, app_handle, recording_id, start_seconds, end_seconds, output_title).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn rename_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, title: String) -> Result<Recording, ErrorPayload> {
+    services::rename_recording(state, app_handle, recording_id, title).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn load_recordings_from_disk(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), ErrorPayload> {
+    services::load_recordings_from_disk(state, app_handle).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn generate_waveform(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, buckets: usize) -> Result<Vec<f32>, ErrorPayload> {
+    services::generate_waveform(state, app_handle, recording_id, buckets).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn merge_recordings(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    recording_ids: Vec<String>,
+    output_title: String,
+    delete_sources: Option<bool>,
+) -> Result<Recording, ErrorPayload> {
+    services::merge_recordings(state, app_handle, recording_ids, output_title, delete_sources.unwrap_or(false)).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn export_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, destination: PathBuf) -> Result<PathBuf, ErrorPayload> {
+    services::export_recording(state, app_handle, recording_id, destination, false).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn export_recording_with_transcript(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, destination: PathBuf) -> Result<PathBuf, ErrorPayload> {
+    services::export_recording(state, app_handle, recording_id, destination, true).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn export_all(app_handle: AppHandle, destination: PathBuf) -> Result<PathBuf, ErrorPayload> {
+    services::export_all(app_handle, destination).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn import_archive(state: State<'_, AppState>, app_handle: AppHandle, source: PathBuf) -> Result<usize, ErrorPayload> {
+    services::import_archive(state, app_handle, source).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn upload_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Recording, ErrorPayload> {
+    services::upload_recording(state, app_handle, recording_id).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_recording_format(app_handle: AppHandle, format: AudioFormat, mp3_bitrate_kbps: Option<u32>) -> Result<(), ErrorPayload> {
+    services::set_recording_format(app_handle, format, mp3_bitrate_kbps).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_conversion_settings(app_handle: AppHandle, bitrate_kbps: u32, sample_rate: u32, channels: u16) -> Result<(), ErrorPayload> {
+    services::set_conversion_settings(app_handle, bitrate_kbps, sample_rate, channels).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_start_delay(app_handle: AppHandle, seconds: u32) -> Result<(), ErrorPayload> {
+    services::set_start_delay(app_handle, seconds).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_filename_template(app_handle: AppHandle, template: String) -> Result<(), ErrorPayload> {
+    services::set_filename_template(app_handle, template).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_recording_layout(app_handle: AppHandle, layout: RecordingLayout) -> Result<(), ErrorPayload> {
+    services::set_recording_layout(app_handle, layout).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_auto_normalize(app_handle: AppHandle, enabled: bool) -> Result<(), ErrorPayload> {
+    services::set_auto_normalize(app_handle, enabled).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_retention_days(app_handle: AppHandle, days: Option<u32>) -> Result<(), ErrorPayload> {
+    services::set_retention_days(app_handle, days).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_keep_original_wav(app_handle: AppHandle, enabled: bool) -> Result<(), ErrorPayload> {
+    services::set_keep_original_wav(app_handle, enabled).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_silence_trim(app_handle: AppHandle, enabled: bool, threshold_db: Option<f32>) -> Result<(), ErrorPayload> {
+    services::set_silence_trim(app_handle, enabled, threshold_db).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_upload_endpoint(app_handle: AppHandle, endpoint: Option<String>) -> Result<(), ErrorPayload> {
+    services::set_upload_endpoint(app_handle, endpoint).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_upload_api_key(app_handle: AppHandle, api_key: Option<String>) -> Result<(), ErrorPayload> {
+    services::set_upload_api_key(app_handle, api_key).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn convert_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Recording, ErrorPayload> {
+    services::convert_recording(state, app_handle, recording_id).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn audit_recordings(state: State<'_, AppState>, app_handle: AppHandle) -> Result<RecordingsAudit, ErrorPayload> {
+    services::audit_recordings(state, app_handle).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn cleanup_orphans(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Vec<String>, ErrorPayload> {
+    services::cleanup_orphans(state, app_handle).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn open_recordings_folder(app_handle: AppHandle) -> Result<(), ErrorPayload> {
+    services::open_recordings_folder(app_handle).await.map_err(ErrorPayload::from)
+}
+
+#[tauri::command]
+pub async fn set_recordings_dir(state: State<'_, AppState>, app_handle: AppHandle, dir: Option<String>) -> Result<(), ErrorPayload> {
+    services::set_recordings_dir(state, app_handle, dir.map(PathBuf::from)).await.map_err(ErrorPayload::from)
+}
@@ -1,6 +1,8 @@
 use tauri::{State, AppHandle};
 use crate::AppState;
-use crate::services::{TranscriptionService, get_recording_path};
+use crate::services::{self, TranscriptionService, TranscriptionResponse, TranscriptionBackend, TranscriptionBackendKind, ensure_not_processing, get_recording_path, restore_recording_status, set_recording_status, store_transcript};
+use crate::RecordingStatus;
+use crate::app_config::AppConfig;
 use crate::events::EventEmitter;
 use crate::error::AppError;
 
@@ -10,10 +12,50 @@ pub async fn transcribe_recording_stream(
     app_handle: AppHandle,
     recording_id: String,
     api_url: String,
-    api_key: Option<String>
+    api_key: Option<String>,
+    language: Option<String>
 ) -> std::result::Result<String, String> {
     println!("Starting streaming transcription for recording: {}", recording_id);
     
+    // Use the configured backend; the remote backend keeps the endpoint passed by the caller
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    let backend = match config.transcription_backend {
+        TranscriptionBackendKind::Remote => TranscriptionBackend::Remote { url: api_url, api_key },
+        kind => config.resolve_transcription_backend(kind)?,
+    };
+    let language = language.or(config.default_language);
+    
+    run_transcription(state, app_handle, recording_id, backend, language, config.transcription_chunk_minutes).await
+}
+
+/// Transcribe a stored recording again, replacing its saved transcript
+///
+/// `backend` overrides the configured backend for this request only.
+#[tauri::command]
+pub async fn retranscribe_recording(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    recording_id: String,
+    backend: Option<TranscriptionBackendKind>,
+    language: Option<String>
+) -> std::result::Result<String, String> {
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    let backend_kind = backend.unwrap_or(config.transcription_backend);
+    let backend = config.resolve_transcription_backend(backend_kind)?;
+    let language = language.or(config.default_language);
+    
+    println!("Re-transcribing recording {} with {:?} backend", recording_id, backend_kind);
+    run_transcription(state, app_handle, recording_id, backend, language, config.transcription_chunk_minutes).await
+}
+
+async fn run_transcription(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    recording_id: String,
+    backend: TranscriptionBackend,
+    language: Option<String>,
+    chunk_minutes: u32
+) -> std::result::Result<String, String> {
     // Find the recording
     let recording = {
         let recordings = state.recordings.lock().unwrap();
@@ -23,6 +65,7 @@ pub async fn transcribe_recording_stream(
             .ok_or_else(|| AppError::Transcription("Recording not found".to_string()))
             .map_err(|e| e.to_string())?
     };
+    ensure_not_processing(&recording).map_err(|e| e.to_string())?;
     
     // Get the full path to the audio file
     let file_path = get_recording_path(&app_handle, &recording.filename).map_err(|e| e.to_string())?;
@@ -34,29 +77,64 @@ pub async fn transcribe_recording_stream(
     
     // Emit status update to frontend
     EventEmitter::transcription_started(&app_handle, &recording_id);
+    let previous_status = set_recording_status(&app_handle, &recording_id, RecordingStatus::Transcribing);
     
-    // Perform streaming transcription
-    let api_key_ref = api_key.as_deref();
-    let transcription_result = TranscriptionService::transcribe_audio_stream(
+    // Perform transcription
+    let transcription_result = TranscriptionService::transcribe(
         &file_path,
-        &api_url,
-        api_key_ref
+        &backend,
+        language.as_deref(),
+        chunk_minutes,
+        &recording_id,
+        &app_handle
     ).await;
+    if let Some(status) = previous_status {
+        restore_recording_status(&app_handle, &recording_id, RecordingStatus::Transcribing, status);
+    }
     
     match transcription_result {
         Ok(response) => {
+            // Overwrites any transcript saved by an earlier run
+            if let Err(e) = store_transcript(&app_handle, &recording_id, &file_path, &response) {
+                eprintln!("Failed to save transcript for recording {}: {}", recording_id, e);
+            }
+            
             // Emit success to frontend with transcription response
             EventEmitter::transcription_success(&app_handle, &recording_id, &response);
             
-            println!("Streaming transcription completed for recording: {}", recording_id);
+            println!("Transcription completed for recording: {}", recording_id);
             Ok("Transcription completed successfully".to_string())
         }
         Err(e) => {
             // Emit error to frontend
             EventEmitter::transcription_failed(&app_handle, &recording_id, &e);
             
-            eprintln!("Streaming transcription failed for recording {}: {}", recording_id, e);
+            eprintln!("Transcription failed for recording {}: {}", recording_id, e);
             Err(e.to_string())
         }
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn cancel_transcription(state: State<'_, AppState>, recording_id: String) -> std::result::Result<(), String> {
+    services::cancel_transcription(state, recording_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_auto_transcribe(app_handle: AppHandle, enabled: bool) -> std::result::Result<(), String> {
+    services::set_auto_transcribe(app_handle, enabled).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_transcription_chunk_minutes(app_handle: AppHandle, minutes: u32) -> std::result::Result<(), String> {
+    services::set_transcription_chunk_minutes(app_handle, minutes).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_transcript(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    recording_id: String
+) -> std::result::Result<Option<TranscriptionResponse>, String> {
+    services::get_transcript(state, app_handle, recording_id).await.map_err(|e| e.to_string())
+}
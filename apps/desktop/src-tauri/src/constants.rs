@@ -1,28 +1,98 @@
 /// Application-wide constants to eliminate magic numbers and provide a single source of truth.
 
 // Recording Configuration
-pub const MAX_RECENT_RECORDINGS: usize = 5;
 pub const WAV_HEADER_MIN_BYTES: u64 = 44;
+pub const RECORDING_BUFFER_SAMPLES: usize = 960_000; // Samples queued for the WAV writer (~10 s of 48 kHz stereo)
+pub const MIN_RECORD_SAMPLE_RATE: u32 = 8000;     // Bounds of a configured recording sample rate
+pub const MAX_RECORD_SAMPLE_RATE: u32 = 192_000;
+pub const RESAMPLER_CHUNK_FRAMES: usize = 1024;   // Captured frames resampled at a time when the rates differ
 
 // Timing Constants (in milliseconds)
 pub const MEETING_CHECK_INTERVAL_MS: u64 = 2000;  // 2 seconds
-pub const WAV_READY_CHECK_DELAY_MS: u64 = 200;    // Wait between WAV file readiness checks
-pub const AUDIO_FINALIZATION_DELAY_MS: u64 = 500; // Wait before finalizing audio processing
-pub const WRITER_CLEANUP_DELAY_MS: u64 = 100;     // Audio writer thread cleanup delay
+pub const WAV_FINALIZE_TIMEOUT_MS: u64 = 5000;    // Max wait for the WAV writer to finalize the file
+pub const RECORDING_START_TIMEOUT_MS: u64 = 5000; // Max wait for the input stream to start
+pub const INPUT_PROBE_DURATION_MS: u64 = 300;     // Capture length when probing the microphone
+pub const ENDPOINT_CHECK_TIMEOUT_MS: u64 = 3000;  // Timeout for transcription endpoint reachability checks
+pub const AUDIO_LEVEL_EMIT_INTERVAL_MS: u64 = 50; // Throttle for input level (VU meter) events
+pub const RECORDING_DEGRADED_EMIT_INTERVAL_MS: u64 = 1000; // Throttle for dropped-sample warnings
+pub const METADATA_FLUSH_DELAY_MS: u64 = 500;   // Coalescing window for recordings.json writes
+pub const TRAY_STATUS_INTERVAL_MS: u64 = 1000;    // Refresh rate of the tray tooltip/title while recording
+pub const TRAY_RECENT_RECORDINGS: usize = 5;      // Recordings listed in the tray's recent submenu
+pub const MAX_WAVEFORM_BUCKETS: usize = 10_000;   // Upper bound on requested waveform peaks
 
 // UI Layout Constants (in pixels)
 pub const NOTIFICATION_MARGIN_PX: i32 = 20;       // Margin from screen edge
 pub const NOTIFICATION_TOP_PX: i32 = 50;          // Distance from top of screen
 
 // Audio Processing Constants
-pub const AUDIO_SAMPLE_RATE_STR: &str = "16000";  // String version for API calls
+pub const AUDIO_SAMPLE_RATE_STR: &str = "16000";  // String version for FFmpeg arguments
+pub const DEFAULT_CONVERSION_BITRATE_KBPS: u32 = 64; // Opus bitrate tuned for speech
+pub const DEFAULT_CONVERSION_SAMPLE_RATE: u32 = 16000; // Speech-friendly sample rate
+pub const DEFAULT_CONVERSION_CHANNELS: u16 = 1;      // Mono
+pub const MIN_CONVERSION_BITRATE_KBPS: u32 = 6;      // Opus encoder limits
+pub const MAX_CONVERSION_BITRATE_KBPS: u32 = 510;
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f32 = -50.0; // Audio quieter than this counts as silence
+pub const SILENCE_TRIM_PADDING_SECS: f32 = 0.5;       // Silence kept before speech when trimming
+pub const LOUDNORM_TARGET_LUFS: f32 = -16.0;        // Integrated loudness target when normalizing
+pub const LOUDNORM_TRUE_PEAK_DB: f32 = -1.5;        // True-peak ceiling for loudness normalization
+pub const CONVERSION_SAMPLE_RATES: [u32; 5] = [8000, 12000, 16000, 24000, 48000]; // Rates both Opus and MP3 support
+pub const DEFAULT_MP3_BITRATE_KBPS: u32 = 128;    // MP3 bitrate when MP3 output is selected
+pub const MIN_MP3_BITRATE_KBPS: u32 = 32;
+pub const MAX_MP3_BITRATE_KBPS: u32 = 320;
 
-// Retry and Attempt Limits
-pub const WAV_READY_MAX_ATTEMPTS: u32 = 5;        // Maximum attempts to check WAV file readiness
+// Recordings Audit
+pub const ORPHAN_MIN_AGE_SECS: u64 = 300;         // Newer unreferenced files may still be in use (recording, converting)
+pub const RETENTION_CHECK_INTERVAL_SECS: u64 = 3600; // How often recordings past `retention_days` are pruned
+
+// Search
+pub const SEARCH_SNIPPET_CONTEXT_CHARS: usize = 60; // Transcript text kept on each side of an untimed match
+pub const MAX_SEARCH_SNIPPETS: usize = 5;         // Snippets returned per matching recording
+
+// Transcription
+pub const TRANSCRIPTION_CANCELLED_MESSAGE: &str = "Transcription cancelled"; // Error (and `transcription_failed` reason) of a cancelled run
+
+// Recording Filenames
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "recording_{date}_{time}"; // Stem of new recordings before the extension
+pub const MAX_FILENAME_STEM_CHARS: usize = 120;   // Longer expanded templates are truncated
+
+// Input Gain
+pub const DEFAULT_INPUT_GAIN: f32 = 1.0;          // Unity gain: samples are recorded as captured
+pub const MAX_INPUT_GAIN: f32 = 8.0;              // ~+18 dB, enough for quiet laptop microphones
+
+// Noise Gate
+pub const DEFAULT_NOISE_GATE_THRESHOLD_DB: f32 = -45.0; // Rolling RMS below this is attenuated
+pub const NOISE_GATE_RMS_WINDOW_MS: f32 = 20.0;   // Averaging time of the rolling RMS
+pub const NOISE_GATE_ATTACK_MS: f32 = 5.0;        // Fade-in time when speech resumes
+pub const NOISE_GATE_RELEASE_MS: f32 = 150.0;     // Fade-out time once the input falls below the threshold
+
+// Pre-roll Buffer
+pub const DEFAULT_PREROLL_SECS: u32 = 5;          // Audio kept from before recording starts
+pub const MAX_PREROLL_SECS: u32 = 30;             // Upper bound on the in-memory pre-roll
+
+// Playback Configuration
+pub const DEFAULT_PLAYBACK_RATE: f32 = 1.0;
+pub const MIN_PLAYBACK_RATE: f32 = 0.5;
+pub const MAX_PLAYBACK_RATE: f32 = 3.0;
+pub const DEFAULT_PLAYBACK_VOLUME: f32 = 1.0;     // Samples played as decoded; the UI can only turn them down
 
 // Time Formatting
-pub const SECONDS_PER_MINUTE: i64 = 60;           // For duration calculations
+pub const SECONDS_PER_MINUTE: u64 = 60;           // For duration calculations
 
 // Meeting Detection
-pub const MEETING_URL_MAX_CHARS: usize = 20;      // Characters to check in meeting URL patterns
-pub const MEETING_URL_MIN_DASHES: usize = 2;      // Minimum dashes for meeting URL detection
\ No newline at end of file
+pub const MEETING_POLL_INTERVAL_SECS: u64 = 5;    // Detector polling interval
+pub const DEFAULT_MEETING_END_GRACE_SECS: u64 = 15; // Negative polls tolerated before a meeting ends
+pub const PROBE_MAX_SIGNAL_CHARS: usize = 80;     // URLs/window titles in detection probes are truncated to this
+
+// Local Control API
+pub const DEFAULT_LOCAL_API_PORT: u16 = 47813;    // Port the local HTTP API binds on 127.0.0.1
+pub const LOCAL_API_REQUEST_TIMEOUT_MS: u64 = 5000; // Max wait for a client to send its request headers
+pub const LOCAL_API_MAX_REQUEST_BYTES: usize = 8192; // Larger request headers are rejected
+
+// Logging
+pub const LOG_FILE_NAME: &str = "content-recorder.log"; // File in the app log directory
+pub const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024; // Rotated to `.old` at startup beyond this size
+
+// Credential Storage
+pub const KEYCHAIN_SERVICE: &str = "com.hanifcarroll.content-recorder"; // OS keychain service name
+pub const KEYCHAIN_API_KEY_ACCOUNT: &str = "api_key";                   // Keychain account for the API key
+pub const KEYCHAIN_UPLOAD_API_KEY_ACCOUNT: &str = "upload_api_key";     // Keychain account for the upload endpoint's key
@@ -1,6 +1,7 @@
 /// Comprehensive error handling for the desktop application.
 /// Provides structured error types instead of string-based errors for better debugging.
 
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,6 +18,9 @@ pub enum AppError {
     #[error("Transcription error: {0}")]
     Transcription(String),
     
+    #[error("Upload error: {0}")]
+    Upload(String),
+    
     #[error("Meeting detection error: {0}")]
     MeetingDetection(String),
     
@@ -42,6 +46,49 @@ pub enum AppError {
     System(String),
 }
 
+impl AppError {
+    /// Category name of this error, matching the variant name
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "Io",
+            AppError::Audio(_) => "Audio",
+            AppError::Config(_) => "Config",
+            AppError::Transcription(_) => "Transcription",
+            AppError::Upload(_) => "Upload",
+            AppError::MeetingDetection(_) => "MeetingDetection",
+            AppError::Path(_) => "Path",
+            AppError::Recording(_) => "Recording",
+            AppError::Playback(_) => "Playback",
+            AppError::Serialization(_) => "Serialization",
+            AppError::Http(_) => "Http",
+            AppError::Conversion(_) => "Conversion",
+            AppError::System(_) => "System",
+        }
+    }
+}
+
+/// Error returned to the frontend, keeping the `AppError` category so the UI can tell failures apart
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorPayload {
+    pub kind: String,
+    pub message: String,
+}
+
+impl From<AppError> for ErrorPayload {
+    fn from(error: AppError) -> Self {
+        ErrorPayload {
+            kind: error.kind().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 // Implement From<String> for AppError to support legacy string errors
 impl From<String> for AppError {
     fn from(s: String) -> Self {
@@ -58,3 +105,17 @@ impl From<&str> for AppError {
 /// Convenience type alias for Results using AppError
 pub type Result<T> = std::result::Result<T, AppError>;
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_payload_keeps_kind() {
+        let payload = ErrorPayload::from(AppError::Recording("Not recording".to_string()));
+        assert_eq!(payload.kind, "Recording");
+        assert_eq!(payload.message, "Recording error: Not recording");
+        
+        let payload = ErrorPayload::from(AppError::from("legacy"));
+        assert_eq!(payload.kind, "System");
+    }
+}
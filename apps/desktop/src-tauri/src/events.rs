@@ -7,18 +7,37 @@ pub struct Events;
 impl Events {
     // Recording related events
     pub const RECORDING_STATE_CHANGED: &'static str = "recording-state-changed";
+    pub const AUDIO_LEVEL: &'static str = "audio-level";
+    pub const RECORDING_AUTO_STOPPED: &'static str = "recording-auto-stopped";
+    pub const RECORDING_ERROR: &'static str = "recording-error";
+    pub const RECORDING_INTERRUPTED: &'static str = "recording-interrupted";
+    pub const RECORDING_DEGRADED: &'static str = "recording-degraded";
+    pub const RECORDING_COUNTDOWN: &'static str = "recording-countdown";
+    pub const RECORDINGS_PRUNED: &'static str = "recordings-pruned";
+    pub const RECORDING_STATUS_CHANGED: &'static str = "recording-status-changed";
+    pub const RECORDING_DEDUPLICATED: &'static str = "recording-deduplicated";
     
     // Playback related events  
     pub const PLAYBACK_FINISHED: &'static str = "playback-finished";
+    pub const PLAYBACK_ERROR: &'static str = "playback-error";
     
     // Transcription related events
     pub const TRANSCRIPTION_STARTED: &'static str = "transcription_started";
     pub const TRANSCRIPTION_SUCCESS: &'static str = "transcription_success";
     pub const TRANSCRIPTION_FAILED: &'static str = "transcription_failed";
+    pub const TRANSCRIPTION_PROGRESS: &'static str = "transcription_progress";
+    
+    // Upload related events
+    pub const UPLOAD_PROGRESS: &'static str = "upload-progress";
+    pub const UPLOAD_SUCCESS: &'static str = "upload-success";
+    pub const UPLOAD_FAILED: &'static str = "upload-failed";
     
     // Meeting detection events
     pub const MEETING_DETECTED: &'static str = "meeting-detected";
     pub const MEETING_ENDED: &'static str = "meeting-ended";
+    
+    // Permission events
+    pub const PERMISSION_REQUIRED: &'static str = "permission-required";
 }
 
 /// Helper functions for emitting common events
@@ -30,11 +49,61 @@ impl EventEmitter {
         let _ = app_handle.emit(Events::RECORDING_STATE_CHANGED, ());
     }
     
+    /// Emit a recording auto-stopped event (maximum duration reached)
+    pub fn recording_auto_stopped<T: Serialize + Clone>(app_handle: &AppHandle, recording: &T) {
+        let _ = app_handle.emit(Events::RECORDING_AUTO_STOPPED, recording);
+    }
+    
+    /// Emit a recording error raised by the audio thread
+    pub fn recording_error<T: Serialize + Clone>(app_handle: &AppHandle, error: &T) {
+        let _ = app_handle.emit(Events::RECORDING_ERROR, error);
+    }
+    
+    /// Emit a recording interrupted event (input stream failed mid-recording)
+    pub fn recording_interrupted<T: Serialize + Clone>(app_handle: &AppHandle, interruption: &T) {
+        let _ = app_handle.emit(Events::RECORDING_INTERRUPTED, interruption);
+    }
+    
+    /// Emit a warning that captured audio was dropped because the WAV writer fell behind
+    pub fn recording_degraded<T: Serialize + Clone>(app_handle: &AppHandle, degradation: &T) {
+        let _ = app_handle.emit(Events::RECORDING_DEGRADED, degradation);
+    }
+    
+    /// Emit a recording countdown tick (or its cancellation)
+    pub fn recording_countdown<T: Serialize + Clone>(app_handle: &AppHandle, countdown: &T) {
+        let _ = app_handle.emit(Events::RECORDING_COUNTDOWN, countdown);
+    }
+    
+    /// Emit a summary of recordings deleted by the retention sweep
+    pub fn recordings_pruned<T: Serialize + Clone>(app_handle: &AppHandle, summary: &T) {
+        let _ = app_handle.emit(Events::RECORDINGS_PRUNED, summary);
+    }
+    
+    /// Emit a recording whose status changed (e.g. processing finished)
+    pub fn recording_status_changed<T: Serialize + Clone>(app_handle: &AppHandle, recording: &T) {
+        let _ = app_handle.emit(Events::RECORDING_STATUS_CHANGED, recording);
+    }
+    
+    /// Emit a new recording discarded as a duplicate of a saved one
+    pub fn recording_deduplicated<T: Serialize + Clone>(app_handle: &AppHandle, deduplicated: &T) {
+        let _ = app_handle.emit(Events::RECORDING_DEDUPLICATED, deduplicated);
+    }
+    
+    /// Emit the current input level (normalized 0.0-1.0) while recording
+    pub fn audio_level(app_handle: &AppHandle, level: f32) {
+        let _ = app_handle.emit(Events::AUDIO_LEVEL, level);
+    }
+    
     /// Emit a playback finished event
     pub fn playback_finished(app_handle: &AppHandle) {
         let _ = app_handle.emit(Events::PLAYBACK_FINISHED, ());
     }
     
+    /// Emit a playback error (e.g. the file could not be decoded)
+    pub fn playback_error<T: Serialize + Clone>(app_handle: &AppHandle, error: &T) {
+        let _ = app_handle.emit(Events::PLAYBACK_ERROR, error);
+    }
+    
     /// Emit a transcription started event
     pub fn transcription_started(app_handle: &AppHandle, recording_id: &str) {
         let _ = app_handle.emit(Events::TRANSCRIPTION_STARTED, recording_id);
@@ -54,6 +123,26 @@ impl EventEmitter {
         let _ = app_handle.emit(Events::TRANSCRIPTION_FAILED, (recording_id, error));
     }
     
+    /// Emit intermediate transcription progress for a recording
+    pub fn transcription_progress<T: Serialize + Clone>(app_handle: &AppHandle, progress: &T) {
+        let _ = app_handle.emit(Events::TRANSCRIPTION_PROGRESS, progress);
+    }
+    
+    /// Emit upload progress for a recording
+    pub fn upload_progress<T: Serialize + Clone>(app_handle: &AppHandle, progress: &T) {
+        let _ = app_handle.emit(Events::UPLOAD_PROGRESS, progress);
+    }
+    
+    /// Emit an upload success event
+    pub fn upload_success(app_handle: &AppHandle, recording_id: &str) {
+        let _ = app_handle.emit(Events::UPLOAD_SUCCESS, recording_id);
+    }
+    
+    /// Emit an upload failed event
+    pub fn upload_failed(app_handle: &AppHandle, recording_id: &str, error: &str) {
+        let _ = app_handle.emit(Events::UPLOAD_FAILED, (recording_id, error));
+    }
+    
     /// Emit a meeting detected event
     pub fn meeting_detected<T: Serialize + Clone>(app_handle: &AppHandle, meeting_state: &T) {
         let _ = app_handle.emit(Events::MEETING_DETECTED, meeting_state);
@@ -63,4 +152,9 @@ impl EventEmitter {
     pub fn meeting_ended(app_handle: &AppHandle) {
         let _ = app_handle.emit(Events::MEETING_ENDED, ());
     }
-}
\ No newline at end of file
+    
+    /// Emit a permission required event (e.g. microphone access not granted)
+    pub fn permission_required<T: Serialize + Clone>(app_handle: &AppHandle, status: &T) {
+        let _ = app_handle.emit(Events::PERMISSION_REQUIRED, status);
+    }
+}
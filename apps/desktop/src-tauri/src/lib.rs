@@ -1,6 +1,7 @@
 use tauri::Manager;
-use std::thread;
-use tracing::{info, error};
+use std::sync::atomic::Ordering;
+use tracing::{info, warn, error};
+use tracing_subscriber::prelude::*;
 
 // Modules
 mod meeting_detector;
@@ -14,27 +15,92 @@ mod events;
 mod path_manager;
 mod constants;
 mod error;
+mod local_api;
 
 // Re-exports
 pub use commands::*;
 pub use state::*;
 use events::EventEmitter;
-use constants::*;
+use app_config::AppConfig;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .setup(|app| {
-            // Initialize logging
-            tracing_subscriber::fmt()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            // Initialize logging to the console and, for diagnostics, the app log file
+            let file_layer = match services::open_log_file(app.handle()) {
+                Ok(file) => Some(tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(std::sync::Mutex::new(file))),
+                Err(e) => {
+                    eprintln!("Failed to open log file, logging to the console only: {}", e);
+                    None
+                }
+            };
+            tracing_subscriber::registry()
+                .with(tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")))
+                .with(tracing_subscriber::fmt::layer())
+                .with(file_layer)
                 .init();
             
             info!("Starting desktop application");
             
+            // Load persisted configuration
+            let first_launch = !AppConfig::exists(app.handle());
+            let mut config = tauri::async_runtime::block_on(AppConfig::load(app.handle()))
+                .unwrap_or_else(|e| {
+                    error!("Failed to load config, using defaults: {}", e);
+                    AppConfig::default()
+                });
+            // Enabling the API by editing config.json leaves the token to be generated here
+            let token_created = config.ensure_local_api_token();
+            if first_launch {
+                // Show the window this once; later launches start in the tray
+                config.start_hidden = true;
+            }
+            if first_launch || token_created {
+                if let Err(e) = tauri::async_runtime::block_on(config.save(app.handle())) {
+                    error!("Failed to save config: {}", e);
+                }
+            }
+            
             // Initialize app state
             let app_state = AppState::default();
+            *app_state.input_device.lock().unwrap() = config.input_device.clone();
+            *app_state.output_device.lock().unwrap() = config.output_device.clone();
+            *app_state.record_channels.lock().unwrap() = config.record_channels;
+            *app_state.wav_bit_depth.lock().unwrap() = config.wav_bit_depth;
+            *app_state.record_sample_rate.lock().unwrap() = config.record_sample_rate();
+            *app_state.noise_gate_threshold_db.lock().unwrap() = config.noise_gate();
+            app_state.input_gain.store(config.input_gain.clamp(0.0, constants::MAX_INPUT_GAIN).to_bits(), Ordering::Relaxed);
+            app_state.playback_volume.store(config.playback_volume.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+            app_state.meeting_detector.update_settings(config.detection_settings());
+            *app_state.meeting_action.lock().unwrap() = config.meeting_action;
+            *app_state.recordings_dir.lock().unwrap() = config.recordings_dir.clone();
+            if let Err(e) = services::apply_preroll(&app_state, config.preroll_enabled, config.preroll_secs) {
+                warn!("Failed to start pre-roll: {}", e);
+            }
+            
+            // Check for FFmpeg now rather than when the first recording stops
+            match services::audio_converter::AudioConverter::verify_ffmpeg(app.handle()) {
+                Ok(path) => {
+                    info!("FFmpeg available at: {}", path.display());
+                    app_state.ffmpeg_available.store(true, Ordering::Relaxed);
+                }
+                Err(e) => error!("FFmpeg unavailable, recordings will be kept as WAV: {}", e),
+            }
+            
+            // Let the UI guide the user if recording would capture only silence
+            match tauri::async_runtime::block_on(services::microphone_permission_status()) {
+                Ok(services::MicrophonePermission::Granted) => info!("Microphone permission granted"),
+                Ok(status) => {
+                    warn!("Microphone permission not granted: {:?}", status);
+                    EventEmitter::permission_required(app.handle(), &status);
+                }
+                Err(e) => error!("Failed to check microphone permission: {}", e),
+            }
             
             // Initialize audio system
             if let Err(e) = app_state.initialize_audio_system() {
@@ -43,83 +109,24 @@ pub fn run() {
                 info!("Audio system initialized successfully");
             }
             
-            // Start meeting detection automatically
-            if let Err(e) = app_state.meeting_detector.start_monitoring() {
+            // Start meeting detection automatically unless the user opted out
+            if let Err(e) = services::apply_meeting_detection(&app_state, app.handle(), config.meeting_detection_enabled) {
                 error!("Failed to start meeting detection: {}", e);
-            } else {
-                info!("Meeting detection started");
             }
             
-            // Set up auto-recording notification when meeting is detected
-            let detector_clone = app_state.meeting_detector.clone();
-            let app_handle_clone = app.handle().clone();
-            thread::spawn(move || {
-                let mut was_in_meeting = false;
-                let mut notification_shown = false;
-                
-                loop {
-                    let meeting_state = detector_clone.get_state();
-                    
-                    if meeting_state.is_in_meeting && !was_in_meeting {
-                        // Meeting just started - show notification popup
-                        info!("Meeting detected: {:?}", meeting_state.detected_app);
-                        
-                        if !notification_shown {
-                            // Show the notification window
-                            if let Some(notification_window) = app_handle_clone.get_webview_window("notification") {
-                                info!("Found notification window, showing...");
-                                
-                                let _ = notification_window.show();
-                                
-                                // Dynamically position window at top-center
-                                if let Ok(monitor) = notification_window.current_monitor() {
-                                    if let Some(monitor) = monitor {
-                                        let screen_size = monitor.size();
-                                        let screen_width = screen_size.width as i32;
-                                        
-                                        // Get window size
-                                        if let Ok(window_size) = notification_window.outer_size() {
-                                            let window_width = window_size.width as i32;
-                                            
-                                            // Calculate top-right position
-                                            let x = screen_width - window_width - NOTIFICATION_MARGIN_PX;
-                                            let y = NOTIFICATION_TOP_PX;
-                                            
-                                            let _ = notification_window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }));
-                                            info!("Positioned notification window at ({}, {}) - Screen width: {}, Window width: {}", 
-                                                     x, y, screen_width, window_width);
-                                        }
-                                    }
-                                }
-                                
-                                let _ = notification_window.set_focus();
-                                info!("Notification window shown and focused");
-                                notification_shown = true;
-                                
-                                // Emit event to update the notification content
-                                EventEmitter::meeting_detected(&app_handle_clone, &meeting_state);
-                            }
-                        }
-                    } else if !meeting_state.is_in_meeting && was_in_meeting {
-                        // Meeting just ended
-                        info!("Meeting ended");
-                        notification_shown = false;
-                        
-                        // Hide notification if still open
-                        if let Some(notification_window) = app_handle_clone.get_webview_window("notification") {
-                            let _ = notification_window.hide();
-                        }
-                        
-                        EventEmitter::meeting_ended(&app_handle_clone);
-                        }
-                        
-                    was_in_meeting = meeting_state.is_in_meeting;
-                    thread::sleep(std::time::Duration::from_millis(MEETING_CHECK_INTERVAL_MS));
-                }
-            });
+            // Serve the local control API if the user enabled it
+            if let Err(e) = tauri::async_runtime::block_on(local_api::apply(&app_state, app.handle(), &config)) {
+                error!("{}", e);
+            }
             
             app.manage(app_state);
             
+            // Finish conversions that failed in earlier sessions
+            tauri::async_runtime::spawn(services::retry_pending_conversions(app.handle().clone()));
+            
+            // Delete recordings past the retention window, if one is configured
+            tauri::async_runtime::spawn(services::run_retention_sweeps(app.handle().clone()));
+            
             // Setup system tray
             tray::setup_system_tray(&app.handle()).map_err(|e| {
                 error!("Failed to setup system tray: {}", e);
@@ -137,8 +144,14 @@ pub fn run() {
                     }
                 });
                 
-                // Hide main window initially (it will show when tray is clicked or dock icon is clicked)
-                window.hide()?;
+                // Start hidden unless disabled (it will show when tray is clicked or dock icon is clicked);
+                // the first launch always shows the window
+                if config.start_hidden && !first_launch {
+                    window.hide()?;
+                } else {
+                    window.show()?;
+                    window.set_focus()?;
+                }
             }
             
             Ok(())
@@ -148,22 +161,95 @@ pub fn run() {
             pause_recording,
             resume_recording,
             stop_recording,
+            cancel_recording,
             get_recent_recordings,
+            get_recordings_page,
             get_recording_state,
+            get_recording_info,
             toggle_recording,
             play_recording,
             stop_playback,
+            pause_playback,
+            resume_playback,
+            set_playback_speed,
+            set_playback_volume,
             get_playback_state,
+            get_playback_info,
             delete_recording,
+            delete_recordings,
+            merge_recordings,
+            trim_recording,
+            get_total_storage_used,
+            rename_recording,
+            duplicate_recording,
+            get_recording_detail,
+            find_duplicates,
+            set_skip_duplicate_recordings,
+            search_recordings,
+            add_note,
+            delete_note,
+            export_recording,
+            export_recording_with_transcript,
+            export_all,
+            import_archive,
+            upload_recording,
+            set_upload_endpoint,
+            set_upload_api_key,
+            set_recording_format,
+            set_conversion_settings,
+            set_silence_trim,
+            set_filename_template,
+            set_recording_layout,
+            set_auto_normalize,
+            set_keep_original_wav,
+            set_retention_days,
+            set_start_delay,
             load_recordings_from_disk,
+            convert_recording,
+            audit_recordings,
+            cleanup_orphans,
             open_recordings_folder,
+            set_recordings_dir,
+            generate_waveform,
             start_meeting_detection,
             stop_meeting_detection,
+            set_meeting_detection_enabled,
             get_meeting_state,
+            is_meeting_detection_active,
+            probe_meeting_detection,
+            set_custom_meeting_patterns,
+            set_meeting_end_grace,
+            set_scan_browser_urls,
+            set_detect_apps,
+            set_meeting_action,
             transcribe_recording_stream,
+            get_transcript,
+            set_transcription_chunk_minutes,
+            set_auto_transcribe,
+            cancel_transcription,
+            retranscribe_recording,
             get_config,
             update_config,
-            reset_config
+            reset_config,
+            set_start_hidden,
+            set_local_api,
+            list_input_devices,
+            set_input_device,
+            list_output_devices,
+            set_output_device,
+            set_record_channels,
+            set_wav_bit_depth,
+            set_record_sample_rate,
+            set_input_gain,
+            set_noise_gate,
+            set_preroll,
+            is_ffmpeg_available,
+            get_system_status,
+            get_audio_diagnostics,
+            get_recent_logs,
+            open_log_folder,
+            microphone_permission_status,
+            request_microphone_permission
         ])
         .on_window_event(|window, event| {
             match event {
@@ -179,6 +265,7 @@ pub fn run() {
         .expect("error while building tauri application")
         .run(|app, event| {
             match event {
+                #[cfg(target_os = "macos")]
                 tauri::RunEvent::Reopen { has_visible_windows, .. } => {
                     // Handle dock icon click when app has no visible windows
                     if !has_visible_windows {
@@ -186,11 +273,14 @@ pub fn run() {
                             window.show().unwrap();
                             window.set_focus().unwrap();
                             // On macOS, also unminimize if needed
-                            #[cfg(target_os = "macos")]
                             let _ = window.unminimize();
                         }
                     }
                 }
+                tauri::RunEvent::ExitRequested { .. } => {
+                    // Finalize any recording still in progress before the process goes away
+                    tauri::async_runtime::block_on(services::shutdown(app.clone()));
+                }
                 _ => {}
             }
         });
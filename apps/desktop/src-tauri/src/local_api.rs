@@ -0,0 +1,299 @@
+//! Local HTTP control API for scripting recordings without the UI.
+//!
+//! Listens on 127.0.0.1 only and requires `Authorization: Bearer <local_api_token>` on every request.
+//! Routes call the same services as the Tauri commands:
+//! - `POST /record/start`, `POST /record/stop`, `POST /record/toggle`
+//! - `GET /state`
+
+use serde::Serialize;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use crate::app_config::AppConfig;
+use crate::constants::{LOCAL_API_MAX_REQUEST_BYTES, LOCAL_API_REQUEST_TIMEOUT_MS};
+use crate::error::ErrorPayload;
+use crate::events::EventEmitter;
+use crate::services;
+use crate::{AppState, RecordingSource};
+
+/// Request line and headers of an API request (bodies are not used)
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+}
+
+/// Status line and JSON body of an API response
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status, body },
+            Err(e) => Self::error(500, "Serialization", e.to_string()),
+        }
+    }
+
+    fn error(status: u16, kind: &str, message: impl Into<String>) -> Self {
+        Self::json(status, &ErrorPayload { kind: kind.to_string(), message: message.into() })
+    }
+
+    fn from_error(error: ErrorPayload) -> Self {
+        // Recording errors are mostly wrong-state requests (e.g. stop while idle)
+        let status = if error.kind == "Recording" { 409 } else { 500 };
+        Self::json(status, &error)
+    }
+}
+
+#[derive(Serialize)]
+struct MessageBody {
+    message: String,
+}
+
+/// A running server: its accept loop and the token that stops it
+#[derive(Debug)]
+pub struct LocalApiServer {
+    cancel: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+/// Start the server if the API is enabled, or stop it. A running server is always restarted so
+/// port and token changes take effect; the port is bound before returning, so a port that is
+/// already in use is reported to the caller.
+pub async fn apply(state: &AppState, app_handle: &AppHandle, config: &AppConfig) -> Result<(), String> {
+    let running = state.local_api.lock().unwrap().take();
+    if let Some(server) = running {
+        server.cancel.cancel();
+        // The listener is dropped when the accept loop ends; wait for that so the port is free again
+        let _ = server.task.await;
+        info!("Local API stopped");
+    }
+
+    if !config.enable_local_api {
+        return Ok(());
+    }
+    let Some(token) = config.local_api_token.clone().filter(|token| !token.is_empty()) else {
+        return Err("Local API is enabled without a token".to_string());
+    };
+
+    let port = config.local_api_port;
+    let listener = bind(port).map_err(|e| format!("Failed to start local API on port {}: {}", port, e))?;
+    info!("Local API listening on 127.0.0.1:{}", port);
+
+    let cancel = CancellationToken::new();
+    let task = tauri::async_runtime::spawn(serve(app_handle.clone(), listener, token, cancel.clone()));
+    *state.local_api.lock().unwrap() = Some(LocalApiServer { cancel, task });
+    Ok(())
+}
+
+// Bind synchronously so errors reach `apply` instead of a detached task
+fn bind(port: u16) -> std::io::Result<TcpListener> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))?;
+    listener.set_nonblocking(true)?;
+    TcpListener::from_std(listener)
+}
+
+async fn serve(app_handle: AppHandle, listener: TcpListener, token: String, cancel: CancellationToken) {
+    loop {
+        let stream = tokio::select! {
+            _ = cancel.cancelled() => return,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!("Local API failed to accept a connection: {}", e);
+                    continue;
+                }
+            },
+        };
+        let app_handle = app_handle.clone();
+        let token = token.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle_connection(stream, &app_handle, &token).await {
+                warn!("Local API connection failed: {}", e);
+            }
+        });
+    }
+}
+
+// Serve one request per connection; the response closes it
+async fn handle_connection(mut stream: TcpStream, app_handle: &AppHandle, token: &str) -> std::io::Result<()> {
+    let timeout = Duration::from_millis(LOCAL_API_REQUEST_TIMEOUT_MS);
+    let response = match tokio::time::timeout(timeout, read_head(&mut stream)).await {
+        Err(_) => Response::error(408, "Request", "Request timed out"),
+        Ok(Err(e)) => return Err(e),
+        Ok(Ok(None)) => Response::error(431, "Request", "Request headers too large"),
+        Ok(Ok(Some(head))) => match parse_request(&head) {
+            None => Response::error(400, "Request", "Malformed request"),
+            Some(request) if !is_authorized(request.authorization.as_deref(), token) => {
+                Response::error(401, "Unauthorized", "Missing or invalid API token")
+            }
+            Some(request) => route(app_handle, &request.method, &request.path).await,
+        },
+    };
+
+    stream.write_all(format_response(&response).as_bytes()).await?;
+    stream.shutdown().await
+}
+
+// Read up to the blank line ending the headers; None if it doesn't arrive within the size limit
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > LOCAL_API_MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(Some(String::from_utf8_lossy(&head).into_owned()))
+}
+
+async fn route(app_handle: &AppHandle, method: &str, path: &str) -> Response {
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return Response::error(503, "System", "App is still starting");
+    };
+
+    match (method, path) {
+        ("POST", "/record/start") => match services::start_recording(state, app_handle.clone(), None, RecordingSource::Manual).await {
+            Ok(()) => {
+                EventEmitter::recording_state_changed(app_handle);
+                Response::json(200, &MessageBody { message: "Recording started".to_string() })
+            }
+            Err(e) => Response::from_error(e.into()),
+        },
+        ("POST", "/record/stop") => match services::stop_recording(state, app_handle.clone()).await {
+            Ok(recording) => {
+                EventEmitter::recording_state_changed(app_handle);
+                Response::json(200, &recording)
+            }
+            Err(e) => Response::from_error(e.into()),
+        },
+        ("POST", "/record/toggle") => match services::toggle_recording(state, app_handle.clone()).await {
+            Ok(message) => {
+                EventEmitter::recording_state_changed(app_handle);
+                Response::json(200, &MessageBody { message })
+            }
+            Err(e) => Response::from_error(e.into()),
+        },
+        ("GET", "/state") => match services::get_recording_info(state).await {
+            Ok(info) => Response::json(200, &info),
+            Err(e) => Response::from_error(e.into()),
+        },
+        (_, "/record/start" | "/record/stop" | "/record/toggle" | "/state") => {
+            Response::error(405, "Request", format!("{} is not allowed on {}", method, path))
+        }
+        _ => Response::error(404, "Request", format!("Unknown path: {}", path)),
+    }
+}
+
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    if !request_line.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    // Query strings aren't used by any route
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let authorization = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .map(|(_, value)| value.trim().to_string());
+    Some(Request { method, path, authorization })
+}
+
+// Accept only `Bearer <token>`, comparing in constant time so the token can't be guessed byte by byte
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    let (provided, expected) = (provided.trim().as_bytes(), token.as_bytes());
+    provided.len() == expected.len()
+        && provided.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn format_response(response: &Response) -> String {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let head = "POST /record/start?source=cli HTTP/1.1\r\nHost: 127.0.0.1\r\nauthorization:  Bearer abc123 \r\n\r\n";
+        assert_eq!(parse_request(head), Some(Request {
+            method: "POST".to_string(),
+            path: "/record/start".to_string(),
+            authorization: Some("Bearer abc123".to_string()),
+        }));
+
+        let head = "GET /state HTTP/1.0\r\n\r\n";
+        assert_eq!(parse_request(head).unwrap().authorization, None);
+
+        assert_eq!(parse_request("GET /state\r\n\r\n"), None);
+        assert_eq!(parse_request(""), None);
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        assert!(is_authorized(Some("Bearer secret"), "secret"));
+        assert!(!is_authorized(Some("Bearer secreT"), "secret"));
+        assert!(!is_authorized(Some("Bearer secret2"), "secret"));
+        assert!(!is_authorized(Some("secret"), "secret"));
+        assert!(!is_authorized(None, "secret"));
+    }
+
+    #[tokio::test]
+    async fn test_bind_reports_port_in_use_until_released() {
+        let listener = bind(0).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(bind(port).is_err());
+
+        drop(listener);
+        assert!(bind(port).is_ok());
+    }
+
+    #[test]
+    fn test_format_response() {
+        let response = Response::error(401, "Unauthorized", "Missing or invalid API token");
+        let text = format_response(&response);
+        assert!(text.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(text.ends_with(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n{}", response.body.len(), response.body)));
+    }
+}
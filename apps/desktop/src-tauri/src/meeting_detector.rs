@@ -1,31 +1,195 @@
+use std::collections::HashSet;
 use std::process::Command;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use std::thread::{self, JoinHandle};
 use std::time::Duration;
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
 use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 use crate::constants::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MeetingApp {
     Zoom,
     SlackHuddle,
     GoogleMeet,
     MicrosoftTeams,
     Discord,
+    Webex,
     Unknown(String),
 }
 
+impl MeetingApp {
+    /// Apps with a dedicated detector (custom patterns and `Unknown` matches are always checked)
+    pub fn known_apps() -> HashSet<MeetingApp> {
+        [
+            MeetingApp::Zoom,
+            MeetingApp::SlackHuddle,
+            MeetingApp::GoogleMeet,
+            MeetingApp::MicrosoftTeams,
+            MeetingApp::Discord,
+            MeetingApp::Webex,
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    /// Human-readable name, e.g. "Google Meet"
+    pub fn display_name(&self) -> &str {
+        match self {
+            MeetingApp::Zoom => "Zoom",
+            MeetingApp::SlackHuddle => "Slack Huddle",
+            MeetingApp::GoogleMeet => "Google Meet",
+            MeetingApp::MicrosoftTeams => "Microsoft Teams",
+            MeetingApp::Discord => "Discord",
+            MeetingApp::Webex => "Webex",
+            MeetingApp::Unknown(name) => name,
+        }
+    }
+}
+
+/// What the app does when a meeting is detected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingAction {
+    /// Show the notification window and let the user decide
+    #[default]
+    Prompt,
+    /// Start recording right away and stop when the meeting ends
+    AutoRecord,
+    /// Do nothing
+    Ignore,
+}
+
+/// Which kind of check found a meeting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionMethod {
+    /// A meeting app process with an active call window
+    Process,
+    /// A meeting URL in an open browser tab
+    BrowserUrl,
+    /// A browser window title that looks like a meeting (Linux)
+    WindowTitle,
+    /// A meeting app (or browser) holding the microphone
+    Microphone,
+}
+
+impl DetectionMethod {
+    /// How much to trust a detection by this method, from 0 to 1
+    pub fn confidence(self) -> f32 {
+        match self {
+            // Room URLs are specific; call windows can linger briefly after hanging up
+            DetectionMethod::BrowserUrl => 0.95,
+            DetectionMethod::Process => 0.85,
+            // Titles match loosely worded patterns such as "meeting"
+            DetectionMethod::WindowTitle => 0.7,
+            // The microphone is also used for voice notes, dictation, etc.
+            DetectionMethod::Microphone => 0.5,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MeetingState {
     pub is_in_meeting: bool,
     pub detected_app: Option<MeetingApp>,
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Title of the meeting's browser tab or app window, when it could be read
+    pub meeting_title: Option<String>,
+    /// How the meeting was found on the latest poll that saw it
+    #[serde(default)]
+    pub detection_method: Option<DetectionMethod>,
+    /// `DetectionMethod::confidence` of `detection_method` (0 when not in a meeting)
+    #[serde(default)]
+    pub confidence: f32,
+}
+
+/// User-configurable detection settings, reloadable while monitoring is running
+#[derive(Debug, Clone)]
+pub struct DetectionSettings {
+    /// Extra URL substrings (case-insensitive) that indicate a meeting, e.g. "meet.mycorp.com"
+    pub custom_meeting_patterns: Vec<String>,
+    /// How long detection must stay negative before a meeting is considered ended
+    pub meeting_end_grace_secs: u64,
+    /// Apps whose detectors run; apps missing from the set are skipped
+    pub detect_apps: HashSet<MeetingApp>,
+    /// Read browser tabs (active tab URLs on macOS, window titles on Linux); off = processes and microphone only
+    pub scan_browser_urls: bool,
+}
+
+impl Default for DetectionSettings {
+    fn default() -> Self {
+        Self {
+            custom_meeting_patterns: Vec::new(),
+            meeting_end_grace_secs: DEFAULT_MEETING_END_GRACE_SECS,
+            detect_apps: MeetingApp::known_apps(),
+            scan_browser_urls: true,
+        }
+    }
+}
+
+impl DetectionSettings {
+    /// Number of consecutive negative polls required to end a meeting (at least one)
+    pub fn end_grace_polls(&self) -> u64 {
+        self.meeting_end_grace_secs.div_ceil(MEETING_POLL_INTERVAL_SECS).max(1)
+    }
+
+    /// Whether detection for the given app is enabled (custom/unknown matches always are)
+    pub fn is_enabled(&self, app: &MeetingApp) -> bool {
+        matches!(app, MeetingApp::Unknown(_)) || self.detect_apps.contains(app)
+    }
+}
+
+/// Raw signals from one detection pass, for debugging why a meeting was or wasn't detected
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DetectionProbe {
+    /// Meeting app processes that are running (not necessarily in a meeting)
+    pub meeting_processes: Vec<String>,
+    /// Active browser tab URLs (macOS), without scheme, query or fragment and truncated
+    pub browser_urls: Vec<String>,
+    /// Window titles checked for web meetings (Linux), truncated
+    pub window_titles: Vec<String>,
+    /// Check that found the meeting
+    pub matched_by: Option<DetectionMethod>,
+    pub detected_app: Option<MeetingApp>,
 }
 
 #[derive(Debug)]
 pub struct MeetingDetector {
     state: Arc<Mutex<MeetingState>>,
     monitoring: Arc<Mutex<bool>>,
+    settings: Arc<Mutex<DetectionSettings>>,
+}
+
+/// Background thread that calls a tick function at a fixed interval until stopped
+#[derive(Debug)]
+pub struct MeetingWatcher {
+    // Dropping the sender wakes the thread and ends it
+    stop_sender: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MeetingWatcher {
+    pub fn spawn(interval: Duration, mut tick: impl FnMut() + Send + 'static) -> Self {
+        let (stop_sender, stop_receiver) = bounded::<()>(0);
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(interval) {
+                tick();
+            }
+        });
+        Self { stop_sender: Some(stop_sender), thread: Some(thread) }
+    }
+
+    /// Signal the thread to stop and wait for the current tick to finish
+    pub fn stop(mut self) {
+        drop(self.stop_sender.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                warn!("Meeting watcher thread panicked");
+            }
+        }
+    }
 }
 
 impl MeetingDetector {
@@ -35,11 +199,20 @@ impl MeetingDetector {
                 is_in_meeting: false,
                 detected_app: None,
                 started_at: None,
+                meeting_title: None,
+                detection_method: None,
+                confidence: 0.0,
             })),
             monitoring: Arc::new(Mutex::new(false)),
+            settings: Arc::new(Mutex::new(DetectionSettings::default())),
         }
     }
 
+    /// Replace the detection settings; picked up on the next detection pass
+    pub fn update_settings(&self, settings: DetectionSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
     pub fn start_monitoring(&self) -> Result<(), String> {
         let mut monitoring = self.monitoring.lock().unwrap();
         if *monitoring {
@@ -49,33 +222,28 @@ impl MeetingDetector {
 
         let state = self.state.clone();
         let monitoring_flag = self.monitoring.clone();
+        let settings = self.settings.clone();
 
         thread::spawn(move || {
+            // Consecutive polls without a detected meeting while one is in progress
+            let mut missed_polls: u64 = 0;
+            
             while *monitoring_flag.lock().unwrap() {
-                let meeting_detected = detect_meeting_apps();
-                
-                let mut current_state = state.lock().unwrap();
+                let current_settings = settings.lock().unwrap().clone();
+                let meeting_detected = detect_meeting_apps(&current_settings);
                 
-                if let Some(app) = meeting_detected {
-                    if !current_state.is_in_meeting {
-                        // Meeting just started
-                        current_state.is_in_meeting = true;
-                        current_state.detected_app = Some(app.clone());
-                        current_state.started_at = Some(chrono::Utc::now());
-                        println!("Meeting detected: {:?}", app);
-                    }
+                // Read the meeting title once, when a meeting starts, before taking the state lock
+                let is_new_meeting = meeting_detected.is_some() && !state.lock().unwrap().is_in_meeting;
+                let meeting_title = if is_new_meeting {
+                    meeting_detected.as_ref().and_then(|(app, _)| lookup_meeting_title(app, &current_settings))
                 } else {
-                    if current_state.is_in_meeting {
-                        // Meeting just ended
-                        current_state.is_in_meeting = false;
-                        current_state.detected_app = None;
-                        current_state.started_at = None;
-                        println!("Meeting ended");
-                    }
-                }
+                    None
+                };
                 
+                let mut current_state = state.lock().unwrap();
+                apply_detection(&mut current_state, meeting_detected, meeting_title, &mut missed_polls, current_settings.end_grace_polls());
                 drop(current_state);
-                thread::sleep(Duration::from_secs(5)); // Check every 5 seconds
+                thread::sleep(Duration::from_secs(MEETING_POLL_INTERVAL_SECS));
             }
         });
 
@@ -87,108 +255,275 @@ impl MeetingDetector {
         *monitoring = false;
     }
 
+    pub fn is_monitoring(&self) -> bool {
+        *self.monitoring.lock().unwrap()
+    }
+
     pub fn get_state(&self) -> MeetingState {
         self.state.lock().unwrap().clone()
     }
+
+    /// Run one detection pass right away with the current settings, whether or not monitoring is on
+    pub fn probe(&self) -> DetectionProbe {
+        let settings = self.settings.lock().unwrap().clone();
+        let mut probe = collect_detection_signals(&settings);
+        if let Some((app, method)) = detect_meeting_apps(&settings) {
+            probe.detected_app = Some(app);
+            probe.matched_by = Some(method);
+        }
+        probe
+    }
+}
+
+// Update the meeting state with one poll's detection result. A meeting starts on the first poll
+// that detects it and ends after `end_grace_polls` consecutive polls that don't, counted in `missed_polls`.
+fn apply_detection(
+    state: &mut MeetingState,
+    detected: Option<(MeetingApp, DetectionMethod)>,
+    meeting_title: Option<String>,
+    missed_polls: &mut u64,
+    end_grace_polls: u64,
+) {
+    if let Some((app, method)) = detected {
+        *missed_polls = 0;
+        state.detection_method = Some(method);
+        state.confidence = method.confidence();
+        if !state.is_in_meeting {
+            // Meeting just started
+            state.is_in_meeting = true;
+            state.detected_app = Some(app.clone());
+            state.started_at = Some(chrono::Utc::now());
+            state.meeting_title = meeting_title;
+            info!("Meeting detected: {:?} ({:?})", app, state.meeting_title);
+        }
+    } else if state.is_in_meeting {
+        // Tolerate transient misses (tab reloads, AppleScript hiccups) before ending
+        *missed_polls += 1;
+        if *missed_polls >= end_grace_polls {
+            // Meeting just ended
+            state.is_in_meeting = false;
+            state.detected_app = None;
+            state.started_at = None;
+            state.meeting_title = None;
+            state.detection_method = None;
+            state.confidence = 0.0;
+            *missed_polls = 0;
+            info!("Meeting ended");
+        } else {
+            info!("Meeting not detected ({}/{} polls), waiting before ending", missed_polls, end_grace_polls);
+        }
+    }
 }
 
 // Platform-specific meeting detection
+// Returns the detected app and the check that found it
 #[cfg(target_os = "macos")]
-fn detect_meeting_apps() -> Option<MeetingApp> {
-    // Checking for meeting apps...
-    
+fn detect_meeting_apps(settings: &DetectionSettings) -> Option<(MeetingApp, DetectionMethod)> {
+    run_macos_checks(settings, &MacosChecks {
+        processes: &check_running_processes,
+        browser_urls: &check_browser_meeting_urls,
+        microphone: &check_microphone_usage,
+        dia_microphone: &check_dia_urls,
+    })
+}
+
+// The macOS detection checks, each querying the system
+#[cfg(any(target_os = "macos", test))]
+struct MacosChecks<'a> {
+    processes: &'a dyn Fn(&DetectionSettings) -> Option<MeetingApp>,
+    browser_urls: &'a dyn Fn(&DetectionSettings) -> Option<MeetingApp>,
+    microphone: &'a dyn Fn(&DetectionSettings) -> Option<MeetingApp>,
+    dia_microphone: &'a dyn Fn() -> Option<MeetingApp>,
+}
+
+// Run the checks in order of confidence, returning the first match
+#[cfg(any(target_os = "macos", test))]
+fn run_macos_checks(settings: &DetectionSettings, checks: &MacosChecks) -> Option<(MeetingApp, DetectionMethod)> {
     // Method 1: Check for known meeting app processes
-    if let Some(app) = check_running_processes() {
-        println!("Meeting detected via process: {:?}", app);
-        return Some(app);
+    if let Some(app) = (checks.processes)(settings) {
+        info!("Meeting detected via process: {:?}", app);
+        return Some((app, DetectionMethod::Process));
     }
 
     // Method 2: Check browser tabs for meeting URLs (prioritize this for web meetings)
-    if let Some(app) = check_browser_meeting_urls() {
-        println!("Meeting detected via browser: {:?}", app);
-        return Some(app);
+    if settings.scan_browser_urls {
+        if let Some(app) = (checks.browser_urls)(settings) {
+            info!("Meeting detected via browser: {:?}", app);
+            return Some((app, DetectionMethod::BrowserUrl));
+        }
+        
+        // Dia browser doesn't expose its tabs to AppleScript, so only its microphone use is visible
+        if let Some(app) = (checks.dia_microphone)() {
+            info!("Meeting detected via microphone: {:?}", app);
+            return Some((app, DetectionMethod::Microphone));
+        }
     }
 
     // Method 3: Check for microphone usage by specific apps
-    if let Some(app) = check_microphone_usage() {
-        println!("Meeting detected via microphone: {:?}", app);
-        return Some(app);
+    if let Some(app) = (checks.microphone)(settings) {
+        info!("Meeting detected via microphone: {:?}", app);
+        return Some((app, DetectionMethod::Microphone));
     }
 
     None
 }
 
+// Process names the macOS checks look for
 #[cfg(target_os = "macos")]
-fn check_running_processes() -> Option<MeetingApp> {
-    // Use ps command to list processes
+const MACOS_MEETING_PROCESSES: &[&str] = &[
+    "zoom.us", "CptHost", "Slack", "Microsoft Teams", "Discord", "Cisco Webex Meetings", "Webex", "Dia",
+];
+
+// Executable paths of every running process, one per line
+#[cfg(target_os = "macos")]
+fn list_process_commands() -> Option<String> {
     let output = Command::new("ps")
-        .args(&["aux"])
+        .args(&["-axo", "comm="])
         .output()
         .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-    let processes = String::from_utf8_lossy(&output.stdout);
+// Helper function to reduce `ps -axo comm=` output to executable names, so "zoom.us" can't match "zoom.usage"
+#[cfg(any(target_os = "macos", test))]
+fn process_names(ps_output: &str) -> Vec<&str> {
+    ps_output.lines()
+        .map(|line| line.trim())
+        .map(|path| path.rsplit('/').next().unwrap_or(path))
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn collect_detection_signals(settings: &DetectionSettings) -> DetectionProbe {
+    let output = list_process_commands().unwrap_or_default();
+    let processes = process_names(&output);
+    let urls: Vec<String> = if settings.scan_browser_urls {
+        [chrome_tab_urls(), safari_tab_urls()].into_iter().flatten().collect()
+    } else {
+        Vec::new()
+    };
+
+    DetectionProbe {
+        meeting_processes: MACOS_MEETING_PROCESSES.iter()
+            .filter(|name| processes.contains(name))
+            .map(|name| name.to_string())
+            .collect(),
+        browser_urls: urls.iter()
+            .flat_map(|urls| urls.split_whitespace())
+            .map(redact_url)
+            .collect(),
+        ..DetectionProbe::default()
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_running_processes(settings: &DetectionSettings) -> Option<MeetingApp> {
+    // Compare whole executable names rather than searching the raw `ps` output
+    let output = list_process_commands()?;
+    let names = process_names(&output);
+    let is_running = |name: &str| names.contains(&name);
     
     // Check for Zoom
-    if processes.contains("zoom.us") || processes.contains("CptHost") {
+    if settings.is_enabled(&MeetingApp::Zoom) && (is_running("zoom.us") || is_running("CptHost")) {
         // Additional check: Zoom creates specific processes during meetings
-        if processes.contains("CptHost") || check_zoom_meeting_window() {
+        if is_running("CptHost") || check_zoom_meeting_window() {
             return Some(MeetingApp::Zoom);
         }
     }
 
     // Check for Slack (Huddle detection is trickier)
-    if processes.contains("Slack") && check_slack_huddle_active() {
+    if settings.is_enabled(&MeetingApp::SlackHuddle) && is_running("Slack") && check_slack_huddle_active() {
         return Some(MeetingApp::SlackHuddle);
     }
 
     // Check for Microsoft Teams
-    if processes.contains("Microsoft Teams") && check_teams_call_active() {
+    if settings.is_enabled(&MeetingApp::MicrosoftTeams) && is_running("Microsoft Teams") && check_teams_call_active() {
         return Some(MeetingApp::MicrosoftTeams);
     }
 
     // Check for Discord
-    if processes.contains("Discord") && check_discord_voice_active() {
+    if settings.is_enabled(&MeetingApp::Discord) && is_running("Discord") && check_discord_voice_active() {
         return Some(MeetingApp::Discord);
     }
 
+    // Check for Cisco Webex (older installs use "Cisco Webex Meetings" while in a meeting)
+    if settings.is_enabled(&MeetingApp::Webex)
+        && (is_running("Cisco Webex Meetings")
+            || (is_running("Webex") && check_webex_meeting_active())) {
+        return Some(MeetingApp::Webex);
+    }
+
     None
 }
 
 #[cfg(target_os = "macos")]
-fn check_microphone_usage() -> Option<MeetingApp> {
-    // Use system_profiler to check audio input
+fn check_microphone_usage(settings: &DetectionSettings) -> Option<MeetingApp> {
+    // Use system_profiler to inspect the audio devices
     let output = Command::new("system_profiler")
         .args(&["SPAudioDataType", "-json"])
         .output()
         .ok()?;
 
-    let audio_info = String::from_utf8_lossy(&output.stdout);
+    let audio_info: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
     
-    // Parse JSON and check for active audio sessions
-    // This is a simplified version - you'd need proper JSON parsing
-    if audio_info.contains("zoom") {
-        return Some(MeetingApp::Zoom);
+    let app = default_input_device_app(&audio_info)?;
+    if settings.is_enabled(&app) { Some(app) } else { None }
+}
+
+// Virtual input devices installed by meeting apps; when one is the default input the app holds the mic
+#[cfg(any(target_os = "macos", test))]
+const MEETING_INPUT_DEVICES: &[(&str, MeetingApp)] = &[
+    ("zoomaudiodevice", MeetingApp::Zoom),
+    ("microsoft teams audio", MeetingApp::MicrosoftTeams),
+    ("webex", MeetingApp::Webex),
+    ("discord", MeetingApp::Discord),
+];
+
+// Helper function to map the default input device in `system_profiler SPAudioDataType -json` output to an app
+#[cfg(any(target_os = "macos", test))]
+fn default_input_device_app(audio_info: &serde_json::Value) -> Option<MeetingApp> {
+    let devices = audio_info.get("SPAudioDataType")?.as_array()?
+        .iter()
+        .filter_map(|group| group.get("_items")?.as_array())
+        .flatten();
+    
+    for device in devices {
+        // Only the default input counts; the system device is the default output
+        let is_default_input = device.get("coreaudio_default_audio_input_device")
+            .and_then(|v| v.as_str()) == Some("spaudio_yes");
+        if !is_default_input {
+            continue;
+        }
+        
+        let identifiers: Vec<String> = ["_name", "coreaudio_device_manufacturer", "coreaudio_input_source"]
+            .iter()
+            .filter_map(|field| device.get(*field)?.as_str())
+            .map(|value| value.to_lowercase())
+            .collect();
+        
+        let app = MEETING_INPUT_DEVICES.iter()
+            .find(|(needle, _)| identifiers.iter().any(|id| id.contains(needle)))
+            .map(|(_, app)| app.clone());
+        if app.is_some() {
+            return app;
+        }
     }
     
     None
 }
 
 #[cfg(target_os = "macos")]
-fn check_browser_meeting_urls() -> Option<MeetingApp> {
+fn check_browser_meeting_urls(settings: &DetectionSettings) -> Option<MeetingApp> {
     // Checking browser URLs...
     
     // Check Chrome specifically first (most common for Google Meet)
-    if let Some(app) = check_chrome_urls() {
-        return Some(app);
-    }
-    
-    // Check Dia browser (Chromium-based)
-    if let Some(app) = check_dia_urls() {
+    if let Some(app) = check_chrome_urls(settings) {
         return Some(app);
     }
     
     // Check Safari
-    if let Some(app) = check_safari_urls() {
+    if let Some(app) = check_safari_urls(settings) {
         return Some(app);
     }
     
@@ -196,16 +531,23 @@ fn check_browser_meeting_urls() -> Option<MeetingApp> {
 }
 
 #[cfg(target_os = "macos")]
-fn check_chrome_urls() -> Option<MeetingApp> {
+fn check_chrome_urls(settings: &DetectionSettings) -> Option<MeetingApp> {
+    let app = match_meeting_urls(&chrome_tab_urls()?, settings)?;
+    info!("Found {:?} meeting URL in Chrome", app);
+    Some(app)
+}
+
+// Space-separated URLs of the active tab in each Chrome window (empty if Chrome isn't running);
+// background tabs are never read
+#[cfg(target_os = "macos")]
+fn chrome_tab_urls() -> Option<String> {
     let script = r#"
         tell application "System Events"
             if exists (processes where name is "Google Chrome") then
                 tell application "Google Chrome"
                     set allUrls to ""
                     repeat with w in windows
-                        repeat with t in tabs of w
-                            set allUrls to allUrls & (URL of t) & " "
-                        end repeat
+                        set allUrls to allUrls & (URL of active tab of w) & " "
                     end repeat
                     return allUrls
                 end tell
@@ -219,28 +561,7 @@ fn check_chrome_urls() -> Option<MeetingApp> {
         .output()
         .ok()?;
 
-    let urls = String::from_utf8_lossy(&output.stdout);
-    // Chrome URLs found: {urls}
-    
-    // Check for Google Meet - only actual meeting rooms, not landing pages  
-    if is_google_meet_room(&urls) {
-        println!("Google Meet detected in Chrome");
-        return Some(MeetingApp::GoogleMeet);
-    }
-    if urls.contains("zoom.us/j/") || urls.contains("zoom.us/wc/") {
-        println!("Zoom meeting detected in Chrome");
-        return Some(MeetingApp::Zoom);
-    }
-    if urls.contains("teams.microsoft.com/l/meetup-join") || urls.contains("teams.live.com") {
-        println!("Found Teams URL in Chrome");
-        return Some(MeetingApp::MicrosoftTeams);
-    }
-    if urls.contains("app.slack.com") && (urls.contains("/huddle/") || urls.contains("huddle")) {
-        println!("Found Slack Huddle URL in Chrome");
-        return Some(MeetingApp::SlackHuddle);
-    }
-
-    None
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 #[cfg(target_os = "macos")]
@@ -284,16 +605,47 @@ fn check_dia_microphone_usage() -> bool {
 }
 
 #[cfg(target_os = "macos")]
-fn check_safari_urls() -> Option<MeetingApp> {
+fn check_safari_urls(settings: &DetectionSettings) -> Option<MeetingApp> {
+    let app = match_meeting_urls(&safari_tab_urls()?, settings)?;
+    info!("Found {:?} meeting URL in Safari", app);
+    Some(app)
+}
+
+// Helper function to find the meeting among space-separated browser tab URLs
+#[cfg(any(target_os = "macos", test))]
+fn match_meeting_urls(urls: &str, settings: &DetectionSettings) -> Option<MeetingApp> {
+    // Check for Google Meet - only actual meeting rooms, not landing pages
+    if settings.is_enabled(&MeetingApp::GoogleMeet) && is_google_meet_room(urls) {
+        return Some(MeetingApp::GoogleMeet);
+    }
+    if settings.is_enabled(&MeetingApp::Zoom) && (urls.contains("zoom.us/j/") || urls.contains("zoom.us/wc/")) {
+        return Some(MeetingApp::Zoom);
+    }
+    if settings.is_enabled(&MeetingApp::MicrosoftTeams)
+        && (urls.contains("teams.microsoft.com/l/meetup-join") || urls.contains("teams.live.com")) {
+        return Some(MeetingApp::MicrosoftTeams);
+    }
+    if settings.is_enabled(&MeetingApp::SlackHuddle)
+        && urls.contains("app.slack.com") && (urls.contains("/huddle/") || urls.contains("huddle")) {
+        return Some(MeetingApp::SlackHuddle);
+    }
+    if settings.is_enabled(&MeetingApp::Webex) && is_webex_meeting_url(urls) {
+        return Some(MeetingApp::Webex);
+    }
+    match_custom_pattern(urls, &settings.custom_meeting_patterns)
+        .map(|pattern| MeetingApp::Unknown(pattern.to_string()))
+}
+
+// Space-separated URLs of the current tab in each Safari window (empty if Safari isn't running)
+#[cfg(target_os = "macos")]
+fn safari_tab_urls() -> Option<String> {
     let script = r#"
         tell application "System Events"
             if exists (processes where name is "Safari") then
                 tell application "Safari"
                     set allUrls to ""
                     repeat with w in windows
-                        repeat with t in tabs of w
-                            set allUrls to allUrls & (URL of t) & " "
-                        end repeat
+                        set allUrls to allUrls & (URL of current tab of w) & " "
                     end repeat
                     return allUrls
                 end tell
@@ -307,28 +659,7 @@ fn check_safari_urls() -> Option<MeetingApp> {
         .output()
         .ok()?;
 
-    let urls = String::from_utf8_lossy(&output.stdout);
-    // Safari URLs checked
-    
-    // Check for Google Meet - only actual meeting rooms, not landing pages  
-    if is_google_meet_room(&urls) {
-        println!("Found Google Meet room URL in Safari");
-        return Some(MeetingApp::GoogleMeet);
-    }
-    if urls.contains("zoom.us/j/") || urls.contains("zoom.us/wc/") {
-        println!("Found Zoom URL in Safari");
-        return Some(MeetingApp::Zoom);
-    }
-    if urls.contains("teams.microsoft.com/l/meetup-join") || urls.contains("teams.live.com") {
-        println!("Found Teams URL in Safari");
-        return Some(MeetingApp::MicrosoftTeams);
-    }
-    if urls.contains("app.slack.com") && (urls.contains("/huddle/") || urls.contains("huddle")) {
-        println!("Found Slack Huddle URL in Safari");
-        return Some(MeetingApp::SlackHuddle);
-    }
-
-    None
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 // Specific app detection helpers
@@ -429,53 +760,744 @@ fn check_discord_voice_active() -> bool {
         .unwrap_or(false)
 }
 
+// Meeting title lookup, run once when a meeting starts
+#[cfg(target_os = "macos")]
+fn lookup_meeting_title(app: &MeetingApp, settings: &DetectionSettings) -> Option<String> {
+    // Tab titles are only read when browser scanning is allowed
+    let browser_tab_title = |url_fragment: &str| {
+        if settings.scan_browser_urls { browser_tab_title(url_fragment) } else { None }
+    };
+    let raw_title = match app {
+        MeetingApp::GoogleMeet => browser_tab_title("meet.google.com/"),
+        MeetingApp::Zoom => app_window_title("zoom.us", "Zoom Meeting")
+            .or_else(|| browser_tab_title("zoom.us/")),
+        MeetingApp::MicrosoftTeams => app_window_title("Microsoft Teams", "Meeting")
+            .or_else(|| browser_tab_title("teams.microsoft.com")),
+        MeetingApp::SlackHuddle => app_window_title("Slack", "uddle"),
+        MeetingApp::Discord => app_window_title("Discord", "Voice Connected"),
+        MeetingApp::Webex => app_window_title("Webex", "Meeting")
+            .or_else(|| browser_tab_title(".webex.com/")),
+        MeetingApp::Unknown(pattern) => browser_tab_title(pattern),
+    }?;
+
+    clean_meeting_title(&raw_title)
+}
+
+#[cfg(target_os = "macos")]
+fn browser_tab_title(url_fragment: &str) -> Option<String> {
+    // Chrome exposes the tab title as "title", Safari as "name"
+    let chrome_script = r#"
+        tell application "System Events"
+            if exists (processes where name is "Google Chrome") then
+                tell application "Google Chrome"
+                    repeat with w in windows
+                        repeat with t in tabs of w
+                            if (URL of t) contains "{fragment}" then return title of t
+                        end repeat
+                    end repeat
+                end tell
+            end if
+        end tell
+        return ""
+    "#;
+    let safari_script = r#"
+        tell application "System Events"
+            if exists (processes where name is "Safari") then
+                tell application "Safari"
+                    repeat with w in windows
+                        repeat with t in tabs of w
+                            if (URL of t) contains "{fragment}" then return name of t
+                        end repeat
+                    end repeat
+                end tell
+            end if
+        end tell
+        return ""
+    "#;
+
+    let fragment = escape_applescript_string(url_fragment);
+    [chrome_script, safari_script]
+        .iter()
+        .find_map(|script| run_title_script(&script.replace("{fragment}", &fragment)))
+}
+
+#[cfg(target_os = "macos")]
+fn app_window_title(process_name: &str, indicator: &str) -> Option<String> {
+    let script = r#"
+        tell application "System Events"
+            if application "{process}" is running then
+                tell application process "{process}"
+                    repeat with w in windows
+                        if (title of w) contains "{indicator}" then return title of w
+                    end repeat
+                end tell
+            end if
+        end tell
+        return ""
+    "#
+    .replace("{process}", &escape_applescript_string(process_name))
+    .replace("{indicator}", &escape_applescript_string(indicator));
+
+    run_title_script(&script)
+}
+
+#[cfg(target_os = "macos")]
+fn run_title_script(script: &str) -> Option<String> {
+    let output = Command::new("osascript")
+        .args(&["-e", script])
+        .output()
+        .ok()?;
+
+    let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if title.is_empty() { None } else { Some(title) }
+}
+
+#[cfg(target_os = "macos")]
+fn escape_applescript_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(target_os = "macos")]
+fn check_webex_meeting_active() -> bool {
+    // Webex names its meeting window after the meeting, separate from the main "Webex" window
+    let script = r#"
+        tell application "System Events"
+            if application "Webex" is running then
+                tell application process "Webex"
+                    set windowTitles to title of windows
+                    return windowTitles as string
+                end tell
+            end if
+        end tell
+        return ""
+    "#;
+
+    Command::new("osascript")
+        .args(&["-e", script])
+        .output()
+        .map(|output| {
+            let titles = String::from_utf8_lossy(&output.stdout);
+            titles.contains("Meeting") || titles.contains("Personal Room")
+        })
+        .unwrap_or(false)
+}
+
+// Helper function to detect Webex meeting pages (e.g. mycompany.webex.com/meet/jdoe)
+#[cfg(any(target_os = "macos", test))]
+fn is_webex_meeting_url(urls: &str) -> bool {
+    urls.contains(".webex.com/meet") || urls.contains(".webex.com/wbxmjs")
+}
+
+// Helper function to find the first user-configured pattern contained in the given URLs/titles
+fn match_custom_pattern<'a>(haystack: &str, patterns: &'a [String]) -> Option<&'a str> {
+    let haystack = haystack.to_lowercase();
+    patterns.iter()
+        .map(|pattern| pattern.trim())
+        .find(|pattern| !pattern.is_empty() && haystack.contains(&pattern.to_lowercase()))
+}
+
+// Helper function to turn a raw tab/window title into a meeting label,
+// e.g. "Meet - Weekly sync - Google Chrome" -> "Weekly sync"
+fn clean_meeting_title(raw_title: &str) -> Option<String> {
+    const BROWSER_SUFFIXES: &[&str] = &[
+        " - Google Chrome", " - Chromium", " - Brave", " - Microsoft Edge",
+        " - Mozilla Firefox", " \u{2014} Mozilla Firefox",
+    ];
+    const APP_PREFIXES: &[&str] = &["Meet - ", "Meet \u{2013} "];
+
+    let mut title = raw_title.trim();
+    if let Some(stripped) = BROWSER_SUFFIXES.iter().find_map(|suffix| title.strip_suffix(suffix)) {
+        title = stripped;
+    }
+    if let Some(stripped) = APP_PREFIXES.iter().find_map(|prefix| title.strip_prefix(prefix)) {
+        title = stripped;
+    }
+
+    let title = title.trim();
+    if title.is_empty() { None } else { Some(title.to_string()) }
+}
+
+// Helper function to shorten a URL for diagnostics: no scheme, query string or fragment
+#[cfg(any(target_os = "macos", test))]
+fn redact_url(url: &str) -> String {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    truncate_chars(url, PROBE_MAX_SIGNAL_CHARS)
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux", test))]
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}…", truncated)
+}
+
 // Helper function to detect actual Google Meet rooms vs landing pages
+#[cfg(any(target_os = "macos", test))]
 fn is_google_meet_room(urls: &str) -> bool {
-    if !urls.contains("meet.google.com/") {
-        return false;
-    }
+    urls.split_whitespace().any(|url| {
+        let Some(start) = url.find("meet.google.com/") else {
+            return false;
+        };
+        let path = &url[start + "meet.google.com/".len()..];
+        
+        // Query strings and fragments can contain dashes of their own, so only the path counts
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        
+        // meet.google.com/lookup/xxx (lookup URLs)
+        if path.starts_with("lookup/") {
+            println!("Detected lookup meeting URL: {}", path);
+            return true;
+        }
+        
+        // meet.google.com/abc-defg-hij (room code)
+        let code = path.split('/').next().unwrap_or_default();
+        if is_meet_room_code(code) {
+            println!("Detected meeting room pattern: {}", code);
+            return true;
+        }
+        
+        false
+    })
+}
+
+// Helper function to match the Meet room code shape, e.g. "abc-defg-hij"
+#[cfg(any(target_os = "macos", test))]
+fn is_meet_room_code(code: &str) -> bool {
+    // Letters per dash-separated group (xxx-xxxx-xxx)
+    const ROOM_CODE_GROUPS: [usize; 3] = [3, 4, 3];
     
-    // Exclude landing pages and general pages
-    if urls.contains("meet.google.com/landing") || 
-       urls.contains("meet.google.com/_meet") ||
-       urls.contains("meet.google.com/?") ||
-       urls.ends_with("meet.google.com/") {
-        return false;
+    let groups: Vec<&str> = code.split('-').collect();
+    groups.len() == ROOM_CODE_GROUPS.len()
+        && groups.iter().zip(ROOM_CODE_GROUPS).all(|(group, len)| {
+            group.len() == len && group.chars().all(|c| c.is_ascii_lowercase())
+        })
+}
+
+// Returns the detected app and the check that found it
+#[cfg(target_os = "linux")]
+fn detect_meeting_apps(settings: &DetectionSettings) -> Option<(MeetingApp, DetectionMethod)> {
+    // AppleScript isn't available, so rely on process names and window titles (via wmctrl)
+    let processes = list_process_names()?;
+    let titles = list_window_titles().unwrap_or_default();
+    detect_from_processes_and_titles(&processes, &titles, settings)
+}
+
+// Match lowercased `ps -e -o comm=` and `wmctrl -l` output against the meeting apps
+#[cfg(any(target_os = "linux", test))]
+fn detect_from_processes_and_titles(processes: &str, titles: &str, settings: &DetectionSettings) -> Option<(MeetingApp, DetectionMethod)> {
+    // Method 1: Check for known meeting app processes with an active call window
+    if let Some(app) = check_running_processes_linux(processes, titles, settings) {
+        info!("Meeting detected via process: {:?}", app);
+        return Some((app, DetectionMethod::Process));
     }
-    
-    // Check for actual meeting room patterns:
-    // meet.google.com/abc-def-ghi (3 segments separated by dashes)
-    // meet.google.com/lookup/xxx (lookup URLs)
-    // meet.google.com/xxx-xxx-xxx?params (with parameters)
-    
-    // Use regex-like pattern matching
-    for line in urls.lines() {
-        if line.contains("meet.google.com/") {
-            // Extract the part after meet.google.com/
-            if let Some(start) = line.find("meet.google.com/") {
-                let after_domain = &line[start + 16..]; // "meet.google.com/".len() = 16
-                
-                // Check for room code patterns (3 groups of letters/numbers separated by dashes)
-                if after_domain.contains('-') && 
-                   after_domain.chars().take(MEETING_URL_MAX_CHARS).filter(|&c| c == '-').count() >= MEETING_URL_MIN_DASHES {
-                    println!("Detected meeting room pattern: {}", after_domain);
-                    return true;
-                }
-                
-                // Check for lookup URLs
-                if after_domain.starts_with("lookup/") {
-                    println!("Detected lookup meeting URL: {}", after_domain);
-                    return true;
-                }
-            }
+
+    // Method 2: Check browser window titles for web meetings
+    if settings.scan_browser_urls {
+        if let Some(app) = check_browser_window_titles(titles, settings) {
+            info!("Meeting detected via browser window: {:?}", app);
+            return Some((app, DetectionMethod::WindowTitle));
         }
     }
-    
-    false
+
+    None
+}
+
+// Process names (as listed by `ps -e -o comm=`) the Linux checks look for
+#[cfg(target_os = "linux")]
+const LINUX_MEETING_PROCESSES: &[&str] = &[
+    "zoom", "zoomwebviewhost", "slack", "teams", "teams-for-linux", "webex", "ciscowebexstart", "discord", "discord-canary",
+];
+
+#[cfg(target_os = "linux")]
+fn collect_detection_signals(settings: &DetectionSettings) -> DetectionProbe {
+    let processes = list_process_names().unwrap_or_default();
+    let mut meeting_processes: Vec<String> = processes.lines()
+        .map(str::trim)
+        .filter(|name| LINUX_MEETING_PROCESSES.contains(name))
+        .map(str::to_string)
+        .collect();
+    meeting_processes.sort();
+    meeting_processes.dedup();
+
+    // wmctrl -l lines look like "0x03a00003  0 hostname Window Title"
+    let titles = if settings.scan_browser_urls { list_window_titles().unwrap_or_default() } else { String::new() };
+    let window_titles = titles
+        .lines()
+        .map(|line| line.split_whitespace().skip(3).collect::<Vec<_>>().join(" "))
+        .filter(|title| !title.is_empty())
+        .map(|title| truncate_chars(&title, PROBE_MAX_SIGNAL_CHARS))
+        .collect();
+
+    DetectionProbe { meeting_processes, window_titles, ..DetectionProbe::default() }
+}
+
+#[cfg(target_os = "linux")]
+fn lookup_meeting_title(app: &MeetingApp, _settings: &DetectionSettings) -> Option<String> {
+    // Same window-title indicators as detection, but matched against the original casing
+    let needles = match app {
+        MeetingApp::GoogleMeet => vec!["meet - ".to_string()],
+        MeetingApp::Zoom => vec!["zoom meeting".to_string()],
+        MeetingApp::MicrosoftTeams => vec!["microsoft teams".to_string()],
+        MeetingApp::SlackHuddle => vec!["huddle".to_string()],
+        MeetingApp::Discord => vec!["voice connected".to_string()],
+        MeetingApp::Webex => vec!["webex".to_string()],
+        MeetingApp::Unknown(pattern) => vec![pattern.to_lowercase()],
+    };
+
+    let output = Command::new("wmctrl")
+        .arg("-l")
+        .output()
+        .ok()?;
+
+    // wmctrl -l lines look like "0x03a00003  0 hostname Window Title"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.split_whitespace().skip(3).collect::<Vec<_>>().join(" "))
+        .find(|title| {
+            let lowered = title.to_lowercase();
+            needles.iter().any(|needle| lowered.contains(needle.as_str()))
+        })
+        .and_then(|title| clean_meeting_title(&title))
+}
+
+#[cfg(target_os = "linux")]
+fn list_process_names() -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-e", "-o", "comm="])
+        .output()
+        .ok()?;
+
+    Some(String::from_utf8_lossy(&output.stdout).to_lowercase())
+}
+
+#[cfg(target_os = "linux")]
+fn list_window_titles() -> Option<String> {
+    // wmctrl is optional; without it only process-based detection is possible
+    let output = Command::new("wmctrl")
+        .arg("-l")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).to_lowercase())
 }
 
-// Fallback for non-macOS platforms
-#[cfg(not(target_os = "macos"))]
-fn detect_meeting_apps() -> Option<MeetingApp> {
+#[cfg(any(target_os = "linux", test))]
+fn check_running_processes_linux(processes: &str, titles: &str, settings: &DetectionSettings) -> Option<MeetingApp> {
+    let is_running = |names: &[&str]| processes.lines().any(|p| names.contains(&p.trim()));
+    let enabled = |app: MeetingApp| settings.is_enabled(&app);
+
+    // Zoom opens a dedicated "Zoom Meeting" window while in a call
+    if enabled(MeetingApp::Zoom) && is_running(&["zoom", "zoomwebviewhost"]) && any_title_contains(titles, &["zoom meeting"]) {
+        return Some(MeetingApp::Zoom);
+    }
+
+    if enabled(MeetingApp::SlackHuddle) && is_running(&["slack"]) && any_title_contains(titles, &["huddle"]) {
+        return Some(MeetingApp::SlackHuddle);
+    }
+
+    if enabled(MeetingApp::MicrosoftTeams) && is_running(&["teams", "teams-for-linux"]) && any_title_contains(titles, &["meeting", "call"]) {
+        return Some(MeetingApp::MicrosoftTeams);
+    }
+
+    if enabled(MeetingApp::Webex) && is_running(&["webex", "ciscowebexstart"]) && any_title_contains(titles, &["meeting", "personal room"]) {
+        return Some(MeetingApp::Webex);
+    }
+
+    if enabled(MeetingApp::Discord) && is_running(&["discord", "discord-canary"]) && any_title_contains(titles, &["voice connected", "screen share"]) {
+        return Some(MeetingApp::Discord);
+    }
+
     None
-}
\ No newline at end of file
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_browser_window_titles(titles: &str, settings: &DetectionSettings) -> Option<MeetingApp> {
+    let enabled = |app: MeetingApp| settings.is_enabled(&app);
+    // Browser window titles reflect the active tab, e.g. "Meet - abc-defg-hij - Google Chrome"
+    if enabled(MeetingApp::GoogleMeet) && any_title_contains(titles, &["meet - "]) {
+        println!("Found Google Meet window");
+        return Some(MeetingApp::GoogleMeet);
+    }
+    if enabled(MeetingApp::Zoom) && any_title_contains(titles, &["zoom meeting"]) {
+        println!("Found Zoom web meeting window");
+        return Some(MeetingApp::Zoom);
+    }
+    if enabled(MeetingApp::MicrosoftTeams) && titles.lines().any(|line| line.contains("microsoft teams") && line.contains("meeting")) {
+        println!("Found Teams meeting window");
+        return Some(MeetingApp::MicrosoftTeams);
+    }
+    if enabled(MeetingApp::Webex) && titles.lines().any(|line| line.contains("webex") && line.contains("meeting")) {
+        println!("Found Webex meeting window");
+        return Some(MeetingApp::Webex);
+    }
+    if let Some(pattern) = match_custom_pattern(titles, &settings.custom_meeting_patterns) {
+        println!("Found custom meeting pattern '{}' in window title", pattern);
+        return Some(MeetingApp::Unknown(pattern.to_string()));
+    }
+
+    None
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn any_title_contains(titles: &str, needles: &[&str]) -> bool {
+    titles.lines().any(|line| needles.iter().any(|needle| line.contains(needle)))
+}
+
+// Fallback for other platforms
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn detect_meeting_apps(_settings: &DetectionSettings) -> Option<(MeetingApp, DetectionMethod)> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn collect_detection_signals(_settings: &DetectionSettings) -> DetectionProbe {
+    DetectionProbe::default()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn lookup_meeting_title(_app: &MeetingApp, _settings: &DetectionSettings) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_threads_end_when_stopped() {
+        // Each watcher's closure holds a clone; it's dropped once that thread has exited
+        let alive = Arc::new(());
+        let ticks = Arc::new(Mutex::new(0));
+        for _ in 0..10 {
+            let alive = alive.clone();
+            let ticks = ticks.clone();
+            let watcher = MeetingWatcher::spawn(Duration::from_millis(1), move || {
+                let _ = &alive;
+                *ticks.lock().unwrap() += 1;
+            });
+            thread::sleep(Duration::from_millis(5));
+            watcher.stop();
+        }
+        
+        assert_eq!(Arc::strong_count(&alive), 1, "stopped watchers must not keep running");
+        assert!(*ticks.lock().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_custom_pattern_matches_case_insensitively() {
+        let patterns = vec!["meet.mycorp.com".to_string()];
+        let urls = "https://mail.google.com/ https://MEET.MyCorp.com/standup ";
+        assert_eq!(match_custom_pattern(urls, &patterns), Some("meet.mycorp.com"));
+    }
+
+    #[test]
+    fn test_clean_meeting_title_strips_browser_and_meet_decoration() {
+        assert_eq!(
+            clean_meeting_title("Meet - Weekly sync - Google Chrome"),
+            Some("Weekly sync".to_string())
+        );
+        assert_eq!(clean_meeting_title("Zoom Meeting"), Some("Zoom Meeting".to_string()));
+        assert_eq!(clean_meeting_title("   "), None);
+    }
+
+    #[test]
+    fn test_end_grace_polls_rounds_up_and_never_hits_zero() {
+        let settings = |secs| DetectionSettings { meeting_end_grace_secs: secs, ..Default::default() };
+        assert_eq!(settings(0).end_grace_polls(), 1);
+        assert_eq!(settings(MEETING_POLL_INTERVAL_SECS).end_grace_polls(), 1);
+        assert_eq!(settings(MEETING_POLL_INTERVAL_SECS + 1).end_grace_polls(), 2);
+    }
+
+    #[test]
+    fn test_is_enabled_skips_disabled_apps_but_not_unknown() {
+        let mut settings = DetectionSettings::default();
+        assert!(settings.is_enabled(&MeetingApp::Discord));
+        
+        settings.detect_apps.remove(&MeetingApp::Discord);
+        assert!(!settings.is_enabled(&MeetingApp::Discord));
+        assert!(settings.is_enabled(&MeetingApp::Zoom));
+        assert!(settings.is_enabled(&MeetingApp::Unknown("meet.mycorp.com".to_string())));
+    }
+
+    #[test]
+    fn test_process_names_match_whole_executables() {
+        let ps_output = "/Applications/zoom.us.app/Contents/MacOS/zoom.us\n\
+            /Applications/ZoomIt.app/Contents/MacOS/ZoomIt\n\
+            /Applications/Microsoft Teams.app/Contents/MacOS/Microsoft Teams\n\
+            /usr/local/bin/slackdump\n";
+        let names = process_names(ps_output);
+        assert!(names.contains(&"zoom.us"));
+        assert!(names.contains(&"Microsoft Teams"));
+        assert!(!names.contains(&"Slack"), "substrings of other executables must not match");
+        assert!(!names.contains(&"Zoom"));
+    }
+
+    #[test]
+    fn test_default_input_device_app_uses_structured_fields() {
+        let audio_info = serde_json::json!({
+            "SPAudioDataType": [{
+                "_name": "coreaudio_device",
+                "_items": [
+                    {
+                        "_name": "MacBook Pro Microphone",
+                        "coreaudio_device_manufacturer": "Apple Inc.",
+                        "coreaudio_input_source": "zoom-lens-mic"
+                    },
+                    {
+                        "_name": "ZoomAudioDevice",
+                        "coreaudio_default_audio_input_device": "spaudio_yes",
+                        "coreaudio_device_manufacturer": "zoom.us"
+                    }
+                ]
+            }]
+        });
+        assert_eq!(default_input_device_app(&audio_info), Some(MeetingApp::Zoom));
+    }
+
+    #[test]
+    fn test_default_input_device_app_ignores_non_default_devices() {
+        // "zoom" appearing in an unrelated, non-default device must not count as a meeting
+        let audio_info = serde_json::json!({
+            "SPAudioDataType": [{
+                "_items": [
+                    { "_name": "ZoomAudioDevice" },
+                    {
+                        "_name": "MacBook Pro Microphone",
+                        "coreaudio_default_audio_input_device": "spaudio_yes"
+                    }
+                ]
+            }]
+        });
+        assert_eq!(default_input_device_app(&audio_info), None);
+        assert_eq!(default_input_device_app(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn test_default_input_device_app_ignores_default_output_device() {
+        // A meeting app's virtual speaker being the default output says nothing about the mic
+        let audio_info = serde_json::json!({
+            "SPAudioDataType": [{
+                "_items": [
+                    {
+                        "_name": "ZoomAudioDevice",
+                        "coreaudio_default_audio_output_device": "spaudio_yes",
+                        "coreaudio_default_audio_system_device": "spaudio_yes"
+                    },
+                    {
+                        "_name": "MacBook Pro Microphone",
+                        "coreaudio_default_audio_input_device": "spaudio_yes"
+                    }
+                ]
+            }]
+        });
+        assert_eq!(default_input_device_app(&audio_info), None);
+    }
+
+    #[test]
+    fn test_microphone_detection_is_least_confident() {
+        let methods = [DetectionMethod::Process, DetectionMethod::BrowserUrl, DetectionMethod::WindowTitle];
+        assert!(methods.iter().all(|method| method.confidence() > DetectionMethod::Microphone.confidence()));
+        assert_eq!(serde_json::to_string(&DetectionMethod::BrowserUrl).unwrap(), "\"browser_url\"");
+    }
+
+    #[test]
+    fn test_redact_url_drops_scheme_query_and_fragment() {
+        assert_eq!(redact_url("https://meet.google.com/abc-defg-hij?authuser=1#x"), "meet.google.com/abc-defg-hij");
+        let long = format!("https://example.com/{}", "a".repeat(200));
+        assert_eq!(redact_url(&long).chars().count(), PROBE_MAX_SIGNAL_CHARS + 1);
+    }
+
+    #[test]
+    fn test_google_meet_room_requires_room_code_shape() {
+        assert!(is_google_meet_room("https://meet.google.com/abc-defg-hij "));
+        assert!(is_google_meet_room("https://github.com https://meet.google.com/abc-defg-hij?authuser=0 "));
+        assert!(is_google_meet_room("https://meet.google.com/lookup/abc123def456 "));
+        
+        assert!(!is_google_meet_room("https://meet.google.com/a-b "));
+        assert!(!is_google_meet_room("https://meet.google.com/abc-def-ghi "));
+        assert!(!is_google_meet_room("https://meet.google.com/new-meeting-room "));
+        assert!(!is_google_meet_room("https://meet.google.com/landing?pli=1&x=a-b-c "));
+        assert!(!is_google_meet_room("https://meet.google.com/#abc-defg-hij "));
+    }
+
+    #[test]
+    fn test_custom_pattern_ignores_blank_patterns() {
+        let patterns = vec!["".to_string(), "   ".to_string()];
+        assert_eq!(match_custom_pattern("https://example.com/", &patterns), None);
+    }
+
+    #[test]
+    fn test_meeting_urls_resolve_to_their_app() {
+        let settings = DetectionSettings {
+            custom_meeting_patterns: vec!["meet.mycorp.com".to_string()],
+            ..Default::default()
+        };
+        let cases = [
+            ("https://meet.google.com/abc-defg-hij?authuser=0&hl=en ", MeetingApp::GoogleMeet),
+            ("https://zoom.us/j/1234567890?pwd=abc123&from=addon ", MeetingApp::Zoom),
+            ("https://us02web.zoom.us/wc/1234567890/join ", MeetingApp::Zoom),
+            ("https://teams.microsoft.com/l/meetup-join/12345?context=something ", MeetingApp::MicrosoftTeams),
+            ("https://teams.live.com/meet/9876543210 ", MeetingApp::MicrosoftTeams),
+            ("https://app.slack.com/huddle/T12345/C67890 ", MeetingApp::SlackHuddle),
+            ("https://mycompany.webex.com/meet/jdoe ", MeetingApp::Webex),
+            ("https://mycompany.webex.com/wbxmjs/joinservice/sites/mycompany/meeting/download/abc123 ", MeetingApp::Webex),
+            ("https://meet.mycorp.com/standup ", MeetingApp::Unknown("meet.mycorp.com".to_string())),
+        ];
+        for (urls, app) in cases {
+            assert_eq!(match_meeting_urls(urls, &settings), Some(app), "{}", urls);
+        }
+        
+        let not_meetings = [
+            "https://meet.google.com/ https://meet.google.com/landing https://meet.google.com/abc ",
+            "https://zoom.us/ https://zoom.us/signin ",
+            "https://www.webex.com/ https://mycompany.webex.com/webappng/sites/mycompany/dashboard ",
+            "https://github.com https://news.ycombinator.com ",
+            "not-a-url-at-all",
+            "",
+        ];
+        for urls in not_meetings {
+            assert_eq!(match_meeting_urls(urls, &settings), None, "{}", urls);
+        }
+    }
+
+    #[test]
+    fn test_meeting_urls_prefer_meet_and_skip_disabled_apps() {
+        let urls = "https://github.com https://zoom.us/j/1234567890 https://meet.google.com/abc-defg-hij ";
+        let mut settings = DetectionSettings::default();
+        assert_eq!(match_meeting_urls(urls, &settings), Some(MeetingApp::GoogleMeet));
+        
+        settings.detect_apps.remove(&MeetingApp::GoogleMeet);
+        assert_eq!(match_meeting_urls(urls, &settings), Some(MeetingApp::Zoom));
+        
+        settings.detect_apps.clear();
+        assert_eq!(match_meeting_urls(urls, &settings), None);
+    }
+
+    #[test]
+    fn test_meeting_ends_only_after_grace_polls() {
+        let mut state = MeetingState {
+            is_in_meeting: false,
+            detected_app: None,
+            started_at: None,
+            meeting_title: None,
+            detection_method: None,
+            confidence: 0.0,
+        };
+        let mut missed_polls = 0;
+        let zoom = || Some((MeetingApp::Zoom, DetectionMethod::Process));
+        
+        apply_detection(&mut state, zoom(), Some("Standup".to_string()), &mut missed_polls, 3);
+        assert!(state.is_in_meeting);
+        assert_eq!(state.detected_app, Some(MeetingApp::Zoom));
+        assert_eq!(state.meeting_title.as_deref(), Some("Standup"));
+        assert_eq!(state.confidence, DetectionMethod::Process.confidence());
+        let started_at = state.started_at;
+        
+        // Two misses are tolerated, and a detection in between resets the count
+        apply_detection(&mut state, None, None, &mut missed_polls, 3);
+        apply_detection(&mut state, None, None, &mut missed_polls, 3);
+        assert!(state.is_in_meeting);
+        apply_detection(&mut state, Some((MeetingApp::Zoom, DetectionMethod::Microphone)), None, &mut missed_polls, 3);
+        assert_eq!(missed_polls, 0);
+        assert_eq!(state.started_at, started_at, "an ongoing meeting keeps its start time");
+        assert_eq!(state.meeting_title.as_deref(), Some("Standup"));
+        assert_eq!(state.detection_method, Some(DetectionMethod::Microphone));
+        
+        for _ in 0..2 {
+            apply_detection(&mut state, None, None, &mut missed_polls, 3);
+        }
+        assert!(state.is_in_meeting);
+        apply_detection(&mut state, None, None, &mut missed_polls, 3);
+        assert!(!state.is_in_meeting);
+        assert_eq!(state.detected_app, None);
+        assert_eq!(state.meeting_title, None);
+        assert_eq!(state.confidence, 0.0);
+        assert_eq!(missed_polls, 0);
+    }
+
+    #[test]
+    fn test_macos_checks_run_in_order_of_confidence() {
+        let found = |app: MeetingApp| move |_: &DetectionSettings| Some(app.clone());
+        let none = |_: &DetectionSettings| None;
+        let no_dia = || None;
+        let checks = |processes, browser_urls, microphone| MacosChecks { processes, browser_urls, microphone, dia_microphone: &no_dia };
+        let settings = DetectionSettings::default();
+        let (zoom, meet, teams) = (found(MeetingApp::Zoom), found(MeetingApp::GoogleMeet), found(MeetingApp::MicrosoftTeams));
+        
+        assert_eq!(run_macos_checks(&settings, &checks(&zoom, &meet, &teams)), Some((MeetingApp::Zoom, DetectionMethod::Process)));
+        assert_eq!(run_macos_checks(&settings, &checks(&none, &meet, &teams)), Some((MeetingApp::GoogleMeet, DetectionMethod::BrowserUrl)));
+        assert_eq!(run_macos_checks(&settings, &checks(&none, &none, &teams)), Some((MeetingApp::MicrosoftTeams, DetectionMethod::Microphone)));
+        
+        let no_browser = DetectionSettings { scan_browser_urls: false, ..Default::default() };
+        assert_eq!(run_macos_checks(&no_browser, &checks(&none, &meet, &teams)), Some((MeetingApp::MicrosoftTeams, DetectionMethod::Microphone)));
+    }
+
+    #[test]
+    fn test_detection_reports_method_and_confidence_of_the_winning_check() {
+        let none = |_: &DetectionSettings| None;
+        let meet = |_: &DetectionSettings| Some(MeetingApp::GoogleMeet);
+        let teams = |_: &DetectionSettings| Some(MeetingApp::MicrosoftTeams);
+        let no_dia = || None;
+        let settings = DetectionSettings::default();
+        let mut state = MeetingState {
+            is_in_meeting: false,
+            detected_app: None,
+            started_at: None,
+            meeting_title: None,
+            detection_method: None,
+            confidence: 0.0,
+        };
+        let mut missed_polls = 0;
+        
+        // Browser URL wins over the microphone and is reported with its confidence
+        let detected = run_macos_checks(&settings, &MacosChecks { processes: &none, browser_urls: &meet, microphone: &teams, dia_microphone: &no_dia });
+        apply_detection(&mut state, detected, None, &mut missed_polls, 3);
+        assert_eq!(state.detection_method, Some(DetectionMethod::BrowserUrl));
+        assert_eq!(state.confidence, DetectionMethod::BrowserUrl.confidence());
+        
+        // Only the microphone still sees the meeting: the method and confidence follow it
+        let detected = run_macos_checks(&settings, &MacosChecks { processes: &none, browser_urls: &none, microphone: &teams, dia_microphone: &no_dia });
+        apply_detection(&mut state, detected, None, &mut missed_polls, 3);
+        assert_eq!(state.detected_app, Some(MeetingApp::GoogleMeet), "the ongoing meeting keeps its app");
+        assert_eq!(state.detection_method, Some(DetectionMethod::Microphone));
+        assert_eq!(state.confidence, DetectionMethod::Microphone.confidence());
+    }
+
+    #[test]
+    fn test_dia_check_is_a_browser_check() {
+        let none = |_: &DetectionSettings| None;
+        let dia = || Some(MeetingApp::Unknown("Meeting detected in Dia browser".to_string()));
+        let checks = MacosChecks { processes: &none, browser_urls: &none, microphone: &none, dia_microphone: &dia };
+        
+        let detected = run_macos_checks(&DetectionSettings::default(), &checks);
+        assert_eq!(detected, Some((MeetingApp::Unknown("Meeting detected in Dia browser".to_string()), DetectionMethod::Microphone)));
+        
+        let no_browser = DetectionSettings { scan_browser_urls: false, ..Default::default() };
+        assert_eq!(run_macos_checks(&no_browser, &checks), None);
+    }
+
+    #[test]
+    fn test_linux_detection_needs_a_call_window() {
+        let settings = DetectionSettings::default();
+        let detect = |processes: &str, titles: &str| detect_from_processes_and_titles(processes, titles, &settings);
+        let title = |text: &str| format!("0x03a00003  0 host {}\n", text);
+        
+        assert_eq!(detect("zoom\nbash\n", ""), None, "Zoom running without a meeting window");
+        assert_eq!(detect("zoom\nbash\n", &title("zoom meeting")), Some((MeetingApp::Zoom, DetectionMethod::Process)));
+        assert_eq!(detect("slack\n", &title("huddle with jane - slack")), Some((MeetingApp::SlackHuddle, DetectionMethod::Process)));
+        assert_eq!(
+            detect("firefox\n", &title("meet - abc-defg-hij - mozilla firefox")),
+            Some((MeetingApp::GoogleMeet, DetectionMethod::WindowTitle))
+        );
+        
+        let no_browser = DetectionSettings { scan_browser_urls: false, ..Default::default() };
+        assert_eq!(detect_from_processes_and_titles("firefox\n", &title("meet - abc-defg-hij - mozilla firefox"), &no_browser), None);
+    }
+}
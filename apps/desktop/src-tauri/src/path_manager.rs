@@ -1,7 +1,22 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
+use tracing::warn;
 use crate::error::{AppError, Result};
 
+/// How new recordings are arranged inside the recordings directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingLayout {
+    /// All recordings directly in the recordings directory
+    #[default]
+    Flat,
+    /// One folder per day, e.g. `2024-08-26/`
+    PerDay,
+    /// One folder per detected meeting, e.g. `2024-08-26 Zoom - Standup/`; other recordings stay flat
+    PerMeeting,
+}
+
 /// Centralized path management for the desktop application.
 /// Provides a single source of truth for all file and directory paths.
 #[derive(Debug, Clone)]
@@ -12,11 +27,25 @@ pub struct AppPaths {
 
 impl AppPaths {
     /// Create a new AppPaths instance, initializing directories as needed.
+    ///
+    /// Uses the user-configured recordings directory when set and available,
+    /// otherwise `<app data>/recordings`.
     pub fn new(app_handle: &AppHandle) -> Result<Self> {
         let app_data_dir = app_handle.path().app_data_dir()
             .map_err(|e| AppError::Path(format!("Failed to get app data directory: {}", e)))?;
         
-        let recordings_dir = app_data_dir.join("recordings");
+        let default_dir = app_data_dir.join("recordings");
+        let configured_dir = app_handle.try_state::<crate::AppState>()
+            .and_then(|state| state.recordings_dir.lock().unwrap().clone());
+        
+        let recordings_dir = match configured_dir {
+            Some(dir) if std::fs::create_dir_all(&dir).is_ok() => dir,
+            Some(dir) => {
+                warn!("Configured recordings directory {} is unavailable, using default", dir.display());
+                default_dir
+            }
+            None => default_dir,
+        };
         
         // Ensure recordings directory exists
         std::fs::create_dir_all(&recordings_dir)
@@ -40,10 +69,52 @@ impl AppPaths {
         self.recordings_dir.join(filename)
     }
     
+    /// Resolve a recording filename relative to `dir`, which may include a subfolder
+    /// (`2024-08-26/recording.opus`). Rejects names that would escape the directory.
+    pub fn resolve_recording_path(dir: &Path, filename: &str) -> Result<PathBuf> {
+        let relative = Path::new(filename);
+        if filename.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(AppError::Path(format!("Invalid recording filename: {}", filename)));
+        }
+        Ok(dir.join(relative))
+    }
+    
+    /// Filename stored in metadata for a file inside `dir`: its path relative to `dir`,
+    /// with `/` separators. Falls back to the bare file name for files outside `dir`.
+    pub fn relative_recording_name(dir: &Path, path: &Path) -> Option<String> {
+        match path.strip_prefix(dir) {
+            Ok(relative) => {
+                let parts: Option<Vec<&str>> = relative.components().map(|c| c.as_os_str().to_str()).collect();
+                parts.map(|parts| parts.join("/"))
+            }
+            Err(_) => path.file_name()?.to_str().map(str::to_string),
+        }
+    }
+    
     /// Get the metadata file path.
     pub fn metadata_file(&self) -> &PathBuf {
         &self.metadata_file
     }
+    
+    /// Get the application log file path (the file may not exist yet).
+    pub fn log_file(app_handle: &AppHandle) -> Result<PathBuf> {
+        let log_dir = app_handle.path().app_log_dir()
+            .map_err(|e| AppError::Path(format!("Failed to get app log directory: {}", e)))?;
+        Ok(log_dir.join(crate::constants::LOG_FILE_NAME))
+    }
+    
+    /// Check that a directory can hold recordings, creating it if missing.
+    pub fn validate_recordings_dir(dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| AppError::Path(format!("Failed to create recordings directory {}: {}", dir.display(), e)))?;
+        
+        let probe_file = dir.join(".write_test");
+        std::fs::write(&probe_file, b"")
+            .map_err(|e| AppError::Path(format!("Recordings directory {} is not writable: {}", dir.display(), e)))?;
+        let _ = std::fs::remove_file(&probe_file);
+        
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -84,6 +155,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_recording_path_allows_subfolders_only() {
+        let dir = Path::new("/recordings");
+        assert_eq!(
+            AppPaths::resolve_recording_path(dir, "2024-08-26/recording.opus").unwrap(),
+            dir.join("2024-08-26").join("recording.opus")
+        );
+        assert!(AppPaths::resolve_recording_path(dir, "../secrets.txt").is_err());
+        assert!(AppPaths::resolve_recording_path(dir, "/etc/passwd").is_err());
+        assert!(AppPaths::resolve_recording_path(dir, "").is_err());
+        
+        let nested = dir.join("2024-08-26").join("recording.opus");
+        assert_eq!(AppPaths::relative_recording_name(dir, &nested).as_deref(), Some("2024-08-26/recording.opus"));
+        assert_eq!(AppPaths::relative_recording_name(Path::new("/elsewhere"), &nested).as_deref(), Some("recording.opus"));
+    }
+
     #[test]
     fn test_app_paths_creation() {
         let mock_handle = MockAppHandle::new();
@@ -214,6 +301,26 @@ mod tests {
         assert!(test_file.exists(), "Test file should be created");
     }
 
+    #[test]
+    fn test_validate_recordings_dir_creates_missing_directory() {
+        let mock_handle = MockAppHandle::new();
+        let custom_dir = mock_handle.temp_dir.path().join("external").join("recordings");
+        
+        assert!(AppPaths::validate_recordings_dir(&custom_dir).is_ok(), "Should accept a creatable directory");
+        assert!(custom_dir.is_dir(), "Custom recordings directory should be created");
+        assert!(!custom_dir.join(".write_test").exists(), "Probe file should be cleaned up");
+    }
+
+    #[test]
+    fn test_validate_recordings_dir_rejects_file_path() {
+        let mock_handle = MockAppHandle::new();
+        let file_path = mock_handle.app_data_path.join("not_a_directory");
+        std::fs::write(&file_path, "content").unwrap();
+        
+        let result = AppPaths::validate_recordings_dir(&file_path);
+        assert!(matches!(result, Err(AppError::Path(_))), "Should reject a path that is a file");
+    }
+
     /// Helper function to create AppPaths from our mock
     /// This simulates what the real implementation does with a Tauri AppHandle
     fn create_app_paths_from_mock(mock_handle: &MockAppHandle) -> Result<AppPaths> {
@@ -0,0 +1,158 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use super::audio_converter::AudioFormat;
+
+/// Zip archives of the recordings directory, for backups and moving a library between machines
+pub struct Archive;
+
+impl Archive {
+    /// Zip every file under `dir` accepted by `include` (given the file name) into `destination`,
+    /// keeping subfolders. Files are streamed into the archive one at a time; returns how many were added.
+    pub fn write(dir: &Path, destination: &Path, include: impl Fn(&str) -> bool) -> Result<usize, String> {
+        let mut files = Vec::new();
+        Self::collect_files(dir, &mut files)
+            .map_err(|e| format!("Failed to list {}: {}", dir.display(), e))?;
+
+        let output = File::create(destination)
+            .map_err(|e| format!("Failed to create archive: {}", e))?;
+        let mut writer = ZipWriter::new(BufWriter::new(output));
+        let mut count = 0;
+        for path in files {
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+            // Don't archive the archive when it's written inside the directory
+            if path == destination || !include(file_name) {
+                continue;
+            }
+            let Some(entry_name) = Self::entry_name(dir, &path) else { continue };
+
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            // Opus and MP3 are already compressed; deflating them only costs time
+            let method = if AudioFormat::from_path(&path).is_some() { CompressionMethod::Stored } else { CompressionMethod::Deflated };
+            let options = SimpleFileOptions::default()
+                .compression_method(method)
+                .large_file(size >= u64::from(u32::MAX));
+            writer.start_file(entry_name.as_str(), options)
+                .map_err(|e| format!("Failed to add {} to archive: {}", entry_name, e))?;
+            let mut file = File::open(&path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            std::io::copy(&mut file, &mut writer)
+                .map_err(|e| format!("Failed to add {} to archive: {}", entry_name, e))?;
+            count += 1;
+        }
+
+        writer.finish().map_err(|e| format!("Failed to finish archive: {}", e))?;
+        Ok(count)
+    }
+
+    /// Read one file of the archive as text, or None if the archive doesn't contain it
+    pub fn read_entry(source: &Path, name: &str) -> Result<Option<String>, String> {
+        let mut archive = Self::open(source)?;
+        let mut entry = match archive.by_name(name) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(format!("Failed to read {} from archive: {}", name, e)),
+        };
+        let mut content = String::new();
+        entry.read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read {} from archive: {}", name, e))?;
+        Ok(Some(content))
+    }
+
+    /// Extract the archive into `dir`, streaming each file. Only entries accepted by `include`
+    /// (given the path relative to `dir`) are extracted; entries that already exist and entries
+    /// that would land outside `dir` are left out. Returns the relative paths of the extracted files.
+    pub fn extract(source: &Path, dir: &Path, include: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>, String> {
+        let mut archive = Self::open(source)?;
+        let mut extracted = Vec::new();
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+            let Some(relative_path) = entry.enclosed_name() else { continue };
+            if entry.is_dir() || !include(&relative_path) {
+                continue;
+            }
+            let target = dir.join(&relative_path);
+            if target.exists() {
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let mut output = File::create(&target)
+                .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+            if let Err(e) = std::io::copy(&mut entry, &mut output) {
+                drop(output);
+                let _ = fs::remove_file(&target);
+                return Err(format!("Failed to extract {}: {}", relative_path.display(), e));
+            }
+            extracted.push(relative_path);
+        }
+        Ok(extracted)
+    }
+
+    fn open(source: &Path) -> Result<ZipArchive<BufReader<File>>, String> {
+        let file = File::open(source)
+            .map_err(|e| format!("Failed to open archive: {}", e))?;
+        ZipArchive::new(BufReader::new(file))
+            .map_err(|e| format!("Not a valid archive: {}", e))
+    }
+
+    /// All files under `dir`, recursively, in a stable order
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        let mut entries = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                Self::collect_files(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Name of `path` inside the archive: relative to `dir`, with `/` separators
+    fn entry_name(dir: &Path, path: &Path) -> Option<String> {
+        let parts = path.strip_prefix(dir).ok()?
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()?;
+        Some(parts.join("/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_round_trip_skips_existing_files() {
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir(source.path().join("2024-08-26")).unwrap();
+        fs::write(source.path().join("recordings.json"), b"[]").unwrap();
+        fs::write(source.path().join("a.opus"), b"opus audio").unwrap();
+        fs::write(source.path().join("2024-08-26").join("b.wav"), b"wav audio").unwrap();
+        fs::write(source.path().join("notes.txt"), b"not a recording").unwrap();
+        let archive_path = source.path().join("backup.zip");
+
+        let count = Archive::write(source.path(), &archive_path, |name| name != "notes.txt").unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(Archive::read_entry(&archive_path, "recordings.json").unwrap().as_deref(), Some("[]"));
+        assert_eq!(Archive::read_entry(&archive_path, "missing.json").unwrap(), None);
+
+        let target = tempfile::tempdir().unwrap();
+        fs::write(target.path().join("a.opus"), b"existing").unwrap();
+        let extracted = Archive::extract(&archive_path, target.path(), |path| path != Path::new("recordings.json")).unwrap();
+        assert_eq!(extracted, vec![Path::new("2024-08-26").join("b.wav")]);
+        assert_eq!(fs::read(target.path().join("2024-08-26").join("b.wav")).unwrap(), b"wav audio");
+        assert_eq!(fs::read(target.path().join("a.opus")).unwrap(), b"existing");
+        assert!(!target.path().join("recordings.json").exists());
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::process::Command;
@@ -5,6 +6,162 @@ use tokio::task;
 use tauri::Manager;
 use crate::constants::*;
 
+/// Compressed format recordings are converted to after capture
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    #[default]
+    Opus,
+    Mp3,
+}
+
+impl AudioFormat {
+    /// File extension for this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Opus => "opus",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+    
+    /// MIME type used when sending files of this format over HTTP
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            AudioFormat::Opus => "audio/opus",
+            AudioFormat::Mp3 => "audio/mpeg",
+        }
+    }
+    
+    /// Format of a file based on its extension, if it is one we convert to
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("opus") => Some(AudioFormat::Opus),
+            Some("mp3") => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+    
+    /// FFmpeg encoder arguments for this format
+    fn codec_args(self, settings: &ConversionSettings) -> Vec<String> {
+        let codec = match self {
+            AudioFormat::Opus => "libopus",
+            AudioFormat::Mp3 => "libmp3lame",
+        };
+        let mut args = Vec::new();
+        if settings.normalize_loudness {
+            args.extend(["-af".to_string(), loudnorm_filter()]);
+        }
+        args.extend([
+            "-c:a".to_string(), codec.to_string(),
+            "-b:a".to_string(), format!("{}k", settings.bitrate_kbps),
+            "-ar".to_string(), settings.sample_rate.to_string(),
+            "-ac".to_string(), settings.channels.to_string(),
+        ]);
+        args
+    }
+}
+
+/// FFmpeg `loudnorm` filter targeting `LOUDNORM_TARGET_LUFS`, printing its measurements to stderr
+fn loudnorm_filter() -> String {
+    format!("loudnorm=I={}:TP={}:LRA=11:print_format=summary", LOUDNORM_TARGET_LUFS, LOUDNORM_TRUE_PEAK_DB)
+}
+
+/// Measured input loudness (LUFS) from the `loudnorm` summary FFmpeg writes to stderr
+fn parse_input_loudness(stderr: &str) -> Option<f32> {
+    stderr.lines()
+        .find_map(|line| line.trim().strip_prefix("Input Integrated:"))
+        .and_then(|value| value.trim().trim_end_matches("LUFS").trim().parse().ok())
+}
+
+/// Codec, sample rate and channel layout of a file's first audio stream, as FFmpeg reports them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioStreamInfo {
+    pub codec: String,
+    pub sample_rate: u32,
+    /// Channel layout such as "mono" or "stereo"
+    pub channels: String,
+}
+
+impl std::fmt::Display for AudioStreamInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} Hz {}", self.codec, self.sample_rate, self.channels)
+    }
+}
+
+/// First audio stream in FFmpeg's input listing, e.g. "Stream #0:0: Audio: opus, 48000 Hz, mono, fltp"
+fn parse_audio_stream(stderr: &str) -> Option<AudioStreamInfo> {
+    let description = stderr.lines()
+        .filter(|line| line.trim_start().starts_with("Stream #"))
+        .find_map(|line| line.split_once("Audio: "))?
+        .1;
+    let mut fields = description.split(", ");
+    let codec = fields.next()?.split_whitespace().next()?.to_string();
+    let sample_rate = fields.next()?.strip_suffix(" Hz")?.trim().parse().ok()?;
+    let channels = fields.next()?.trim().to_string();
+    Some(AudioStreamInfo { codec, sample_rate, channels })
+}
+
+/// Length in milliseconds from the "Duration: HH:MM:SS.ss" line FFmpeg prints for an input
+fn parse_duration_ms(stderr: &str) -> Option<u64> {
+    let (_, rest) = stderr.split_once("Duration: ")?;
+    let timestamp = rest.split(',').next()?.trim();
+    let mut parts = timestamp.splitn(3, ':');
+    let hours: u64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some((hours * 3600 + minutes * 60) * 1000 + (seconds * 1000.0).round() as u64)
+}
+
+/// A piece of a recording produced by `AudioConverter::split_into_chunks`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioChunk {
+    pub path: PathBuf,
+    /// Where the chunk starts in the original recording
+    pub start_secs: f64,
+}
+
+/// Encoder parameters for WAV conversion; the defaults favour speech (mono, 16kHz, 64kbps)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConversionSettings {
+    pub bitrate_kbps: u32,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Normalize loudness to `LOUDNORM_TARGET_LUFS` while converting
+    pub normalize_loudness: bool,
+    /// Keep the source WAV instead of deleting it after a successful conversion
+    pub keep_source: bool,
+}
+
+impl Default for ConversionSettings {
+    fn default() -> Self {
+        Self {
+            bitrate_kbps: DEFAULT_CONVERSION_BITRATE_KBPS,
+            sample_rate: DEFAULT_CONVERSION_SAMPLE_RATE,
+            channels: DEFAULT_CONVERSION_CHANNELS,
+            normalize_loudness: false,
+            keep_source: false,
+        }
+    }
+}
+
+impl ConversionSettings {
+    /// Check the settings are within what the encoders accept
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_CONVERSION_BITRATE_KBPS..=MAX_CONVERSION_BITRATE_KBPS).contains(&self.bitrate_kbps) {
+            return Err(format!(
+                "Bitrate must be between {} and {} kbps", MIN_CONVERSION_BITRATE_KBPS, MAX_CONVERSION_BITRATE_KBPS
+            ));
+        }
+        if !CONVERSION_SAMPLE_RATES.contains(&self.sample_rate) {
+            return Err(format!("Sample rate must be one of {:?} Hz", CONVERSION_SAMPLE_RATES));
+        }
+        if !(1..=2).contains(&self.channels) {
+            return Err("Channels must be 1 (mono) or 2 (stereo)".to_string());
+        }
+        Ok(())
+    }
+}
+
 /// Audio conversion service for optimizing recorded audio files
 pub struct AudioConverter;
 
@@ -13,95 +170,60 @@ impl AudioConverter {
     /// 
     /// This function:
     /// 1. Uses bundled FFmpeg binary to convert WAV to OGG Opus format
-    /// 2. Converts to mono and resamples to 16kHz 
-    /// 3. Optimized for speech recognition with 64kbps bitrate
-    /// 4. Creates standard OGG Opus file compatible with all players
-    /// 5. Returns the new Opus file path
-    pub async fn convert_wav_to_opus(wav_path: &Path, app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    /// 2. Applies the channel count, sample rate and bitrate from `settings`
+    ///    (mono, 16kHz and 64kbps by default, optimized for speech recognition),
+    ///    normalizing loudness first if `settings.normalize_loudness` is set
+    /// 3. Creates standard OGG Opus file compatible with all players
+    /// 4. Returns the new Opus file path
+    pub async fn convert_wav_to_opus(wav_path: &Path, settings: &ConversionSettings, app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+        Self::convert_wav_to(AudioFormat::Opus, wav_path, settings, app_handle).await
+    }
+
+    /// Convert WAV file to MP3 (libmp3lame) for compatibility with older players
+    pub async fn convert_wav_to_mp3(wav_path: &Path, settings: &ConversionSettings, app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+        Self::convert_wav_to(AudioFormat::Mp3, wav_path, settings, app_handle).await
+    }
+
+    /// Convert a WAV file to `format` with bundled FFmpeg, deleting the WAV on success
+    pub async fn convert_wav_to(format: AudioFormat, wav_path: &Path, settings: &ConversionSettings, app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
         // Validate input file exists
         if !wav_path.exists() {
             return Err(format!("WAV file does not exist: {}", wav_path.display()));
         }
 
-        // Create output path with .opus extension
-        let opus_path = wav_path.with_extension("opus");
+        // Create output path with the format's extension
+        let output_path = wav_path.with_extension(format.extension());
 
-        println!("Converting {} to {}", wav_path.display(), opus_path.display());
+        println!("Converting {} to {}", wav_path.display(), output_path.display());
 
-        // Get bundled FFmpeg path (handle both development and production modes)
-        let ffmpeg_name = if cfg!(target_os = "windows") {
-            "ffmpeg-windows.exe"
-        } else if cfg!(target_os = "macos") {
-            "ffmpeg-macos"
-        } else {
-            "ffmpeg-linux"
-        };
-        
-        // Try development mode path first (binaries/ subdirectory)
-        let dev_path = std::env::current_exe()
-            .ok()
-            .and_then(|exe| exe.parent().map(|p| p.join("binaries").join(ffmpeg_name)));
-            
-        // Try production mode path (resource directory)
-        let prod_path = app_handle.path().resource_dir()
-            .ok()
-            .map(|dir| dir.join(ffmpeg_name));
-        
-        // Debug: Log paths being checked
-        if let Some(ref path) = dev_path {
-            println!("Checking dev path: {} (exists: {})", path.display(), path.exists());
-        }
-        if let Some(ref path) = prod_path {
-            println!("Checking prod path: {} (exists: {})", path.display(), path.exists());
-        }
-        
-        // Find the first path that exists
-        let ffmpeg_path = dev_path
-            .clone()
-            .filter(|p| p.exists())
-            .or_else(|| prod_path.clone().filter(|p| p.exists()))
-            .ok_or_else(|| {
-                let dev_str = dev_path.map(|p| p.display().to_string()).unwrap_or_else(|| "unknown".to_string());
-                let prod_str = prod_path.map(|p| p.display().to_string()).unwrap_or_else(|| "unknown".to_string());
-                format!("FFmpeg binary not found. Tried dev: {}, prod: {}", dev_str, prod_str)
-            })?;
-        
-        // Make executable on Unix systems
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            if let Ok(metadata) = fs::metadata(&ffmpeg_path) {
-                let mut perms = metadata.permissions();
-                perms.set_mode(0o755);
-                let _ = fs::set_permissions(&ffmpeg_path, perms);
-            }
-        }
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
 
         // Perform conversion using bundled FFmpeg
         let wav_path_owned = wav_path.to_owned();
-        let opus_path_owned = opus_path.clone();
+        let output_path_owned = output_path.clone();
         let ffmpeg_path_owned = ffmpeg_path.clone();
+        let codec_args = format.codec_args(settings);
         
         let result = task::spawn_blocking(move || {
-            Self::convert_to_opus_ffmpeg(&wav_path_owned, &opus_path_owned, &ffmpeg_path_owned)
+            Self::convert_with_ffmpeg(&wav_path_owned, &output_path_owned, &ffmpeg_path_owned, &codec_args)
         }).await
         .map_err(|e| format!("Failed to spawn conversion task: {}", e))?;
 
         match result {
-            Ok(_) => {
+            Ok(stderr) => {
                 // Verify the conversion was successful
-                if !opus_path.exists() {
-                    return Err("Opus file was not created successfully".to_string());
+                if !output_path.exists() {
+                    return Err(format!("{:?} file was not created successfully", format));
                 }
 
                 // Verify the Opus file has content
-                let opus_size = fs::metadata(&opus_path)
-                    .map_err(|e| format!("Failed to check Opus file size: {}", e))?
+                let output_size = fs::metadata(&output_path)
+                    .map_err(|e| format!("Failed to check converted file size: {}", e))?
                     .len();
 
-                if opus_size == 0 {
-                    let _ = fs::remove_file(&opus_path);
-                    return Err("Opus file was created but is empty".to_string());
+                if output_size == 0 {
+                    let _ = fs::remove_file(&output_path);
+                    return Err(format!("{:?} file was created but is empty", format));
                 }
 
                 // Get size reduction info for logging
@@ -110,58 +232,367 @@ impl AudioConverter {
                     .unwrap_or(0);
                 
                 let reduction = if original_size > 0 {
-                    ((original_size - opus_size) as f64 / original_size as f64) * 100.0
+                    ((original_size - output_size) as f64 / original_size as f64) * 100.0
                 } else {
                     0.0
                 };
 
-                // Delete original WAV file to save space (Opus now handles both playback and transcription)
-                if let Err(e) = fs::remove_file(wav_path) {
+                // Delete original WAV file to save space (the converted file handles both playback and transcription)
+                if settings.keep_source {
+                    println!("Keeping original WAV file: {}", wav_path.display());
+                } else if let Err(e) = fs::remove_file(wav_path) {
                     eprintln!("Warning: Failed to delete original WAV file: {}", e);
                     // Don't return error here - conversion succeeded, cleanup failed
                 }
 
-                println!("Successfully converted to Opus: {} bytes → {} bytes ({:.1}% reduction)", 
-                        original_size, opus_size, reduction);
-                Ok(opus_path)
+                println!("Successfully converted to {:?}: {} bytes → {} bytes ({:.1}% reduction)", 
+                        format, original_size, output_size, reduction);
+                if settings.normalize_loudness {
+                    match parse_input_loudness(&stderr) {
+                        Some(measured) => println!("Loudness normalized: {:.1} LUFS → {:.1} LUFS target", measured, LOUDNORM_TARGET_LUFS),
+                        None => println!("Loudness normalized to {:.1} LUFS target (input loudness not reported)", LOUDNORM_TARGET_LUFS),
+                    }
+                }
+                Ok(output_path)
             }
             Err(e) => {
                 // Clean up failed conversion attempt
-                if opus_path.exists() {
-                    let _ = fs::remove_file(&opus_path);
+                if output_path.exists() {
+                    let _ = fs::remove_file(&output_path);
                 }
                 Err(e)
             }
         }
     }
 
-    /// Convert WAV to Opus using bundled FFmpeg
-    fn convert_to_opus_ffmpeg(input_path: &Path, output_path: &Path, ffmpeg_path: &Path) -> Result<(), String> {
+    /// A temporary file in `dir` named after `source_path`, unique per call so concurrent jobs
+    /// on the same recording don't overwrite each other's files
+    pub fn unique_temp_path(dir: &Path, source_path: &Path, suffix: &str) -> PathBuf {
+        let stem = source_path.file_stem().and_then(|s| s.to_str()).unwrap_or("recording");
+        dir.join(format!("{}.{}{}", stem, uuid::Uuid::new_v4().simple(), suffix))
+    }
+
+    /// Strip leading and trailing audio quieter than `threshold_db` from a WAV file in place,
+    /// returning the trimmed duration in milliseconds
+    pub async fn trim_silence(wav_path: &Path, threshold_db: f32, app_handle: &tauri::AppHandle) -> Result<u64, String> {
+        if !wav_path.exists() {
+            return Err(format!("WAV file does not exist: {}", wav_path.display()));
+        }
+        
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        // Kept next to the recording so the rename over it stays on one volume
+        let trimmed_dir = wav_path.parent().unwrap_or(Path::new("."));
+        let trimmed_path = Self::unique_temp_path(trimmed_dir, wav_path, ".trimmed.wav");
+        let wav_path_owned = wav_path.to_owned();
+        let trimmed_path_owned = trimmed_path.clone();
+        
+        let output = task::spawn_blocking(move || {
+            Command::new(&ffmpeg_path)
+                .arg("-i").arg(&wav_path_owned)
+                .args(["-af", &Self::silence_filter(threshold_db)])
+                .arg("-y")
+                .arg(&trimmed_path_owned)
+                .output()
+        }).await
+        .map_err(|e| format!("Failed to spawn trim task: {}", e))?
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+        
+        if !output.status.success() {
+            let _ = fs::remove_file(&trimmed_path);
+            return Err(format!("FFmpeg silence trim failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        
+        // Keep the original if everything was below the threshold
+        let duration_ms = hound::WavReader::open(&trimmed_path)
+            .map(|reader| reader.duration() as u64 * 1000 / reader.spec().sample_rate.max(1) as u64)
+            .unwrap_or(0);
+        if duration_ms == 0 {
+            let _ = fs::remove_file(&trimmed_path);
+            return Err("Recording is entirely silent, leaving it untrimmed".to_string());
+        }
+        
+        fs::rename(&trimmed_path, wav_path)
+            .map_err(|e| format!("Failed to replace WAV with trimmed audio: {}", e))?;
+        Ok(duration_ms)
+    }
+
+    /// Write the `start_secs`..`end_secs` range of a recording to `output_path`, copying the
+    /// stream when possible and re-encoding with `settings` if FFmpeg can't cut it as-is
+    pub async fn extract_range(input_path: &Path, output_path: &Path, start_secs: f64, end_secs: f64, settings: &ConversionSettings, app_handle: &tauri::AppHandle) -> Result<(), String> {
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        let range_args = [
+            "-ss".to_string(), format!("{:.3}", start_secs),
+            "-to".to_string(), format!("{:.3}", end_secs),
+            "-vn".to_string(),
+        ];
+        let copy_args: Vec<String> = range_args.iter().cloned()
+            .chain(["-c".to_string(), "copy".to_string()])
+            .collect();
+        let reencode_args: Option<Vec<String>> = AudioFormat::from_path(output_path)
+            .map(|format| range_args.iter().cloned().chain(format.codec_args(settings)).collect());
+        
+        let input_path = input_path.to_owned();
+        let output_path_owned = output_path.to_owned();
+        let result = task::spawn_blocking(move || {
+            Self::convert_with_ffmpeg(&input_path, &output_path_owned, &ffmpeg_path, &copy_args)
+                .or_else(|copy_error| match reencode_args {
+                    Some(args) => {
+                        println!("Stream copy failed ({}), re-encoding range instead", copy_error);
+                        Self::convert_with_ffmpeg(&input_path, &output_path_owned, &ffmpeg_path, &args)
+                    }
+                    None => Err(copy_error),
+                })
+        }).await
+        .map_err(|e| format!("Failed to spawn trim task: {}", e))?;
+        
+        if let Err(e) = result {
+            let _ = fs::remove_file(output_path);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// FFmpeg filter removing silence at both ends: trim the start, reverse, trim again, reverse back
+    fn silence_filter(threshold_db: f32) -> String {
+        let trim_start = format!(
+            "silenceremove=start_periods=1:start_threshold={}dB:start_silence={}",
+            threshold_db, SILENCE_TRIM_PADDING_SECS
+        );
+        format!("{0},areverse,{0},areverse", trim_start)
+    }
+
+    /// Check that the bundled FFmpeg binary exists and runs. This waits on FFmpeg, so async
+    /// callers should run it on a blocking task.
+    pub fn verify_ffmpeg(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        
+        let output = Command::new(&ffmpeg_path)
+            .arg("-version")
+            .output()
+            .map_err(|e| format!("FFmpeg at {} could not be executed: {}", ffmpeg_path.display(), e))?;
+        if !output.status.success() {
+            return Err(format!("FFmpeg at {} exited with {}", ffmpeg_path.display(), output.status));
+        }
+        
+        Ok(ffmpeg_path)
+    }
+
+    /// Locate the bundled FFmpeg binary (handles both development and production modes)
+    pub fn find_ffmpeg(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+        Self::find_bundled_binary(app_handle, "ffmpeg", "FFmpeg")
+    }
+
+    /// Locate a bundled binary named `<base_name>-<platform>` in `binaries/` (dev) or the resource directory (prod)
+    pub fn find_bundled_binary(app_handle: &tauri::AppHandle, base_name: &str, display_name: &str) -> Result<PathBuf, String> {
+        let binary_name = if cfg!(target_os = "windows") {
+            format!("{}-windows.exe", base_name)
+        } else if cfg!(target_os = "macos") {
+            format!("{}-macos", base_name)
+        } else {
+            format!("{}-linux", base_name)
+        };
+        
+        // Try development mode path first (binaries/ subdirectory)
+        let dev_path = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|p| p.join("binaries").join(&binary_name)));
+            
+        // Try production mode path (resource directory)
+        let prod_path = app_handle.path().resource_dir()
+            .ok()
+            .map(|dir| dir.join(&binary_name));
+        
+        // Debug: Log paths being checked
+        if let Some(ref path) = dev_path {
+            println!("Checking dev path: {} (exists: {})", path.display(), path.exists());
+        }
+        if let Some(ref path) = prod_path {
+            println!("Checking prod path: {} (exists: {})", path.display(), path.exists());
+        }
+        
+        // Find the first path that exists
+        let binary_path = dev_path
+            .clone()
+            .filter(|p| p.exists())
+            .or_else(|| prod_path.clone().filter(|p| p.exists()))
+            .ok_or_else(|| {
+                let dev_str = dev_path.map(|p| p.display().to_string()).unwrap_or_else(|| "unknown".to_string());
+                let prod_str = prod_path.map(|p| p.display().to_string()).unwrap_or_else(|| "unknown".to_string());
+                format!("{} binary not found. Tried dev: {}, prod: {}", display_name, dev_str, prod_str)
+            })?;
+        
+        // Make executable on Unix systems
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Ok(metadata) = fs::metadata(&binary_path) {
+                let mut perms = metadata.permissions();
+                perms.set_mode(0o755);
+                let _ = fs::set_permissions(&binary_path, perms);
+            }
+        }
+
+        Ok(binary_path)
+    }
+
+    /// Convert WAV using bundled FFmpeg with the given encoder arguments, returning FFmpeg's log output
+    fn convert_with_ffmpeg(input_path: &Path, output_path: &Path, ffmpeg_path: &Path, codec_args: &[String]) -> Result<String, String> {
         println!("Using FFmpeg at: {}", ffmpeg_path.display());
         
-        // Run FFmpeg to convert WAV to OGG Opus
         let output = Command::new(ffmpeg_path)
-            .args([
-                "-i", input_path.to_str().unwrap(),
-                "-c:a", "libopus",           // Use Opus codec
-                "-b:a", "64k",              // 64kbps bitrate for speech
-                "-ar", AUDIO_SAMPLE_RATE_STR, // 16kHz sample rate
-                "-ac", "1",                 // Mono (1 channel)
-                "-y",                       // Overwrite output file
-                output_path.to_str().unwrap()
-            ])
+            .arg("-i").arg(input_path)
+            .args(codec_args)
+            // Deterministic output: no random Ogg stream serial or encoder tags varying between runs
+            .args(["-fflags", "+bitexact", "-flags:a", "+bitexact"])
+            .arg("-y")                      // Overwrite output file
+            .arg(output_path)
             .output()
             .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
         
         if output.status.success() {
             println!("FFmpeg conversion completed successfully");
-            Ok(())
+            Ok(String::from_utf8_lossy(&output.stderr).into_owned())
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
             Err(format!("FFmpeg conversion failed: {}", stderr))
         }
     }
 
+    /// Decode a compressed recording to a temporary 32-bit float WAV file for playback.
+    /// The caller is responsible for removing the returned file.
+    pub fn decode_to_wav(input_path: &Path, app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        let output_path = Self::unique_temp_path(&std::env::temp_dir(), input_path, "-playback.wav");
+        
+        let codec_args = ["-vn", "-c:a", "pcm_f32le"].map(String::from);
+        if let Err(e) = Self::convert_with_ffmpeg(input_path, &output_path, &ffmpeg_path, &codec_args) {
+            let _ = fs::remove_file(&output_path);
+            return Err(e);
+        }
+        
+        Ok(output_path)
+    }
+
+    /// Join audio files end to end with FFmpeg's concat demuxer, without re-encoding.
+    /// All inputs must share the same codec and stream parameters.
+    pub async fn concat_files(input_paths: &[PathBuf], output_path: &Path, app_handle: &tauri::AppHandle) -> Result<(), String> {
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        let list_path = output_path.with_extension("concat.txt");
+        fs::write(&list_path, Self::concat_list(input_paths))
+            .map_err(|e| format!("Failed to write concat list: {}", e))?;
+        
+        let list_path_owned = list_path.clone();
+        let output_path_owned = output_path.to_owned();
+        let output = task::spawn_blocking(move || {
+            Command::new(&ffmpeg_path)
+                .args(["-f", "concat", "-safe", "0"])
+                .arg("-i").arg(&list_path_owned)
+                .args(["-c", "copy"])
+                .arg("-y")
+                .arg(&output_path_owned)
+                .output()
+        }).await
+        .map_err(|e| format!("Failed to spawn concat task: {}", e))?
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e));
+        let _ = fs::remove_file(&list_path);
+        let output = output?;
+        
+        if !output.status.success() {
+            let _ = fs::remove_file(output_path);
+            return Err(format!("FFmpeg concat failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+
+    /// Codec, sample rate and channel layout of the first audio stream in `path`
+    pub async fn probe_audio_stream(path: &Path, app_handle: &tauri::AppHandle) -> Result<AudioStreamInfo, String> {
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        let path_owned = path.to_owned();
+        // Without an output FFmpeg exits with an error, but still lists the input's streams
+        let output = task::spawn_blocking(move || {
+            Command::new(&ffmpeg_path)
+                .arg("-hide_banner")
+                .arg("-i").arg(&path_owned)
+                .output()
+        }).await
+        .map_err(|e| format!("Failed to spawn probe task: {}", e))?
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+        
+        parse_audio_stream(&String::from_utf8_lossy(&output.stderr))
+            .ok_or_else(|| format!("No audio stream found in {}", path.display()))
+    }
+
+    /// Length of a recording in milliseconds, read from the WAV header or, for other formats,
+    /// from FFmpeg's description of the input. None if it can't be determined.
+    pub fn probe_duration_ms(path: &Path, ffmpeg_path: Option<&Path>) -> Option<u64> {
+        if let Ok(reader) = hound::WavReader::open(path) {
+            return Some(reader.duration() as u64 * 1000 / reader.spec().sample_rate.max(1) as u64);
+        }
+        let output = Command::new(ffmpeg_path?)
+            .arg("-hide_banner")
+            .arg("-i").arg(path)
+            .output()
+            .ok()?;
+        parse_duration_ms(&String::from_utf8_lossy(&output.stderr))
+    }
+
+    /// Split a recording into consecutive pieces of about `chunk_secs` each in `output_dir`,
+    /// without re-encoding. Cuts land on packet boundaries, so each chunk reports its actual start.
+    pub async fn split_into_chunks(input_path: &Path, chunk_secs: u32, output_dir: &Path, app_handle: &tauri::AppHandle) -> Result<Vec<AudioChunk>, String> {
+        let ffmpeg_path = Self::find_ffmpeg(app_handle)?;
+        fs::create_dir_all(output_dir)
+            .map_err(|e| format!("Failed to create chunk directory: {}", e))?;
+        
+        let extension = input_path.extension().and_then(|ext| ext.to_str()).unwrap_or("opus");
+        let output_pattern = output_dir.join(format!("chunk_%03d.{}", extension));
+        let list_path = output_dir.join("chunks.csv");
+        let input_path_owned = input_path.to_owned();
+        let list_path_owned = list_path.clone();
+        let output = task::spawn_blocking(move || {
+            Command::new(&ffmpeg_path)
+                .arg("-i").arg(&input_path_owned)
+                .args(["-vn", "-c", "copy", "-f", "segment", "-reset_timestamps", "1"])
+                .arg("-segment_time").arg(chunk_secs.to_string())
+                .args(["-segment_list_type", "csv"])
+                .arg("-segment_list").arg(&list_path_owned)
+                .arg("-y")
+                .arg(&output_pattern)
+                .output()
+        }).await
+        .map_err(|e| format!("Failed to spawn split task: {}", e))?
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+        
+        if !output.status.success() {
+            return Err(format!("FFmpeg split failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        let list = fs::read_to_string(&list_path)
+            .map_err(|e| format!("Failed to read chunk list: {}", e))?;
+        let chunks = Self::parse_segment_list(&list, output_dir);
+        if chunks.is_empty() {
+            return Err("FFmpeg produced no chunks".to_string());
+        }
+        Ok(chunks)
+    }
+
+    /// Chunks listed in FFmpeg's CSV segment list, one "name,start,end" line per chunk
+    fn parse_segment_list(list: &str, output_dir: &Path) -> Vec<AudioChunk> {
+        list.lines()
+            .filter_map(|line| {
+                let mut fields = line.split(',');
+                let name = fields.next()?.trim();
+                let start_secs = fields.next()?.trim().parse().ok()?;
+                (!name.is_empty()).then(|| AudioChunk { path: output_dir.join(name), start_secs })
+            })
+            .collect()
+    }
+
+    /// Concat demuxer input list; single quotes in paths are escaped as `'\''`
+    fn concat_list(input_paths: &[PathBuf]) -> String {
+        input_paths.iter()
+            .map(|path| format!("file '{}'\n", path.display().to_string().replace('\'', "'\\''")))
+            .collect()
+    }
+
     /// Get file size reduction info for logging/debugging
     pub fn get_conversion_info(original_path: &Path, converted_path: &Path) -> Result<String, String> {
         let original_size = fs::metadata(original_path)
@@ -183,4 +614,154 @@ impl AudioConverter {
             original_size, converted_size, reduction
         ))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codec_args_per_format() {
+        assert_eq!(
+            AudioFormat::Opus.codec_args(&ConversionSettings::default()),
+            ["-c:a", "libopus", "-b:a", "64k", "-ar", "16000", "-ac", "1"]
+        );
+        let stereo = ConversionSettings { bitrate_kbps: 192, sample_rate: 48000, channels: 2, normalize_loudness: false, keep_source: false };
+        assert_eq!(
+            AudioFormat::Mp3.codec_args(&stereo),
+            ["-c:a", "libmp3lame", "-b:a", "192k", "-ar", "48000", "-ac", "2"]
+        );
+    }
+
+    #[test]
+    fn test_codec_args_normalize_loudness_first() {
+        let settings = ConversionSettings { normalize_loudness: true, ..ConversionSettings::default() };
+        let args = AudioFormat::Opus.codec_args(&settings);
+        assert_eq!(args[0], "-af");
+        assert_eq!(args[1], "loudnorm=I=-16:TP=-1.5:LRA=11:print_format=summary");
+        assert_eq!(args[2], "-c:a");
+    }
+
+    #[test]
+    fn test_parse_input_loudness_from_summary() {
+        let stderr = "[Parsed_loudnorm_0 @ 0x1]\nInput Integrated:    -27.3 LUFS\nInput True Peak:      -9.1 dBTP\n\
+                      Output Integrated:   -16.2 LUFS\n";
+        assert_eq!(parse_input_loudness(stderr), Some(-27.3));
+        assert_eq!(parse_input_loudness("size=12kB time=00:00:03.00"), None);
+    }
+
+    #[test]
+    fn test_parse_audio_stream_from_input_listing() {
+        let stderr = "Input #0, ogg, from 'a.opus':\n  Duration: 00:00:03.02, start: 0.000000, bitrate: 66 kb/s\n\
+                      \x20 Stream #0:0: Audio: opus, 48000 Hz, mono, fltp\n\
+                      At least one output file must be specified\n";
+        assert_eq!(parse_audio_stream(stderr), Some(AudioStreamInfo { codec: "opus".to_string(), sample_rate: 48000, channels: "mono".to_string() }));
+        
+        let stderr = "  Stream #0:0(und): Audio: aac (LC) (mp4a / 0x6134706D), 44100 Hz, stereo, fltp, 128 kb/s (default)\n";
+        assert_eq!(parse_audio_stream(stderr), Some(AudioStreamInfo { codec: "aac".to_string(), sample_rate: 44100, channels: "stereo".to_string() }));
+        assert_eq!(parse_audio_stream("a.opus: No such file or directory"), None);
+    }
+
+    #[test]
+    fn test_parse_duration_from_input_listing() {
+        assert_eq!(parse_duration_ms("  Duration: 00:00:03.02, start: 0.000000, bitrate: 66 kb/s\n"), Some(3020));
+        assert_eq!(parse_duration_ms("  Duration: 01:02:03.50, bitrate: 64 kb/s\n"), Some(3_723_500));
+        assert_eq!(parse_duration_ms("  Duration: N/A, bitrate: N/A\n"), None);
+        assert_eq!(parse_duration_ms("a.opus: No such file or directory"), None);
+    }
+
+    #[test]
+    fn test_silence_filter_trims_both_ends() {
+        assert_eq!(
+            AudioConverter::silence_filter(-50.0),
+            "silenceremove=start_periods=1:start_threshold=-50dB:start_silence=0.5,areverse,\
+             silenceremove=start_periods=1:start_threshold=-50dB:start_silence=0.5,areverse"
+        );
+    }
+
+    #[test]
+    fn test_conversion_settings_validation() {
+        assert!(ConversionSettings::default().validate().is_ok());
+        let valid = ConversionSettings { bitrate_kbps: 128, sample_rate: 48000, channels: 2, normalize_loudness: false, keep_source: false };
+        assert!(valid.validate().is_ok());
+        assert!(ConversionSettings { bitrate_kbps: 1000, ..valid }.validate().is_err());
+        assert!(ConversionSettings { sample_rate: 44100, ..valid }.validate().is_err());
+        assert!(ConversionSettings { channels: 0, ..valid }.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_segment_list() {
+        let list = "chunk_000.opus,0.000000,600.020000\nchunk_001.opus,600.020000,912.500000\n\n";
+        let chunks = AudioConverter::parse_segment_list(list, Path::new("/tmp/chunks"));
+        assert_eq!(chunks, vec![
+            AudioChunk { path: PathBuf::from("/tmp/chunks/chunk_000.opus"), start_secs: 0.0 },
+            AudioChunk { path: PathBuf::from("/tmp/chunks/chunk_001.opus"), start_secs: 600.02 },
+        ]);
+    }
+
+    #[test]
+    fn test_concat_list_quotes_paths() {
+        let paths = [PathBuf::from("/rec/a.opus"), PathBuf::from("/rec/it's.opus")];
+        assert_eq!(
+            AudioConverter::concat_list(&paths),
+            "file '/rec/a.opus'\nfile '/rec/it'\\''s.opus'\n"
+        );
+    }
+
+    #[test]
+    fn test_stereo_recording_stays_stereo_through_conversion() {
+        // Uses FFmpeg from PATH since there is no app handle to locate the bundled one
+        let ffmpeg_path = PathBuf::from("ffmpeg");
+        if Command::new(&ffmpeg_path).arg("-version").output().is_err() {
+            eprintln!("FFmpeg not found on PATH, skipping");
+            return;
+        }
+        
+        // Record one second of stereo through the same writer path as a capture
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("stereo.wav");
+        let spec = hound::WavSpec { channels: 2, sample_rate: 48000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        // Left and right carry different tones so a downmix would be detectable
+        let tone = |frame: usize, hz: f32| (frame as f32 / 48000.0 * hz * std::f32::consts::TAU).sin() * 0.25;
+        let samples: Vec<f32> = (0..48000).flat_map(|frame| [tone(frame, 440.0), tone(frame, 660.0)]).collect();
+        let mut writer = hound::WavWriter::create(&wav_path, spec).unwrap();
+        let written = crate::audio_system::write_remixed(&mut writer, &samples, 2, 2, &mut None, &mut Vec::new()).unwrap();
+        writer.finalize().unwrap();
+        assert_eq!(written, samples.len() as u64);
+        
+        let settings = ConversionSettings { bitrate_kbps: 128, sample_rate: 48000, channels: 2, normalize_loudness: false, keep_source: false };
+        let opus_path = dir.path().join("stereo.opus");
+        AudioConverter::convert_with_ffmpeg(&wav_path, &opus_path, &ffmpeg_path, &AudioFormat::Opus.codec_args(&settings)).unwrap();
+        
+        let decoded_path = dir.path().join("decoded.wav");
+        let decode_args = ["-c:a", "pcm_s16le"].map(String::from);
+        AudioConverter::convert_with_ffmpeg(&opus_path, &decoded_path, &ffmpeg_path, &decode_args).unwrap();
+        assert_eq!(hound::WavReader::open(&decoded_path).unwrap().spec().channels, 2);
+    }
+
+    #[test]
+    fn test_stereo_settings_keep_two_channels() {
+        let settings = ConversionSettings { bitrate_kbps: 128, sample_rate: 48000, channels: 2, normalize_loudness: false, keep_source: false };
+        let args = AudioFormat::Opus.codec_args(&settings);
+        let channels = args.iter().position(|arg| arg == "-ac").map(|i| args[i + 1].as_str());
+        assert_eq!(channels, Some("2"));
+    }
+
+    #[test]
+    fn test_unique_temp_path_differs_per_call() {
+        let source = Path::new("/rec/standup.opus");
+        let first = AudioConverter::unique_temp_path(Path::new("/tmp"), source, ".whisper.wav");
+        let second = AudioConverter::unique_temp_path(Path::new("/tmp"), source, ".whisper.wav");
+        assert_ne!(first, second);
+        let name = first.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("standup.") && name.ends_with(".whisper.wav"), "{}", name);
+        assert_eq!(first.parent(), Some(Path::new("/tmp")));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(AudioFormat::from_path(Path::new("a.mp3")), Some(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::from_path(Path::new("a.opus")), Some(AudioFormat::Opus));
+        assert_eq!(AudioFormat::from_path(Path::new("a.wav")), None);
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::atomic::Ordering;
+use tauri::{State, AppHandle};
+use crate::AppState;
+use crate::audio_system::{self, AudioDeviceInfo, PrerollSettings, RecordChannels, WavBitDepth};
+use crate::app_config::AppConfig;
+use crate::constants::{MAX_INPUT_GAIN, MAX_PREROLL_SECS, MAX_RECORD_SAMPLE_RATE, MIN_RECORD_SAMPLE_RATE};
+use crate::error::{AppError, Result};
+use tracing::info;
+
+pub async fn list_input_devices() -> Result<Vec<AudioDeviceInfo>> {
+    audio_system::list_input_devices()
+}
+
+pub async fn list_output_devices() -> Result<Vec<AudioDeviceInfo>> {
+    audio_system::list_output_devices()
+}
+
+// Whether the startup check found a working FFmpeg binary
+pub async fn is_ffmpeg_available(state: State<'_, AppState>) -> Result<bool> {
+    Ok(state.ffmpeg_available.load(Ordering::Relaxed))
+}
+
+// Select the input device used for new recordings and persist the choice
+pub async fn set_input_device(state: State<'_, AppState>, app_handle: AppHandle, name: String) -> Result<()> {
+    {
+        let mut input_device = state.input_device.lock().unwrap();
+        *input_device = Some(name.clone());
+    }
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.input_device = Some(name);
+    config.save(&app_handle).await?;
+    
+    // Restart the pre-roll on the new device
+    apply_preroll(&state, config.preroll_enabled, config.preroll_secs)?;
+    
+    info!("Input device set to: {:?}", config.input_device);
+    Ok(())
+}
+
+// Select the output device used for playback and persist the choice
+pub async fn set_output_device(state: State<'_, AppState>, app_handle: AppHandle, name: String) -> Result<()> {
+    {
+        let mut output_device = state.output_device.lock().unwrap();
+        *output_device = Some(name.clone());
+    }
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.output_device = Some(name);
+    config.save(&app_handle).await?;
+    
+    info!("Output device set to: {:?}", config.output_device);
+    Ok(())
+}
+
+// Choose the channel layout for new recordings and persist it
+pub async fn set_record_channels(state: State<'_, AppState>, app_handle: AppHandle, channels: RecordChannels) -> Result<()> {
+    *state.record_channels.lock().unwrap() = channels;
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.record_channels = channels;
+    config.save(&app_handle).await?;
+    
+    info!("Record channels set to: {:?}", channels);
+    Ok(())
+}
+
+// Choose the sample format new recordings are written with and persist it
+pub async fn set_wav_bit_depth(state: State<'_, AppState>, app_handle: AppHandle, bit_depth: WavBitDepth) -> Result<()> {
+    *state.wav_bit_depth.lock().unwrap() = bit_depth;
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.wav_bit_depth = bit_depth;
+    config.save(&app_handle).await?;
+    
+    info!("WAV bit depth set to: {:?}", bit_depth);
+    Ok(())
+}
+
+// Choose the sample rate new recordings are written at (None = the input device's rate) and persist it
+pub async fn set_record_sample_rate(state: State<'_, AppState>, app_handle: AppHandle, sample_rate: Option<u32>) -> Result<()> {
+    if let Some(rate) = sample_rate {
+        if !(MIN_RECORD_SAMPLE_RATE..=MAX_RECORD_SAMPLE_RATE).contains(&rate) {
+            return Err(AppError::Config(format!(
+                "Sample rate must be between {} and {} Hz", MIN_RECORD_SAMPLE_RATE, MAX_RECORD_SAMPLE_RATE
+            )));
+        }
+    }
+    *state.record_sample_rate.lock().unwrap() = sample_rate;
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.record_sample_rate = sample_rate;
+    config.save(&app_handle).await?;
+    
+    info!("Record sample rate set to: {:?}", sample_rate);
+    Ok(())
+}
+
+// Set the microphone gain applied to new samples (takes effect immediately) and persist it
+pub async fn set_input_gain(state: State<'_, AppState>, app_handle: AppHandle, gain: f32) -> Result<()> {
+    if !(0.0..=MAX_INPUT_GAIN).contains(&gain) {
+        return Err(AppError::Config(format!("Input gain must be between 0 and {}", MAX_INPUT_GAIN)));
+    }
+    state.input_gain.store(gain.to_bits(), Ordering::Relaxed);
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.input_gain = gain;
+    config.save(&app_handle).await?;
+    
+    info!("Input gain set to: {}", gain);
+    Ok(())
+}
+
+// Enable or disable the recording noise gate, optionally changing its threshold (dBFS)
+pub async fn set_noise_gate(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool, threshold_db: Option<f32>) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    if let Some(threshold_db) = threshold_db {
+        if !(-100.0..=0.0).contains(&threshold_db) {
+            return Err(AppError::Config("Noise gate threshold must be between -100 and 0 dB".to_string()));
+        }
+        config.noise_gate_threshold = threshold_db;
+    }
+    config.noise_gate_enabled = enabled;
+    config.save(&app_handle).await?;
+    
+    // Picked up by the next recording
+    *state.noise_gate_threshold_db.lock().unwrap() = config.noise_gate();
+    info!("Noise gate set to: {:?}", config.noise_gate());
+    Ok(())
+}
+
+// Turn the pre-roll buffer on or off and persist the choice
+pub async fn set_preroll(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool, seconds: Option<u32>) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    if let Some(seconds) = seconds {
+        if !(1..=MAX_PREROLL_SECS).contains(&seconds) {
+            return Err(AppError::Config(format!("Pre-roll must be between 1 and {} seconds", MAX_PREROLL_SECS)));
+        }
+        config.preroll_secs = seconds;
+    }
+    config.preroll_enabled = enabled;
+    config.save(&app_handle).await?;
+    
+    apply_preroll(&state, config.preroll_enabled, config.preroll_secs)?;
+    
+    info!("Pre-roll {} ({} seconds)", if enabled { "enabled" } else { "disabled" }, config.preroll_secs);
+    Ok(())
+}
+
+// Start or stop buffering microphone audio on the selected input device while idle
+pub fn apply_preroll(state: &AppState, enabled: bool, seconds: u32) -> Result<()> {
+    let settings = enabled.then(|| PrerollSettings {
+        seconds: seconds.min(MAX_PREROLL_SECS),
+        device_name: state.input_device.lock().unwrap().clone(),
+        buffer: state.preroll_buffer.clone(),
+    });
+    state.audio_recorder.lock().unwrap().set_preroll(settings)
+}
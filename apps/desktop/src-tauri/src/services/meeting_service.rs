@@ -1,16 +1,295 @@
-use tauri::State;
-use crate::{AppState, meeting_detector::MeetingState};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{State, AppHandle, Manager};
+use tracing::{info, warn, error};
+use crate::{AppState, RecordingSource, RecordingState, meeting_detector::{DetectionProbe, MeetingAction, MeetingApp, MeetingDetector, MeetingState, MeetingWatcher}};
+use crate::app_config::AppConfig;
+use crate::constants::*;
+use crate::events::EventEmitter;
+use super::recording_service::{start_recording, stop_recording};
 
-pub async fn start_meeting_detection(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn start_meeting_detection(state: State<'_, AppState>, app_handle: AppHandle) -> Result<(), String> {
     state.meeting_detector.start_monitoring()?;
+    start_meeting_watcher(&state, app_handle);
     Ok(())
 }
 
+// Stop the detector and wait for the meeting watcher thread to exit
 pub async fn stop_meeting_detection(state: State<'_, AppState>) -> Result<(), String> {
     state.meeting_detector.stop_monitoring();
+    if let Some(stopped) = stop_meeting_watcher(&state) {
+        let _ = stopped.await;
+    }
     Ok(())
 }
 
+// Replace any running meeting watcher with a new one
+fn start_meeting_watcher(state: &AppState, app_handle: AppHandle) {
+    stop_meeting_watcher(state);
+    let watcher = spawn_meeting_watcher(app_handle, state.meeting_detector.clone(), state.meeting_action.clone());
+    *state.meeting_watcher.lock().unwrap() = Some(watcher);
+}
+
+// Stop the watcher on a blocking thread, as joining waits for its current tick (which may be
+// stopping a recording); await the returned handle to wait for it
+fn stop_meeting_watcher(state: &AppState) -> Option<tauri::async_runtime::JoinHandle<()>> {
+    let watcher = state.meeting_watcher.lock().unwrap().take()?;
+    Some(tauri::async_runtime::spawn_blocking(move || watcher.stop()))
+}
+
+// Persist whether meeting detection runs (including at startup) and start or stop it now
+pub async fn set_meeting_detection_enabled(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.meeting_detection_enabled = enabled;
+    config.save(&app_handle).await?;
+    
+    apply_meeting_detection(&state, &app_handle, enabled)
+}
+
+// Start detection and its watcher if `enabled` and not already running, or stop them if not `enabled`
+pub fn apply_meeting_detection(state: &AppState, app_handle: &AppHandle, enabled: bool) -> Result<(), String> {
+    if !enabled {
+        if state.meeting_detector.is_monitoring() {
+            state.meeting_detector.stop_monitoring();
+            stop_meeting_watcher(state);
+            info!("Meeting detection stopped");
+        } else {
+            info!("Meeting detection disabled");
+        }
+        return Ok(());
+    }
+    if state.meeting_detector.is_monitoring() {
+        return Ok(());
+    }
+    
+    state.meeting_detector.start_monitoring()?;
+    start_meeting_watcher(state, app_handle.clone());
+    info!("Meeting detection started");
+    Ok(())
+}
+
+// Watch the detector and auto-record (or show a notification) when a meeting is detected,
+// until the returned watcher is stopped
+fn spawn_meeting_watcher(app_handle: AppHandle, detector: Arc<MeetingDetector>, meeting_action: Arc<Mutex<MeetingAction>>) -> MeetingWatcher {
+    let mut was_in_meeting = false;
+    let mut notification_shown = false;
+    let mut auto_recording = false;
+    // When the current auto-recording is force-stopped, per `max_meeting_recording_minutes`
+    let mut auto_record_deadline: Option<Instant> = None;
+    // Whether meeting-detected was emitted for the current meeting (so meeting-ended pairs with it)
+    let mut meeting_announced = false;
+    
+    MeetingWatcher::spawn(Duration::from_millis(MEETING_CHECK_INTERVAL_MS), move || {
+        let meeting_state = detector.get_state();
+        
+        if meeting_state.is_in_meeting && !was_in_meeting {
+            info!("Meeting detected: {:?}", meeting_state.detected_app);
+            
+            let action = *meeting_action.lock().unwrap();
+            if action == MeetingAction::Ignore {
+                info!("Ignoring meeting per configured meeting action");
+            } else if action == MeetingAction::AutoRecord {
+                // Meeting just started - record it directly
+                EventEmitter::meeting_detected(&app_handle, &meeting_state);
+                meeting_announced = true;
+                
+                match tauri::async_runtime::block_on(start_meeting_recording(&app_handle, &meeting_state)) {
+                    Ok(started) => {
+                        auto_recording = started;
+                        if started {
+                            auto_record_deadline = tauri::async_runtime::block_on(AppConfig::load(&app_handle)).ok()
+                                .and_then(|config| config.max_meeting_recording_minutes)
+                                .map(|minutes| Instant::now() + Duration::from_secs(u64::from(minutes) * 60));
+                            info!("Auto-recording started for meeting");
+                        } else {
+                            info!("Recording already in progress, not auto-recording meeting");
+                        }
+                    }
+                    Err(e) => error!("Failed to auto-start meeting recording: {}", e),
+                }
+            } else if !notification_shown {
+                // Meeting just started - show notification popup
+                if let Some(notification_window) = app_handle.get_webview_window("notification") {
+                    info!("Found notification window, showing...");
+                    
+                    let _ = notification_window.show();
+                    
+                    // Dynamically position window at top-center
+                    if let Ok(monitor) = notification_window.current_monitor() {
+                        if let Some(monitor) = monitor {
+                            let screen_size = monitor.size();
+                            let screen_width = screen_size.width as i32;
+                            
+                            // Get window size
+                            if let Ok(window_size) = notification_window.outer_size() {
+                                let window_width = window_size.width as i32;
+                                
+                                // Calculate top-right position
+                                let x = screen_width - window_width - NOTIFICATION_MARGIN_PX;
+                                let y = NOTIFICATION_TOP_PX;
+                                
+                                let _ = notification_window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }));
+                                info!("Positioned notification window at ({}, {}) - Screen width: {}, Window width: {}", 
+                                         x, y, screen_width, window_width);
+                            }
+                        }
+                    }
+                    
+                    let _ = notification_window.set_focus();
+                    info!("Notification window shown and focused");
+                    notification_shown = true;
+                    
+                    // Emit event to update the notification content
+                    EventEmitter::meeting_detected(&app_handle, &meeting_state);
+                    meeting_announced = true;
+                }
+            }
+        } else if meeting_state.is_in_meeting && auto_recording && auto_record_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            // Detection may be stuck (e.g. the browser crashed with the meeting tab open)
+            warn!("Auto-recorded meeting reached the maximum length while still detected, stopping recording");
+            auto_recording = false;
+            auto_record_deadline = None;
+            if let Err(e) = tauri::async_runtime::block_on(stop_meeting_recording(&app_handle)) {
+                error!("Failed to stop over-long meeting recording: {}", e);
+            }
+        } else if !meeting_state.is_in_meeting && was_in_meeting {
+            // Meeting just ended
+            info!("Meeting ended");
+            notification_shown = false;
+            
+            // Only stop recordings that were started for this meeting
+            if auto_recording {
+                auto_recording = false;
+                auto_record_deadline = None;
+                if let Err(e) = tauri::async_runtime::block_on(stop_meeting_recording(&app_handle)) {
+                    error!("Failed to auto-stop meeting recording: {}", e);
+                }
+            }
+            
+            // Hide notification if still open
+            if let Some(notification_window) = app_handle.get_webview_window("notification") {
+                let _ = notification_window.hide();
+            }
+            
+            if meeting_announced {
+                meeting_announced = false;
+                EventEmitter::meeting_ended(&app_handle);
+            }
+        }
+        
+        was_in_meeting = meeting_state.is_in_meeting;
+    })
+}
+
 pub async fn get_meeting_state(state: State<'_, AppState>) -> Result<MeetingState, String> {
     Ok(state.meeting_detector.get_state())
-}
\ No newline at end of file
+}
+
+// Whether the detector is currently polling for meetings
+pub async fn is_meeting_detection_active(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.meeting_detector.is_monitoring())
+}
+
+// Run one detection pass now and return what the detector saw, for troubleshooting missed meetings
+pub async fn probe_meeting_detection(state: State<'_, AppState>) -> Result<DetectionProbe, String> {
+    let detector = state.meeting_detector.clone();
+    tokio::task::spawn_blocking(move || detector.probe())
+        .await
+        .map_err(|e| format!("Detection probe failed: {}", e))
+}
+
+// Persist custom meeting URL patterns and apply them to the running detector
+pub async fn set_custom_meeting_patterns(state: State<'_, AppState>, app_handle: AppHandle, patterns: Vec<String>) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.custom_meeting_patterns = patterns.into_iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    config.save(&app_handle).await?;
+    
+    state.meeting_detector.update_settings(config.detection_settings());
+    Ok(())
+}
+
+// Persist the meeting-ended grace period and apply it to the running detector
+pub async fn set_meeting_end_grace(state: State<'_, AppState>, app_handle: AppHandle, seconds: u64) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.meeting_end_grace_secs = seconds;
+    config.save(&app_handle).await?;
+    
+    state.meeting_detector.update_settings(config.detection_settings());
+    Ok(())
+}
+
+// Persist whether meeting detection may read browser tabs and apply it to the running detector
+pub async fn set_scan_browser_urls(state: State<'_, AppState>, app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.scan_browser_urls = enabled;
+    config.save(&app_handle).await?;
+    
+    state.meeting_detector.update_settings(config.detection_settings());
+    Ok(())
+}
+
+// Persist which meeting apps are detected and apply the selection to the running detector
+pub async fn set_detect_apps(state: State<'_, AppState>, app_handle: AppHandle, apps: HashSet<MeetingApp>) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.detect_apps = apps.into_iter()
+        .filter(|app| !matches!(app, MeetingApp::Unknown(_)))
+        .collect();
+    config.save(&app_handle).await?;
+    
+    state.meeting_detector.update_settings(config.detection_settings());
+    Ok(())
+}
+
+// Persist the meeting-detected action and apply it to the meeting watcher
+pub async fn set_meeting_action(state: State<'_, AppState>, app_handle: AppHandle, action: MeetingAction) -> Result<(), String> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.meeting_action = action;
+    config.save(&app_handle).await?;
+    
+    *state.meeting_action.lock().unwrap() = action;
+    Ok(())
+}
+
+// Start recording for a newly detected meeting; returns false if a recording is already in progress
+//
+// Meetings are identified by their `started_at`; a second start for the same meeting is ignored
+// until `stop_meeting_recording` runs for it, even if the first one is still starting up.
+pub async fn start_meeting_recording(app_handle: &AppHandle, meeting: &MeetingState) -> Result<bool, String> {
+    let state = app_handle.try_state::<AppState>().ok_or("App state not available")?;
+    {
+        let mut recording_meeting = state.recording_meeting.lock().unwrap();
+        if recording_meeting.is_some() && *recording_meeting == meeting.started_at {
+            return Ok(false);
+        }
+        if !matches!(*state.recording_state.lock().unwrap(), RecordingState::Idle) {
+            return Ok(false);
+        }
+        *recording_meeting = meeting.started_at;
+    }
+    
+    // Meetings are recorded right away; a countdown would cut off the start
+    if let Err(e) = start_recording(state.clone(), app_handle.clone(), Some(0), RecordingSource::Meeting).await {
+        *state.recording_meeting.lock().unwrap() = None;
+        return Err(e.to_string());
+    }
+    EventEmitter::recording_state_changed(app_handle);
+    Ok(true)
+}
+
+// Stop the recording that was started for a meeting that has now ended. A recording the user
+// started themselves (e.g. after stopping the automatic one) is left running.
+pub async fn stop_meeting_recording(app_handle: &AppHandle) -> Result<(), String> {
+    let state = app_handle.try_state::<AppState>().ok_or("App state not available")?;
+    *state.recording_meeting.lock().unwrap() = None;
+    if state.recording_state.lock().unwrap().source() != Some(RecordingSource::Meeting) {
+        return Ok(());
+    }
+    
+    stop_recording(state, app_handle.clone()).await.map_err(|e| e.to_string())?;
+    EventEmitter::recording_state_changed(app_handle);
+    Ok(())
+}
@@ -2,9 +2,18 @@ pub mod recording_service;
 pub mod meeting_service;
 pub mod audio_converter;
 pub mod transcription_service;
+pub mod audio_service;
+pub mod waveform;
+pub mod archive;
+pub mod upload_service;
+pub mod system_service;
+pub mod permission_service;
 
 // Re-export all service functions for cleaner imports
 pub use recording_service::*;
 pub use meeting_service::*;
 pub use transcription_service::*;
+pub use audio_service::*;
+pub use system_service::*;
+pub use permission_service::*;
 // Note: AudioConverter is used internally by recording_service
@@ -0,0 +1,96 @@
+use serde::Serialize;
+use crate::error::Result;
+
+/// Whether the app may record from the microphone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MicrophonePermission {
+    Granted,
+    Denied,
+    Undetermined,
+}
+
+// Current microphone permission, without prompting
+pub async fn microphone_permission_status() -> Result<MicrophonePermission> {
+    Ok(platform::status())
+}
+
+// Show the OS permission prompt if the user hasn't decided yet, returning the resulting status
+pub async fn request_microphone_permission() -> Result<MicrophonePermission> {
+    platform::request().await
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::sync::Mutex;
+    use block2::RcBlock;
+    use objc2::msg_send;
+    use objc2::runtime::{AnyClass, AnyObject, Bool};
+    use super::MicrophonePermission;
+    use crate::error::{AppError, Result};
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: &'static AnyObject;
+    }
+
+    fn capture_device_class() -> Option<&'static AnyClass> {
+        AnyClass::get(c"AVCaptureDevice")
+    }
+
+    pub fn status() -> MicrophonePermission {
+        let Some(class) = capture_device_class() else {
+            return MicrophonePermission::Undetermined;
+        };
+        // AVAuthorizationStatus: 0 = not determined, 1 = restricted, 2 = denied, 3 = authorized
+        let status: isize = unsafe { msg_send![class, authorizationStatusForMediaType: AVMediaTypeAudio] };
+        match status {
+            3 => MicrophonePermission::Granted,
+            0 => MicrophonePermission::Undetermined,
+            _ => MicrophonePermission::Denied,
+        }
+    }
+
+    pub async fn request() -> Result<MicrophonePermission> {
+        let current = status();
+        if current != MicrophonePermission::Undetermined {
+            return Ok(current);
+        }
+        let class = capture_device_class()
+            .ok_or_else(|| AppError::Audio("AVFoundation is not available".to_string()))?;
+        
+        let granted = show_prompt(class).await
+            .map_err(|_| AppError::Audio("Microphone permission request was abandoned".to_string()))?;
+        Ok(if granted { MicrophonePermission::Granted } else { MicrophonePermission::Denied })
+    }
+
+    // Kept synchronous so the (non-Send) block is dropped before awaiting; AVFoundation retains its own copy
+    fn show_prompt(class: &AnyClass) -> tokio::sync::oneshot::Receiver<bool> {
+        // The completion handler runs on an arbitrary queue once the user answers the prompt
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        let sender = Mutex::new(Some(sender));
+        let handler = RcBlock::new(move |granted: Bool| {
+            if let Some(sender) = sender.lock().unwrap().take() {
+                let _ = sender.send(granted.as_bool());
+            }
+        });
+        unsafe {
+            let _: () = msg_send![class, requestAccessForMediaType: AVMediaTypeAudio, completionHandler: &*handler];
+        }
+        receiver
+    }
+}
+
+// Other platforms don't gate microphone access per app in a way we can query
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use super::MicrophonePermission;
+    use crate::error::Result;
+
+    pub fn status() -> MicrophonePermission {
+        MicrophonePermission::Granted
+    }
+
+    pub async fn request() -> Result<MicrophonePermission> {
+        Ok(MicrophonePermission::Granted)
+    }
+}
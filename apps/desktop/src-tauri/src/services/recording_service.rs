@@ -1,40 +1,87 @@
-use chrono::Utc;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use tauri::{Manager, State, AppHandle};
 use uuid::Uuid;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use serde_json;
-use crate::{AppState, Recording, RecordingState, RecordingStatus, PlaybackState};
-use crate::audio_system::AudioCommand;
+use crate::{format_duration, AppState, DeleteOutcome, Note, Recording, RecordingCountdown, RecordingDeduplicated, RecordingDetail, RecordingInfo, RecordingMatch, RecordingSource, RecordingState, RecordingStatus, RecordingsPruned, PlaybackInfo, PlaybackState, RecordingsAudit, RecordingsPage, SearchSnippet, SortOrder};
+use crate::audio_system::{self, AudioCommand, PlaybackOptions, RecorderState};
 use crate::events::EventEmitter;
-use crate::path_manager::AppPaths;
+use crate::path_manager::{AppPaths, RecordingLayout};
 use crate::constants::*;
-use crate::error::{AppError, Result};
+use crate::error::{AppError, ErrorPayload, Result};
 use tracing::{info, warn, debug};
-use super::audio_converter::AudioConverter;
-use super::transcription_service::TranscriptionService;
+use super::audio_converter::{AudioConverter, AudioFormat, ConversionSettings};
+use super::transcription_service::{TranscriptionService, TranscriptionResponse};
+use super::waveform::Waveform;
+use super::archive::Archive;
+use super::upload_service::UploadService;
 use crate::app_config::AppConfig;
+use crate::meeting_detector::MeetingApp;
 
-// Helper function to get the app's recordings directory
+// Helper function to get the app's effective recordings directory
 pub fn get_recordings_directory(app_handle: &AppHandle) -> Result<PathBuf> {
-    let app_data_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let recordings_dir = app_data_dir.join("recordings");
-    std::fs::create_dir_all(&recordings_dir)
-        .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
-    
-    Ok(recordings_dir)
+    let paths = AppPaths::new(app_handle)?;
+    Ok(paths.recordings_dir().clone())
 }
 
-// Helper function to get the full path to a recording file
+// Helper function to get the full path to a recording file; `filename` may include a subfolder
 pub fn get_recording_path(app_handle: &AppHandle, filename: &str) -> Result<PathBuf> {
     let recordings_dir = get_recordings_directory(app_handle)?;
-    Ok(recordings_dir.join(filename))
+    AppPaths::resolve_recording_path(&recordings_dir, filename)
+}
+
+// Remove the subfolder a deleted recording lived in once nothing else is left in it
+fn remove_empty_subfolder(recordings_dir: &Path, file_path: &Path) {
+    if let Some(parent) = file_path.parent().filter(|parent| *parent != recordings_dir) {
+        // Fails harmlessly while the folder still has files
+        let _ = std::fs::remove_dir(parent);
+    }
 }
 
 
-// Save recordings metadata to disk
+// Save recordings metadata to disk. Writes are debounced: saves within `METADATA_FLUSH_DELAY_MS`
+// are coalesced into one write of the latest list. The in-memory state stays authoritative meanwhile.
 pub fn save_recordings_metadata(app_handle: &AppHandle, recordings: &[Recording]) -> Result<()> {
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return write_recordings_metadata(app_handle, recordings);
+    };
+    
+    *state.pending_metadata.recordings.lock().unwrap() = Some(recordings.to_vec());
+    if !state.pending_metadata.flush_scheduled.swap(true, Ordering::AcqRel) {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(METADATA_FLUSH_DELAY_MS)).await;
+            // The list stays queued for the next flush; let the UI warn that changes aren't saved
+            if let Err(e) = flush_recordings_metadata(&app_handle) {
+                warn!("Failed to save recordings metadata: {}", e);
+                EventEmitter::recording_error(&app_handle, &ErrorPayload::from(e));
+            }
+        });
+    }
+    
+    // Keep the tray's recent recordings submenu current
+    crate::tray::schedule_tray_refresh(app_handle);
+    Ok(())
+}
+
+// Write queued recordings metadata now, e.g. before exiting or reading the file back.
+// A list that fails to write is queued again, so a later flush retries it.
+pub fn flush_recordings_metadata(app_handle: &AppHandle) -> Result<()> {
+    let Some(state) = app_handle.try_state::<AppState>() else { return Ok(()) };
+    state.pending_metadata.flush_scheduled.store(false, Ordering::Release);
+    
+    let mut pending = state.pending_metadata.recordings.lock().unwrap();
+    let Some(recordings) = pending.take() else { return Ok(()) };
+    let result = write_recordings_metadata(app_handle, &recordings);
+    if result.is_err() {
+        *pending = Some(recordings);
+    }
+    result
+}
+
+fn write_recordings_metadata(app_handle: &AppHandle, recordings: &[Recording]) -> Result<()> {
     let paths = AppPaths::new(app_handle)?;
     let metadata_path = paths.metadata_file();
     let json_data = serde_json::to_string_pretty(recordings)?;
@@ -44,15 +91,38 @@ pub fn save_recordings_metadata(app_handle: &AppHandle, recordings: &[Recording]
         debug!("Saving recording: {} ({})", recording.filename, recording.timestamp);
     }
     
-    std::fs::write(metadata_path, json_data)
+    write_file_atomically(metadata_path, json_data.as_bytes())
         .map_err(|e| AppError::Recording(format!("Failed to write metadata file: {}", e)))?;
     
     info!("Successfully saved recordings metadata");
     Ok(())
 }
 
+// Write to a temporary file in the same directory, then rename it over the target.
+// The rename is atomic on the same volume, so an interrupted write never truncates the original.
+fn write_file_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut temp_name = path.file_name().map(|name| name.to_os_string()).unwrap_or_default();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    })();
+    
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
 // Load recordings metadata from disk
 pub fn load_recordings_metadata(app_handle: &AppHandle) -> Result<Vec<Recording>> {
+    // Don't read back a file that's missing recent changes
+    flush_recordings_metadata(app_handle)?;
+    
     let paths = AppPaths::new(app_handle)?;
     let metadata_path = paths.metadata_file();
     
@@ -67,294 +137,2106 @@ pub fn load_recordings_metadata(app_handle: &AppHandle) -> Result<Vec<Recording>
     let json_data = std::fs::read_to_string(metadata_path)
         .map_err(|e| AppError::Recording(format!("Failed to read metadata file: {}", e)))?;
     
-    let recordings: Vec<Recording> = serde_json::from_str(&json_data)?;
+    let recordings: Vec<Recording> = match serde_json::from_str(&json_data) {
+        Ok(recordings) => recordings,
+        Err(e) => {
+            warn!("Recordings metadata is corrupted ({}), rebuilding from recording files", e);
+            let recordings = recover_corrupted_metadata(&paths)?;
+            if let Err(e) = save_recordings_metadata(app_handle, &recordings) {
+                warn!("Failed to save rebuilt recordings metadata: {}", e);
+            }
+            recordings
+        }
+    };
     
     // Filter out recordings where the actual file no longer exists
     let mut valid_recordings = Vec::new();
-    for recording in recordings {
+    let mut backfilled = false;
+    // Looked up on first use, as only legacy recordings need it
+    let mut ffmpeg_path: Option<Option<PathBuf>> = None;
+    for mut recording in recordings {
         let file_path = get_recording_path(app_handle, &recording.filename)?;
-        if file_path.exists() {
+        if let Ok(metadata) = std::fs::metadata(&file_path) {
+            // Older metadata predates file sizes
+            if recording.file_size_bytes == 0 && metadata.len() > 0 {
+                recording.file_size_bytes = metadata.len();
+                backfilled = true;
+            }
+            // ...and millisecond durations
+            if recording.duration_ms == 0 {
+                let ffmpeg_path = ffmpeg_path.get_or_insert_with(|| AudioConverter::find_ffmpeg(app_handle).ok());
+                if let Some(duration_ms) = AudioConverter::probe_duration_ms(&file_path, ffmpeg_path.as_deref()).filter(|ms| *ms > 0) {
+                    recording.duration_ms = duration_ms;
+                    recording.duration = format_duration(duration_ms);
+                    backfilled = true;
+                }
+            }
             valid_recordings.push(recording);
         }
     }
     
-    // Sort by timestamp (most recent first) and limit to MAX_RECENT_RECORDINGS
-    valid_recordings.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-    if valid_recordings.len() > MAX_RECENT_RECORDINGS {
-        valid_recordings.truncate(MAX_RECENT_RECORDINGS);
+    if backfilled {
+        if let Err(e) = save_recordings_metadata(app_handle, &valid_recordings) {
+            warn!("Failed to save backfilled recording details: {}", e);
+        }
     }
     
+    // Sort by timestamp (most recent first); all entries are kept, display limits apply on read
+    valid_recordings.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    
     Ok(valid_recordings)
 }
 
-pub async fn start_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<()> {
-    let start_time = Utc::now();
-    let file_name = format!("recording_{}.wav", start_time.format("%Y%m%d_%H%M%S"));
+// Move a corrupted recordings.json aside and rebuild a best-effort list from the files on disk
+fn recover_corrupted_metadata(paths: &AppPaths) -> Result<Vec<Recording>> {
+    let metadata_path = paths.metadata_file();
+    let mut backup_name = metadata_path.file_name().map(|name| name.to_os_string()).unwrap_or_default();
+    backup_name.push(format!(".corrupt-{}", Utc::now().format("%Y%m%d_%H%M%S")));
+    let backup_path = metadata_path.with_file_name(backup_name);
     
-    // Get app-specific recordings directory
-    let paths = AppPaths::new(&app_handle)?;
-    let file_path = paths.recording_path(&file_name);
+    std::fs::rename(metadata_path, &backup_path)
+        .map_err(|e| AppError::Recording(format!("Failed to back up corrupted metadata file: {}", e)))?;
+    info!("Moved corrupted metadata to: {}", backup_path.display());
+    
+    let mut recordings = Vec::new();
+    for path in list_recordings_dir_files(paths.recordings_dir())? {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let is_audio = matches!(extension, Some("opus") | Some("mp3") | Some("wav"));
+        // A WAV next to a converted file with the same name is a leftover (or kept original) from conversion
+        let converted_exists = path.with_extension("opus").exists() || path.with_extension("mp3").exists();
+        if !is_audio || (extension == Some("wav") && converted_exists) {
+            continue;
+        }
+        let Some(filename) = AppPaths::relative_recording_name(paths.recordings_dir(), &path) else { continue };
+        let original_wav = path.with_extension("wav");
+        let original_filename = (extension != Some("wav") && original_wav.exists())
+            .then(|| AppPaths::relative_recording_name(paths.recordings_dir(), &original_wav))
+            .flatten();
+        
+        let timestamp = parse_recording_timestamp(&filename)
+            .or_else(|| std::fs::metadata(&path).and_then(|m| m.modified()).ok().map(DateTime::<Utc>::from))
+            .unwrap_or_else(Utc::now);
+        
+        recordings.push(Recording {
+            id: Uuid::new_v4().to_string(),
+            filename,
+            duration: format_duration(0), // Unknown without decoding the file
+            duration_ms: 0,
+            file_size_bytes: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            timestamp,
+            status: RecordingStatus::Local,
+            title: None,
+            has_transcript: TranscriptionService::transcript_path(&path).exists(),
+            dropped_samples: 0,
+            needs_conversion: AudioFormat::from_path(&path).is_none(),
+            original_filename,
+            content_hash: None,
+            notes: Vec::new(),
+        });
+    }
+    
+    info!("Rebuilt metadata for {} recordings", recordings.len());
+    Ok(recordings)
+}
 
-    // Update recording state
+// Count down before recording, emitting a tick per second; the state stays Idle throughout
+async fn run_recording_countdown(state: &State<'_, AppState>, app_handle: &AppHandle, delay_secs: u32) -> Result<()> {
+    if !matches!(*state.recording_state.lock().unwrap(), RecordingState::Idle) {
+        return Err(AppError::Recording("Already recording".to_string()));
+    }
+    
+    let countdown_id = Uuid::new_v4().to_string();
     {
-        let mut recording_state = state.recording_state.lock().unwrap();
-        *recording_state = RecordingState::Recording { 
-            start_time,
-            file_path: file_path.clone(),
+        let mut countdown = state.recording_countdown.lock().unwrap();
+        if countdown.is_some() {
+            return Err(AppError::Recording("Recording countdown already in progress".to_string()));
+        }
+        *countdown = Some(countdown_id.clone());
+    }
+    
+    for remaining_secs in (1..=delay_secs).rev() {
+        EventEmitter::recording_countdown(app_handle, &RecordingCountdown { remaining_secs, cancelled: false });
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        
+        // Stop/toggle during the countdown clears (or replaces) the ID
+        if state.recording_countdown.lock().unwrap().as_deref() != Some(countdown_id.as_str()) {
+            return Err(AppError::Recording("Recording countdown was cancelled".to_string()));
+        }
+    }
+    
+    state.recording_countdown.lock().unwrap().take();
+    Ok(())
+}
+
+// Cancel a pending recording countdown; returns false if none was running
+pub fn cancel_recording_countdown(state: &State<'_, AppState>, app_handle: &AppHandle) -> bool {
+    if state.recording_countdown.lock().unwrap().take().is_none() {
+        return false;
+    }
+    
+    info!("Recording countdown cancelled");
+    EventEmitter::recording_countdown(app_handle, &RecordingCountdown { remaining_secs: 0, cancelled: true });
+    true
+}
+
+// Expand a filename template (see `AppConfig::filename_template`) into a file stem
+//
+// Returns None if the template uses a placeholder without a value or an unknown placeholder,
+// or if nothing usable is left after sanitizing.
+fn expand_filename_template(
+    template: &str,
+    start_time: DateTime<Utc>,
+    app: Option<&str>,
+    title: Option<&str>,
+    counter: u32,
+) -> Option<String> {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let close = rest[open..].find('}')? + open;
+        let value = match &rest[open + 1..close] {
+            "date" => start_time.format("%Y%m%d").to_string(),
+            "time" => start_time.format("%H%M%S").to_string(),
+            "app" => app.filter(|app| !app.trim().is_empty())?.to_string(),
+            "title" => title.filter(|title| !title.trim().is_empty())?.to_string(),
+            "counter" => counter.to_string(),
+            _ => return None,
         };
+        expanded.push_str(&value);
+        rest = &rest[close + 1..];
     }
+    expanded.push_str(rest);
+    
+    let stem = sanitize_file_stem(&expanded);
+    (!stem.is_empty()).then_some(stem)
+}
 
-    // Start audio recording
-    {
-        let mut audio_recorder = state.audio_recorder.lock().unwrap();
-        
-        // Initialize audio system if not already done
-        if !audio_recorder.is_initialized() {
-            audio_recorder.initialize().map_err(|e| format!("Failed to initialize audio system: {}", e))?;
+// Make a file stem safe on all platforms: no path separators, reserved or control characters
+fn sanitize_file_stem(stem: &str) -> String {
+    let sanitized: String = stem.chars()
+        .map(|c| if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .take(MAX_FILENAME_STEM_CHARS)
+        .collect();
+    sanitized.trim_matches(|c: char| c.is_whitespace() || c == '.').to_string()
+}
+
+// Filename for a new WAV recording in `dir`, named by `template` with the default pattern as fallback
+fn recording_file_name(dir: &Path, template: &str, start_time: DateTime<Utc>, app: Option<&str>, title: Option<&str>) -> String {
+    let expand = |template: &str, counter| expand_filename_template(template, start_time, app, title, counter);
+    let template = match expand(template, 1) {
+        Some(_) => template,
+        None => {
+            warn!("Could not expand filename template '{}', using the default", template);
+            DEFAULT_FILENAME_TEMPLATE
         }
-        
-        audio_recorder.send_command(AudioCommand::StartRecording { 
-            file_path: file_path.clone() 
-        }).map_err(|e| format!("Failed to send start command: {}", e))?;
-        audio_recorder.set_current_file_path(Some(file_path));
-        audio_recorder.set_recording(true);
+    };
+    
+    if template.contains("{counter}") {
+        return (1..)
+            .filter_map(|counter| expand(template, counter))
+            .find(|stem| !recording_stem_in_use(dir, stem))
+            .map(|stem| format!("{}.wav", stem))
+            .expect("unbounded counter always yields a free name");
+    }
+    
+    let stem = expand(template, 1).unwrap_or_else(|| format!("recording_{}", start_time.format("%Y%m%d_%H%M%S")));
+    std::iter::once(stem.clone())
+        .chain((1..).map(|counter| format!("{} ({})", stem, counter)))
+        .find(|candidate| !recording_stem_in_use(dir, candidate))
+        .map(|stem| format!("{}.wav", stem))
+        .expect("unbounded counter always yields a free name")
+}
+
+// Whether any file of a recording named `stem` exists in `dir`. The WAV is deleted after
+// conversion, so the converted file and sidecars must be checked too or FFmpeg would overwrite them.
+fn recording_stem_in_use(dir: &Path, stem: &str) -> bool {
+    ["wav", "opus", "mp3", "m4a"].iter().any(|extension| {
+        let file_name = format!("{}.{}", stem, extension);
+        [String::new(), ".peaks.json".to_string(), ".transcript.json".to_string()]
+            .iter()
+            .any(|sidecar| dir.join(format!("{}{}", file_name, sidecar)).exists())
+    })
+}
+
+// Files in the recordings directory. Recordings sit at the top level or one folder down
+// (per-day or per-meeting layouts).
+fn list_recordings_dir_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| AppError::Recording(format!("Failed to scan recordings directory: {}", e)))?;
+    
+    let mut files = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+        if path.is_dir() {
+            if let Ok(nested) = std::fs::read_dir(&path) {
+                files.extend(nested.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file()));
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+// Whether a file name is one the app creates for a recording (audio, waveform cache or transcript)
+fn is_recording_artifact(file_name: &str) -> bool {
+    [".wav", ".opus", ".mp3", ".peaks.json", ".transcript.json"]
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+}
+
+// Whether an archive entry (relative to the recordings directory) is a recording file to import.
+// Anything else, including recordings.json, which is merged instead, is left in the archive.
+fn is_importable_entry(path: &Path) -> bool {
+    // Recordings sit at the top level or one folder down
+    path.components().count() <= 2
+        && path.file_name().and_then(|name| name.to_str()).is_some_and(is_recording_artifact)
+}
+
+// Subfolder of the recordings directory a new recording belongs in (None = top level)
+fn recording_subfolder(layout: RecordingLayout, start_time: DateTime<Utc>, app: Option<&str>, title: Option<&str>) -> Option<String> {
+    let day = start_time.format("%Y-%m-%d").to_string();
+    match layout {
+        RecordingLayout::Flat => None,
+        RecordingLayout::PerDay => Some(day),
+        RecordingLayout::PerMeeting => {
+            let label = match (app, title.filter(|title| !title.trim().is_empty())) {
+                (Some(app), Some(title)) => format!("{} - {}", app, title),
+                (Some(app), None) => app.to_string(),
+                (None, Some(title)) => title.to_string(),
+                (None, None) => return None,
+            };
+            Some(sanitize_file_stem(&format!("{} {}", day, label)))
+        }
+    }
+}
+
+// Parse the timestamp from a `recording_YYYYMMDD_HHMMSS.<ext>` filename
+fn parse_recording_timestamp(filename: &str) -> Option<DateTime<Utc>> {
+    let stem = Path::new(filename).file_stem()?.to_str()?;
+    let timestamp = stem.strip_prefix("recording_")?;
+    NaiveDateTime::parse_from_str(timestamp, "%Y%m%d_%H%M%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+// Start recording, after counting down `start_delay_secs` (None = the configured delay)
+pub async fn start_recording(state: State<'_, AppState>, app_handle: AppHandle, start_delay_secs: Option<u32>, source: RecordingSource) -> Result<()> {
+    let start_delay_secs = match start_delay_secs {
+        Some(secs) => secs,
+        None => AppConfig::load(&app_handle).await.unwrap_or_default().start_delay_secs,
+    };
+    if start_delay_secs > 0 {
+        run_recording_countdown(&state, &app_handle, start_delay_secs).await?;
+    }
+    
+    let start_time = Utc::now();
+
+    // Label the recording after the meeting in progress, if one was detected
+    let meeting_state = state.meeting_detector.get_state();
+    let (meeting_app, title) = if meeting_state.is_in_meeting {
+        (meeting_state.detected_app, meeting_state.meeting_title)
+    } else {
+        (None, None)
+    };
+    
+    // Get app-specific recordings directory (or the subfolder the layout puts this recording in)
+    // and name the file after the configured template
+    let paths = AppPaths::new(&app_handle)?;
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    let app_name = meeting_app.as_ref().map(MeetingApp::display_name);
+    let recording_dir = match recording_subfolder(config.recording_layout, start_time, app_name, title.as_deref()) {
+        Some(subfolder) => {
+            let dir = paths.recording_path(&subfolder);
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| AppError::Path(format!("Failed to create recording folder {}: {}", dir.display(), e)))?;
+            dir
+        }
+        None => paths.recordings_dir().clone(),
+    };
+    let file_name = recording_file_name(&recording_dir, &config.filename_template, start_time, app_name, title.as_deref());
+    let file_path = recording_dir.join(&file_name);
+
+    // Check for a microphone before updating the recording state, so an unplugged device
+    // fails here instead of leaving the UI stuck in `Recording`
+    let device_name = state.input_device.lock().unwrap().clone();
+    let lookup_name = device_name.clone();
+    let input_device = tokio::task::spawn_blocking(move || audio_system::resolve_input_device_name(lookup_name.as_deref()))
+        .await
+        .map_err(|e| AppError::Audio(format!("Failed to check input device: {}", e)))??;
+    state.capture_paused.store(false, Ordering::Relaxed);
+    *state.recording_state.lock().unwrap() = RecordingState::Recording {
+        start_time,
+        paused_ms: 0,
+        file_path: file_path.clone(),
+        title,
+        source,
+    };
+    info!("Recording from input device: {}", input_device);
+
+    // Start audio recording, then wait for the audio thread to confirm the input stream is running
+    let (started_sender, started_receiver) = tokio::sync::oneshot::channel();
+    let start_command = AudioCommand::StartRecording { 
+        file_path: file_path.clone(),
+        device_name,
+        app_handle: app_handle.clone(),
+        started: started_sender,
+    };
+    let start_result = match send_start_command(&state.audio_recorder, &file_path, start_command) {
+        Ok(()) => {
+            let start_timeout = tokio::time::Duration::from_millis(RECORDING_START_TIMEOUT_MS);
+            match tokio::time::timeout(start_timeout, started_receiver).await {
+                Ok(Ok(Ok(()))) => Ok(()),
+                Ok(Ok(Err(e))) => Err(AppError::Audio(e)),
+                Ok(Err(_)) => Err(AppError::Audio("Audio thread exited before starting the recording".to_string())),
+                Err(_) => Err(AppError::Audio("Timed out waiting for the audio stream to start".to_string())),
+            }
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = start_result {
+        abort_recording_start(&state.recording_state, &state.audio_recorder, &file_path);
+        EventEmitter::recording_state_changed(&app_handle);
+        return Err(e);
+    }
+
+    // Enforce the configured maximum recording duration, if any
+    if let Some(max_minutes) = AppConfig::load(&app_handle).await.ok().and_then(|c| c.max_recording_minutes) {
+        spawn_max_duration_watchdog(app_handle.clone(), start_time, max_minutes);
     }
 
-    // Update tray menu
+    // Update tray menu and icon
     let _ = crate::tray::update_tray_menu(&app_handle, true);
+    let _ = crate::tray::set_tray_recording(&app_handle, true);
 
     Ok(())
 }
 
-pub async fn pause_recording(state: State<'_, AppState>) -> Result<()> {
-    let mut recording_state = state.recording_state.lock().unwrap();
-    match *recording_state {
-        RecordingState::Recording { start_time, ref file_path } => {
-            let elapsed = (Utc::now() - start_time).num_seconds() as u64;
-            let file_path_clone = file_path.clone();
-            *recording_state = RecordingState::Paused { 
-                start_time, 
-                elapsed, 
-                file_path: file_path_clone,
+// Hand the start command to the audio thread, starting the thread first if needed
+fn send_start_command(audio_recorder: &std::sync::Mutex<RecorderState>, file_path: &Path, command: AudioCommand) -> Result<()> {
+    let mut audio_recorder = audio_recorder.lock().unwrap();
+    
+    // Initialize audio system if not already done
+    if !audio_recorder.is_initialized() {
+        audio_recorder.initialize()
+            .map_err(|e| AppError::Audio(format!("Failed to initialize audio system: {}", e)))?;
+    }
+    
+    audio_recorder.send_command(command)
+        .map_err(|e| AppError::Audio(format!("Failed to send start command: {}", e)))?;
+    audio_recorder.set_current_file_path(Some(file_path.to_path_buf()));
+    audio_recorder.set_recording(true);
+    Ok(())
+}
+
+// Undo a recording start that failed after the state switched to `Recording`
+fn abort_recording_start(recording_state: &std::sync::Mutex<RecordingState>, audio_recorder: &std::sync::Mutex<RecorderState>, file_path: &Path) {
+    *recording_state.lock().unwrap() = RecordingState::Idle;
+    {
+        let mut audio_recorder = audio_recorder.lock().unwrap();
+        // Stop a stream that may still come up after a timeout
+        let (finalized, _) = tokio::sync::oneshot::channel();
+        let _ = audio_recorder.send_command(AudioCommand::StopRecording { finalized });
+        audio_recorder.set_recording(false);
+        audio_recorder.set_current_file_path(None);
+    }
+    // Drop the header-only WAV left behind by a stream that never started
+    let _ = std::fs::remove_file(file_path);
+}
+
+// Stop the recording started at `start_time` once it reaches the maximum duration
+// Time spent paused doesn't count toward the maximum
+fn spawn_max_duration_watchdog(app_handle: AppHandle, start_time: DateTime<Utc>, max_minutes: u32) {
+    let max_ms = u64::from(max_minutes) * 60 * 1000;
+    tauri::async_runtime::spawn(async move {
+        let state = loop {
+            let Some(state) = app_handle.try_state::<AppState>() else { return };
+            
+            // Only stop if the same recording is still in progress
+            let remaining_ms = {
+                let recording_state = state.recording_state.lock().unwrap();
+                max_duration_remaining_ms(&recording_state, start_time, max_ms, Utc::now())
             };
-            Ok(())
+            match remaining_ms {
+                None => return,
+                Some(0) => break state,
+                Some(remaining_ms) => tokio::time::sleep(tokio::time::Duration::from_millis(remaining_ms)).await,
+            }
+        };
+        
+        warn!("Recording reached maximum duration of {} minutes, stopping", max_minutes);
+        match stop_recording(state, app_handle.clone()).await {
+            Ok(recording) => {
+                EventEmitter::recording_auto_stopped(&app_handle, &recording);
+                EventEmitter::recording_state_changed(&app_handle);
+            }
+            Err(e) => warn!("Failed to auto-stop recording: {}", e),
         }
-        _ => Err(AppError::Recording("Not currently recording".to_string())),
+    });
+}
+
+// Active time left before the recording started at `start_time` reaches `max_ms`,
+// or None once that recording is no longer in progress. Nothing is written while paused,
+// so active time also bounds the file size.
+fn max_duration_remaining_ms(recording_state: &RecordingState, start_time: DateTime<Utc>, max_ms: u64, now: DateTime<Utc>) -> Option<u64> {
+    match recording_state {
+        RecordingState::Recording { start_time: started, .. } |
+        RecordingState::Paused { start_time: started, .. } if *started == start_time => {
+            Some(max_ms.saturating_sub(recording_state.active_ms(now)))
+        }
+        _ => None,
     }
 }
 
+pub async fn pause_recording(state: State<'_, AppState>) -> Result<()> {
+    let mut recording_state = state.recording_state.lock().unwrap();
+    let paused = paused_state(&recording_state, Utc::now())
+        .ok_or_else(|| AppError::Recording("Not currently recording".to_string()))?;
+    // Stop writing audio too, so the file, its duration and the max-duration limit all cover active time only
+    state.capture_paused.store(true, Ordering::Relaxed);
+    *recording_state = paused;
+    Ok(())
+}
+
 pub async fn resume_recording(state: State<'_, AppState>) -> Result<()> {
     let mut recording_state = state.recording_state.lock().unwrap();
-    match *recording_state {
-        RecordingState::Paused { start_time, ref file_path, .. } => {
-            let file_path_clone = file_path.clone();
-            *recording_state = RecordingState::Recording { 
-                start_time, 
-                file_path: file_path_clone,
-            };
-            Ok(())
-        }
-        _ => Err(AppError::Recording("Recording is not paused".to_string())),
+    let resumed = resumed_state(&recording_state, Utc::now())
+        .ok_or_else(|| AppError::Recording("Recording is not paused".to_string()))?;
+    state.capture_paused.store(false, Ordering::Relaxed);
+    *recording_state = resumed;
+    Ok(())
+}
+
+// Pause `recording_state` at `now`, keeping the time recorded so far; None if not recording
+fn paused_state(recording_state: &RecordingState, now: DateTime<Utc>) -> Option<RecordingState> {
+    match recording_state {
+        RecordingState::Recording { start_time, file_path, title, source, .. } => Some(RecordingState::Paused {
+            start_time: *start_time,
+            elapsed_ms: recording_state.active_ms(now),
+            file_path: file_path.clone(),
+            title: title.clone(),
+            source: *source,
+        }),
+        _ => None,
+    }
+}
+
+// Resume `recording_state` at `now`, counting everything since the start that wasn't recorded
+// as paused; None if not paused
+fn resumed_state(recording_state: &RecordingState, now: DateTime<Utc>) -> Option<RecordingState> {
+    match recording_state {
+        RecordingState::Paused { start_time, elapsed_ms, file_path, title, source } => Some(RecordingState::Recording {
+            start_time: *start_time,
+            paused_ms: ((now - *start_time).num_milliseconds().max(0) as u64).saturating_sub(*elapsed_ms),
+            file_path: file_path.clone(),
+            title: title.clone(),
+            source: *source,
+        }),
+        _ => None,
     }
 }
 
 pub async fn stop_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Recording> {
-    let (start_time, file_path) = {
+    finish_recording(state, app_handle, false).await
+}
+
+// Finalize an in-progress recording and pending metadata before the app exits. Conversion and
+// transcription are skipped to exit promptly; the WAV is marked for conversion on the next launch.
+pub async fn shutdown(app_handle: AppHandle) {
+    let Some(state) = app_handle.try_state::<AppState>() else { return };
+    let is_recording = matches!(
+        *state.recording_state.lock().unwrap(),
+        RecordingState::Recording { .. } | RecordingState::Paused { .. }
+    );
+    if is_recording {
+        info!("Finalizing in-progress recording before exit");
+        match finish_recording(state, app_handle.clone(), true).await {
+            Ok(recording) => info!("Saved recording before exit: {}", recording.filename),
+            Err(e) => warn!("Failed to finalize recording before exit: {}", e),
+        }
+    }
+    
+    // Write metadata still waiting on the debounce
+    if let Err(e) = flush_recordings_metadata(&app_handle) {
+        warn!("Failed to save recordings metadata before exit: {}", e);
+    }
+}
+
+// Stop and save the recording, then trim, convert and transcribe it in the background unless
+// `defer_processing` is set
+async fn finish_recording(state: State<'_, AppState>, app_handle: AppHandle, defer_processing: bool) -> Result<Recording> {
+    let (active_ms, file_path, title) = {
         let mut recording_state = state.recording_state.lock().unwrap();
         
         match *recording_state {
-            RecordingState::Recording { start_time, ref file_path } |
-            RecordingState::Paused { start_time, ref file_path, .. } => {
+            RecordingState::Recording { ref file_path, ref title, .. } |
+            RecordingState::Paused { ref file_path, ref title, .. } => {
+                let active_ms = recording_state.active_ms(Utc::now());
                 let file_path_clone = file_path.clone();
+                let title_clone = title.clone();
                 *recording_state = RecordingState::Idle;
-                (start_time, file_path_clone)
+                state.capture_paused.store(false, Ordering::Relaxed);
+                (active_ms, file_path_clone, title_clone)
+            }
+            _ => {
+                drop(recording_state);
+                if cancel_recording_countdown(&state, &app_handle) {
+                    return Err(AppError::Recording("Recording countdown was cancelled".to_string()));
+                }
+                return Err(AppError::Recording("Not recording".to_string()));
             }
-            _ => return Err(AppError::Recording("Not recording".to_string())),
         }
     };
 
     // Stop audio recording
+    let (finalized_sender, finalized_receiver) = tokio::sync::oneshot::channel();
     {
         let mut audio_recorder = state.audio_recorder.lock().unwrap();
-        audio_recorder.send_command(AudioCommand::StopRecording).map_err(|e| format!("Failed to send stop command: {}", e))?;
+        audio_recorder.send_command(AudioCommand::StopRecording { finalized: finalized_sender })
+            .map_err(|e| format!("Failed to send stop command: {}", e))?;
         audio_recorder.set_recording(false);
         audio_recorder.set_current_file_path(None);
         
         // Clean up the recorder state to force reinitialization for next use
         audio_recorder.cleanup();
+        if let Err(e) = audio_recorder.resume_preroll() {
+            warn!("Failed to resume pre-roll: {}", e);
+        }
     }
 
     let end_time = Utc::now();
-    let duration_seconds = (end_time - start_time).num_seconds();
-    let duration = format!("{}:{:02}", duration_seconds / SECONDS_PER_MINUTE, duration_seconds % SECONDS_PER_MINUTE);
 
-    // Wait for WAV file to be fully written and finalized
+    // Wait for the audio thread to confirm the WAV file is fully written and finalized
     info!("Waiting for WAV file to be finalized...");
-    tokio::time::sleep(tokio::time::Duration::from_millis(AUDIO_FINALIZATION_DELAY_MS)).await;
-    
-    // Validate WAV file before conversion
-    let mut attempts = 0;
-    while attempts < WAV_READY_MAX_ATTEMPTS {
-        if let Ok(metadata) = std::fs::metadata(&file_path) {
-            if metadata.len() > WAV_HEADER_MIN_BYTES { // WAV header minimum bytes
-                break;
-            }
+    let finalize_timeout = tokio::time::Duration::from_millis(WAV_FINALIZE_TIMEOUT_MS);
+    let finalized = match tokio::time::timeout(finalize_timeout, finalized_receiver).await {
+        Ok(Ok(finalized)) => {
+            debug!("WAV file finalized");
+            finalized
         }
-        debug!("WAV file not ready, waiting... (attempt {})", attempts + 1);
-        tokio::time::sleep(tokio::time::Duration::from_millis(WAV_READY_CHECK_DELAY_MS)).await;
-        attempts += 1;
-    }
-
-    // Convert WAV to Opus for optimal storage and universal playability
-    let final_file_path = match AudioConverter::convert_wav_to_opus(&file_path, &app_handle).await {
-        Ok(opus_path) => {
-            // Log conversion statistics and use Opus as the primary file
-            if let Ok(info) = AudioConverter::get_conversion_info(&file_path, &opus_path) {
-                info!("Audio conversion successful: {}", info);
-            } else {
-                info!("Audio conversion successful: {}", opus_path.display());
-            }
-            opus_path
+        Ok(Err(_)) => {
+            warn!("Audio thread exited before confirming WAV finalization");
+            None
         }
-        Err(e) => {
-            warn!("Failed to convert audio to Opus: {}, keeping WAV file", e);
-            // Keep the original WAV file if conversion fails
-            file_path
+        Err(_) => {
+            warn!("Timed out waiting for WAV file to be finalized");
+            None
         }
     };
+    
+    // Prefer the sample count; wall-clock time over-counts when the device was slow to start
+    let duration_ms = finalized
+        .map(|finalized| finalized.duration_ms)
+        .unwrap_or(active_ms);
+    let dropped_samples = finalized.map_or(0, |finalized| finalized.dropped_samples);
+    
+    if std::fs::metadata(&file_path).map(|m| m.len() <= WAV_HEADER_MIN_BYTES).unwrap_or(true) {
+        warn!("WAV file contains no audio data: {}", file_path.display());
+    }
 
+    // Save the WAV right away so the recording is listed while it is trimmed, converted and
+    // transcribed in the background; `needs_conversion` is cleared once that succeeds
+    let recordings_dir = get_recordings_directory(&app_handle)?;
     let recording = Recording {
         id: Uuid::new_v4().to_string(),
-        filename: final_file_path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("recording.opus")
-            .to_string(),
-        duration,
+        filename: AppPaths::relative_recording_name(&recordings_dir, &file_path)
+            .unwrap_or_else(|| "recording.wav".to_string()),
+        duration: format_duration(duration_ms),
+        duration_ms,
+        file_size_bytes: std::fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0),
         timestamp: end_time,
-        status: RecordingStatus::Local,
+        status: if defer_processing { RecordingStatus::Local } else { RecordingStatus::Processing },
+        title,
+        has_transcript: false,
+        dropped_samples,
+        needs_conversion: true,
+        original_filename: None,
+        content_hash: None,
+        notes: Vec::new(),
     };
 
     // Add to recordings list and save metadata
     {
         let mut recordings = state.recordings.lock().unwrap();
         recordings.insert(0, recording.clone());
-        if recordings.len() > MAX_RECENT_RECORDINGS {
-            recordings.truncate(MAX_RECENT_RECORDINGS);
-        }
         
         // Save recordings metadata to disk
         if let Err(e) = save_recordings_metadata(&app_handle, &recordings) {
             eprintln!("Failed to save recordings metadata: {}", e);
         }
     }
-
-    // Automatically start transcription if Opus conversion was successful
-    if final_file_path.extension().and_then(|ext| ext.to_str()) == Some("opus") {
-        let recording_id = recording.id.clone();
+    
+    if !defer_processing {
         let app_handle_clone = app_handle.clone();
-        
-        // Spawn async task for auto-transcription
+        let recording_id = recording.id.clone();
         tauri::async_runtime::spawn(async move {
-            // Load config to get web app URL
-            let config = match AppConfig::load(&app_handle_clone).await {
-                Ok(config) => config,
-                Err(e) => {
-                    eprintln!("Failed to load config for auto-transcription: {}", e);
-                    EventEmitter::transcription_failed(&app_handle_clone, &recording_id, &format!("Config error: {}", e));
-                    return;
-                }
-            };
-            
-            let api_url = config.transcribe_endpoint();
-            let api_key = config.api_key.as_deref();
-            
-            println!("Auto-starting transcription for recording: {} -> {}", recording_id, api_url);
-            
-            // Emit transcription started event
-            EventEmitter::transcription_started(&app_handle_clone, &recording_id);
-            
-            match TranscriptionService::transcribe_audio_stream(
-                &final_file_path,
-                &api_url,
-                api_key
-            ).await {
-                Ok(response) => {
-                    println!("Auto-transcription completed for {}: {} words", 
-                            recording_id, response.word_count.unwrap_or(0));
-                    EventEmitter::transcription_success(&app_handle_clone, &recording_id, &response);
-                }
-                Err(e) => {
-                    eprintln!("Auto-transcription failed for {}: {}", recording_id, e);
-                    EventEmitter::transcription_failed(&app_handle_clone, &recording_id, &e);
-                }
-            }
+            process_recording(app_handle_clone, recording_id, file_path, duration_ms).await;
         });
     }
 
-    // Update tray menu
+    // Update tray menu and icon
     let _ = crate::tray::update_tray_menu(&app_handle, false);
+    let _ = crate::tray::set_tray_recording(&app_handle, false);
 
     Ok(recording)
 }
 
-pub async fn get_recent_recordings(state: State<'_, AppState>) -> Result<Vec<Recording>> {
-    let recordings = state.recordings.lock().unwrap();
-    Ok(recordings.clone())
-}
-
-// Load recordings from persistent storage and populate the state
-pub async fn load_recordings_from_disk(state: State<'_, AppState>, app_handle: AppHandle) -> Result<()> {
-    let recordings = load_recordings_metadata(&app_handle)?;
-    
-    println!("Loading {} recordings from disk", recordings.len());
-    for recording in &recordings {
-        println!("Loaded recording: {} ({})", recording.filename, recording.timestamp);
-    }
+// Trim and convert a just-stopped recording, then transcribe it if conversion succeeded. Its status
+// moves from `Processing` through `Transcribing` to `Local`, with an event at each step.
+async fn process_recording(app_handle: AppHandle, recording_id: String, file_path: PathBuf, mut duration_ms: u64) {
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
     
-    {
-        let mut state_recordings = state.recordings.lock().unwrap();
-        *state_recordings = recordings;
+    // Optionally remove dead air at the start and end before converting
+    if config.auto_trim_silence {
+        match AudioConverter::trim_silence(&file_path, config.silence_threshold_db, &app_handle).await {
+            Ok(trimmed_ms) => {
+                info!("Trimmed silence: {} ms -> {} ms", duration_ms, trimmed_ms);
+                duration_ms = trimmed_ms;
+            }
+            Err(e) => warn!("Failed to trim silence, keeping full recording: {}", e),
+        }
     }
-    
-    Ok(())
-}
-
-pub async fn get_recording_state(state: State<'_, AppState>) -> Result<String> {
-    let recording_state = state.recording_state.lock().unwrap();
-    let state_str = match *recording_state {
-        RecordingState::Idle => "idle",
-        RecordingState::Recording { .. } => "recording", 
-        RecordingState::Paused { .. } => "paused",
-    };
-    Ok(state_str.to_string())
-}
 
-pub async fn toggle_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String> {
-    let current_state = {
-        let recording_state = state.recording_state.lock().unwrap();
-        match *recording_state {
-            RecordingState::Idle => "idle".to_string(),
-            RecordingState::Recording { .. } => "recording".to_string(),
-            RecordingState::Paused { .. } => "paused".to_string(),
+    // Hash the captured audio before it is encoded, so identical captures hash alike regardless of the encoder
+    let content_hash = content_hash(&file_path).await;
+    
+    // Convert WAV to the configured format (Opus by default) for optimal storage and playability
+    let format = config.default_recording_format;
+    let conversion = convert_to_configured_format(&file_path, &config, &app_handle).await;
+    let needs_conversion = conversion.is_err();
+    let recordings_dir = match get_recordings_directory(&app_handle) {
+        Ok(dir) => dir,
+        Err(e) => {
+            warn!("Failed to process recording {}: {}", recording_id, e);
+            restore_recording_status(&app_handle, &recording_id, RecordingStatus::Processing, RecordingStatus::Local);
+            return;
         }
     };
-
-    match current_state.as_str() {
-        "idle" => {
-            start_recording(state, app_handle).await?;
-            Ok("Started recording".to_string())
-        }
-        "recording" | "paused" => {
-            stop_recording(state, app_handle).await?;
-            Ok("Stopped recording".to_string())
+    // With `keep_original_wav` the WAV survives a successful conversion and is recorded alongside it
+    let original_filename = (!needs_conversion && file_path.exists())
+        .then(|| AppPaths::relative_recording_name(&recordings_dir, &file_path))
+        .flatten();
+    let final_file_path = match conversion {
+        Ok(converted_path) => {
+            // Log conversion statistics and use the converted file as the primary file
+            if let Ok(info) = AudioConverter::get_conversion_info(&file_path, &converted_path) {
+                info!("Audio conversion successful: {}", info);
+            } else {
+                info!("Audio conversion successful: {}", converted_path.display());
+            }
+            converted_path
+        }
+        Err(e) => {
+            warn!("Failed to convert audio to {:?}: {}, keeping WAV file", format, e);
+            // Keep the original WAV file if conversion fails
+            file_path.clone()
+        }
+    };
+    
+    // Automatically start transcription if enabled and conversion was successful
+    let auto_transcribe = config.auto_transcribe && AudioFormat::from_path(&final_file_path).is_some();
+    let updated = {
+        let state = app_handle.state::<AppState>();
+        let mut recordings = state.recordings.lock().unwrap();
+        
+        // With `skip_duplicate_recordings`, drop the new recording if the same audio is already saved
+        let duplicate_of = content_hash.as_deref()
+            .filter(|_| config.skip_duplicate_recordings)
+            .and_then(|hash| find_duplicate(&recordings, &recording_id, hash));
+        if let Some(duplicate_of) = duplicate_of {
+            recordings.retain(|r| r.id != recording_id);
+            if let Err(e) = save_recordings_metadata(&app_handle, &recordings) {
+                eprintln!("Failed to save recordings metadata: {}", e);
+            }
+            drop(recordings);
+            let _ = std::fs::remove_file(&final_file_path);
+            let _ = std::fs::remove_file(&file_path);
+            info!("Discarded recording {} as a duplicate of {}", recording_id, duplicate_of);
+            EventEmitter::recording_deduplicated(&app_handle, &RecordingDeduplicated { recording_id, duplicate_of });
+            let _ = crate::tray::refresh_tray_menu(&app_handle);
+            return;
+        }
+        
+        let Some(recording) = recordings.iter_mut().find(|r| r.id == recording_id) else {
+            warn!("Recording {} was deleted while it was being processed", recording_id);
+            let _ = std::fs::remove_file(&final_file_path);
+            let _ = std::fs::remove_file(&file_path);
+            return;
+        };
+        if let Some(filename) = AppPaths::relative_recording_name(&recordings_dir, &final_file_path) {
+            recording.filename = filename;
+        }
+        recording.duration = format_duration(duration_ms);
+        recording.duration_ms = duration_ms;
+        recording.file_size_bytes = std::fs::metadata(&final_file_path).map(|m| m.len()).unwrap_or(0);
+        recording.needs_conversion = needs_conversion;
+        recording.original_filename = original_filename;
+        recording.content_hash = content_hash;
+        recording.status = if auto_transcribe { RecordingStatus::Transcribing } else { RecordingStatus::Local };
+        let updated = recording.clone();
+        
+        if let Err(e) = save_recordings_metadata(&app_handle, &recordings) {
+            eprintln!("Failed to save recordings metadata: {}", e);
+        }
+        updated
+    };
+    EventEmitter::recording_status_changed(&app_handle, &updated);
+    let _ = crate::tray::refresh_tray_menu(&app_handle);
+    
+    if auto_transcribe {
+        auto_transcribe_recording(&app_handle, &recording_id, &final_file_path).await;
+        restore_recording_status(&app_handle, &recording_id, RecordingStatus::Transcribing, RecordingStatus::Local);
+    }
+}
+
+// Transcribe a new recording with the configured backend, saving the transcript on success
+async fn auto_transcribe_recording(app_handle: &AppHandle, recording_id: &str, file_path: &Path) {
+    // Load config to get web app URL
+    let config = match AppConfig::load(app_handle).await {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load config for auto-transcription: {}", e);
+            EventEmitter::transcription_failed(app_handle, recording_id, &format!("Config error: {}", e));
+            return;
+        }
+    };
+    
+    let backend = match config.resolve_transcription_backend(config.transcription_backend) {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("Failed to resolve transcription backend: {}", e);
+            EventEmitter::transcription_failed(app_handle, recording_id, &e);
+            return;
+        }
+    };
+    
+    println!("Auto-starting transcription for recording: {} -> {:?}", recording_id, config.transcription_backend);
+    
+    // Emit transcription started event
+    EventEmitter::transcription_started(app_handle, recording_id);
+    
+    match TranscriptionService::transcribe(
+        file_path,
+        &backend,
+        config.default_language.as_deref(),
+        config.transcription_chunk_minutes,
+        recording_id,
+        app_handle
+    ).await {
+        Ok(response) => {
+            println!("Auto-transcription completed for {}: {} words", 
+                    recording_id, response.word_count.unwrap_or(0));
+            if let Err(e) = store_transcript(app_handle, recording_id, file_path, &response) {
+                eprintln!("Failed to save transcript for {}: {}", recording_id, e);
+            }
+            EventEmitter::transcription_success(app_handle, recording_id, &response);
+        }
+        Err(e) => {
+            eprintln!("Auto-transcription failed for {}: {}", recording_id, e);
+            EventEmitter::transcription_failed(app_handle, recording_id, &e);
+        }
+    }
+}
+
+// SHA-256 of a file's contents as lowercase hex
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// ID of a recording other than `recording_id` with the given content hash
+fn find_duplicate(recordings: &[Recording], recording_id: &str, hash: &str) -> Option<String> {
+    recordings.iter()
+        .find(|r| r.id != recording_id && r.content_hash.as_deref() == Some(hash))
+        .map(|r| r.id.clone())
+}
+
+// Hash a recording file on a blocking thread, logging failures
+async fn content_hash(path: &Path) -> Option<String> {
+    let owned_path = path.to_owned();
+    match tokio::task::spawn_blocking(move || hash_file(&owned_path)).await {
+        Ok(Ok(hash)) => Some(hash),
+        Ok(Err(e)) => {
+            warn!("Failed to hash {}: {}", path.display(), e);
+            None
+        }
+        Err(e) => {
+            warn!("Failed to spawn hashing task: {}", e);
+            None
+        }
+    }
+}
+
+// Change a recording's status, persist it and emit `recording-status-changed`,
+// returning the status it replaced (None if the recording no longer exists)
+pub fn set_recording_status(app_handle: &AppHandle, recording_id: &str, status: RecordingStatus) -> Option<RecordingStatus> {
+    update_recording_status(app_handle, recording_id, None, status)
+}
+
+// Move a recording from the transient `expected` status (e.g. `Transcribing`) to `status`, leaving
+// it alone if its status changed meanwhile, such as to `Uploaded` or `Failed` by an upload
+pub fn restore_recording_status(app_handle: &AppHandle, recording_id: &str, expected: RecordingStatus, status: RecordingStatus) {
+    update_recording_status(app_handle, recording_id, Some(expected), status);
+}
+
+fn update_recording_status(app_handle: &AppHandle, recording_id: &str, expected: Option<RecordingStatus>, status: RecordingStatus) -> Option<RecordingStatus> {
+    let state = app_handle.try_state::<AppState>()?;
+    let (previous, updated) = {
+        let mut recordings = state.recordings.lock().unwrap();
+        let (previous, updated) = replace_status(&mut recordings, recording_id, expected, status)?;
+        if let Err(e) = save_recordings_metadata(app_handle, &recordings) {
+            warn!("Failed to save status of recording {}: {}", recording_id, e);
+        }
+        (previous, updated)
+    };
+    EventEmitter::recording_status_changed(app_handle, &updated);
+    Some(previous)
+}
+
+// Set the status of a recording, if it exists and (when given) still has the `expected` status.
+// Returns the replaced status and the updated recording.
+fn replace_status(recordings: &mut [Recording], recording_id: &str, expected: Option<RecordingStatus>, status: RecordingStatus) -> Option<(RecordingStatus, Recording)> {
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .filter(|r| expected.as_ref().is_none_or(|expected| r.status == *expected))?;
+    let previous = std::mem::replace(&mut recording.status, status);
+    Some((previous, recording.clone()))
+}
+
+// Reject operations on a recording whose file is still being trimmed or converted in the background
+pub fn ensure_not_processing(recording: &Recording) -> Result<()> {
+    if recording.status == RecordingStatus::Processing {
+        return Err(AppError::Recording("Recording is still being processed; try again once it finishes".to_string()));
+    }
+    Ok(())
+}
+
+// Statuses left behind by processing or transcription that a previous run didn't finish
+fn reset_interrupted_statuses(recordings: &mut [Recording]) -> bool {
+    let mut changed = false;
+    for recording in recordings.iter_mut() {
+        match recording.status {
+            RecordingStatus::Processing => {
+                // The WAV may or may not have been converted before the app quit
+                recording.needs_conversion = AudioFormat::from_path(Path::new(&recording.filename)).is_none();
+                recording.status = RecordingStatus::Local;
+                changed = true;
+            }
+            RecordingStatus::Transcribing => {
+                recording.status = RecordingStatus::Local;
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    changed
+}
+
+// Stop the current recording and discard it: the WAV is deleted and nothing is saved, converted or transcribed
+pub async fn cancel_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<()> {
+    let file_path = {
+        let mut recording_state = state.recording_state.lock().unwrap();
+        match *recording_state {
+            RecordingState::Recording { ref file_path, .. } |
+            RecordingState::Paused { ref file_path, .. } => {
+                let file_path = file_path.clone();
+                *recording_state = RecordingState::Idle;
+                file_path
+            }
+            RecordingState::Idle => return Err(AppError::Recording("Not recording".to_string())),
+        }
+    };
+    
+    // Stop audio recording
+    let (finalized_sender, finalized_receiver) = tokio::sync::oneshot::channel();
+    {
+        let mut audio_recorder = state.audio_recorder.lock().unwrap();
+        audio_recorder.send_command(AudioCommand::StopRecording { finalized: finalized_sender })
+            .map_err(|e| format!("Failed to send stop command: {}", e))?;
+        audio_recorder.set_recording(false);
+        audio_recorder.set_current_file_path(None);
+        audio_recorder.cleanup();
+        if let Err(e) = audio_recorder.resume_preroll() {
+            warn!("Failed to resume pre-roll: {}", e);
+        }
+    }
+    
+    // Let the audio thread release the file before deleting it
+    let finalize_timeout = tokio::time::Duration::from_millis(WAV_FINALIZE_TIMEOUT_MS);
+    if tokio::time::timeout(finalize_timeout, finalized_receiver).await.is_err() {
+        warn!("Timed out waiting for WAV file to be finalized before discarding it");
+    }
+    
+    if let Err(e) = std::fs::remove_file(&file_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return Err(AppError::Recording(format!("Failed to delete cancelled recording: {}", e)));
+        }
+    }
+    info!("Discarded recording: {}", file_path.display());
+    
+    // Update tray menu and icon
+    let _ = crate::tray::update_tray_menu(&app_handle, false);
+    let _ = crate::tray::set_tray_recording(&app_handle, false);
+    EventEmitter::recording_state_changed(&app_handle);
+    
+    Ok(())
+}
+
+// Convert a finished WAV to the configured format, deleting the WAV on success
+async fn convert_to_configured_format(file_path: &Path, config: &AppConfig, app_handle: &AppHandle) -> std::result::Result<PathBuf, String> {
+    let settings = config.conversion_settings();
+    match config.default_recording_format {
+        AudioFormat::Opus => AudioConverter::convert_wav_to_opus(file_path, &settings, app_handle).await,
+        AudioFormat::Mp3 => {
+            let mp3_settings = ConversionSettings { bitrate_kbps: config.mp3_bitrate_kbps, ..settings };
+            AudioConverter::convert_wav_to_mp3(file_path, &mp3_settings, app_handle).await
+        }
+    }
+}
+
+// Retry converting a recording that was kept as WAV, updating its file and metadata on success
+pub async fn convert_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Recording> {
+    // Mark the recording as processing while converting, so a second conversion (or an edit
+    // guarded by `ensure_not_processing`) can't run on it at the same time
+    let (recording, previous_status) = {
+        let mut recordings = state.recordings.lock().unwrap();
+        claim_for_processing(&mut recordings, &recording_id)?
+    };
+    
+    let result = convert_claimed_recording(&state, &app_handle, recording).await;
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?;
+    recording.status = previous_status;
+    let recording = recording.clone();
+    save_recordings_metadata(&app_handle, &recordings)?;
+    result?;
+    
+    info!("Converted pending recording {} to {}", recording_id, recording.filename);
+    Ok(recording)
+}
+
+// Set a recording's status to `Processing`, returning it with the status it had before;
+// fails if it is already being processed
+fn claim_for_processing(recordings: &mut [Recording], recording_id: &str) -> Result<(Recording, RecordingStatus)> {
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?;
+    ensure_not_processing(recording)?;
+    let previous_status = recording.status.clone();
+    recording.status = RecordingStatus::Processing;
+    Ok((recording.clone(), previous_status))
+}
+
+// Convert a recording claimed by `claim_for_processing` and update its file in the metadata.
+// The status is left for the caller to restore.
+async fn convert_claimed_recording(state: &AppState, app_handle: &AppHandle, recording: Recording) -> Result<()> {
+    let recording_id = recording.id;
+    let wav_path = get_recording_path(app_handle, &recording.filename)?;
+    // Like new recordings, hash the WAV rather than the encoded file
+    let content_hash = match recording.content_hash.clone() {
+        Some(hash) => Some(hash),
+        None => content_hash(&wav_path).await,
+    };
+    let converted_path = if AudioFormat::from_path(&wav_path).is_some() {
+        // Already converted (e.g. by an earlier attempt whose metadata update was lost)
+        wav_path.clone()
+    } else {
+        let config = AppConfig::load(app_handle).await.unwrap_or_default();
+        convert_to_configured_format(&wav_path, &config, app_handle).await
+            .map_err(AppError::Recording)?
+    };
+    
+    // Transcripts are named after the audio file, so follow the new extension
+    let old_transcript = TranscriptionService::transcript_path(&wav_path);
+    if converted_path != wav_path && old_transcript.exists() {
+        if let Err(e) = std::fs::rename(&old_transcript, TranscriptionService::transcript_path(&converted_path)) {
+            warn!("Failed to move transcript for {}: {}", recording_id, e);
+        }
+    }
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?;
+    let recordings_dir = get_recordings_directory(app_handle)?;
+    if let Some(filename) = AppPaths::relative_recording_name(&recordings_dir, &converted_path) {
+        recording.filename = filename;
+    }
+    recording.file_size_bytes = std::fs::metadata(&converted_path).map(|m| m.len()).unwrap_or(0);
+    recording.content_hash = content_hash;
+    recording.needs_conversion = false;
+    if converted_path != wav_path && wav_path.exists() {
+        recording.original_filename = AppPaths::relative_recording_name(&recordings_dir, &wav_path);
+    }
+    Ok(())
+}
+
+// Retry every conversion left pending by an earlier failure or by quitting mid-processing (run once at startup)
+pub async fn retry_pending_conversions(app_handle: AppHandle) {
+    let state = app_handle.state::<AppState>();
+    if let Err(e) = load_recordings_from_disk(state.clone(), app_handle.clone()).await {
+        warn!("Failed to load recordings for pending conversions: {}", e);
+        return;
+    }
+    {
+        let mut recordings = state.recordings.lock().unwrap();
+        if reset_interrupted_statuses(&mut recordings) {
+            if let Err(e) = save_recordings_metadata(&app_handle, &recordings) {
+                warn!("Failed to save reset recording statuses: {}", e);
+            }
+        }
+    }
+    if !state.ffmpeg_available.load(Ordering::Relaxed) {
+        return;
+    }
+    
+    let pending: Vec<String> = state.recordings.lock().unwrap().iter()
+        .filter(|r| r.needs_conversion)
+        .map(|r| r.id.clone())
+        .collect();
+    for recording_id in pending {
+        if let Err(e) = convert_recording(state.clone(), app_handle.clone(), recording_id.clone()).await {
+            warn!("Pending conversion of {} failed again: {}", recording_id, e);
+        }
+    }
+}
+
+// Prune recordings past the configured retention window now and then every
+// `RETENTION_CHECK_INTERVAL_SECS`, for as long as the app runs
+pub async fn run_retention_sweeps(app_handle: AppHandle) {
+    let state = app_handle.state::<AppState>();
+    if let Err(e) = load_recordings_from_disk(state, app_handle.clone()).await {
+        warn!("Failed to load recordings for the retention sweep: {}", e);
+    }
+    
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(RETENTION_CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(e) = prune_expired_recordings(&app_handle).await {
+            warn!("Retention sweep failed: {}", e);
+        }
+    }
+}
+
+// Delete recordings older than `retention_days`, except the one being played, returning the deleted ids.
+// The recording in progress has no metadata entry yet, so it is never a candidate.
+pub async fn prune_expired_recordings(app_handle: &AppHandle) -> Result<Vec<String>> {
+    let config = AppConfig::load(app_handle).await.unwrap_or_default();
+    let Some(retention_days) = config.retention_days.filter(|days| *days > 0) else {
+        return Ok(Vec::new());
+    };
+    let state = app_handle.state::<AppState>();
+    
+    let playing_id = match &*state.playback_state.lock().unwrap() {
+        PlaybackState::Playing { recording_id, .. } |
+        PlaybackState::Paused { recording_id, .. } => Some(recording_id.clone()),
+        PlaybackState::Idle => None,
+    };
+    let expired = {
+        let recordings = state.recordings.lock().unwrap();
+        expired_recordings(&recordings, Utc::now(), retention_days, playing_id.as_deref())
+    };
+    if expired.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let sizes: std::collections::HashMap<String, u64> = expired.iter()
+        .map(|recording| (recording.id.clone(), recording.file_size_bytes))
+        .collect();
+    let ids = expired.into_iter().map(|recording| recording.id).collect();
+    let recording_ids: Vec<String> = delete_recordings(state, app_handle.clone(), ids).await?
+        .into_iter()
+        .filter(|outcome| outcome.deleted)
+        .map(|outcome| outcome.recording_id)
+        .collect();
+    
+    let summary = RecordingsPruned {
+        freed_bytes: recording_ids.iter().filter_map(|id| sizes.get(id)).sum(),
+        recording_ids,
+        retention_days,
+    };
+    info!("Retention sweep deleted {} recordings older than {} days", summary.recording_ids.len(), retention_days);
+    if !summary.recording_ids.is_empty() {
+        EventEmitter::recordings_pruned(app_handle, &summary);
+        let _ = crate::tray::refresh_tray_menu(app_handle);
+    }
+    Ok(summary.recording_ids)
+}
+
+// Recordings made more than `retention_days` before `now`, skipping `keep_id`
+fn expired_recordings(recordings: &[Recording], now: DateTime<Utc>, retention_days: u32, keep_id: Option<&str>) -> Vec<Recording> {
+    let cutoff = now - chrono::Duration::days(i64::from(retention_days));
+    recordings.iter()
+        .filter(|recording| recording.timestamp < cutoff && Some(recording.id.as_str()) != keep_id)
+        .cloned()
+        .collect()
+}
+
+// Set how many days recordings are kept before the retention sweep deletes them (None = forever)
+pub async fn set_retention_days(app_handle: AppHandle, days: Option<u32>) -> Result<()> {
+    if days == Some(0) {
+        return Err(AppError::Config("Retention must be at least one day".to_string()));
+    }
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.retention_days = days;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    info!("Recording retention set to {:?} days", days);
+    Ok(())
+}
+
+// Save a transcript next to its recording and flag the recording as transcribed
+pub fn store_transcript(app_handle: &AppHandle, recording_id: &str, file_path: &std::path::Path, transcript: &TranscriptionResponse) -> Result<()> {
+    TranscriptionService::save_transcript(file_path, transcript)
+        .map_err(AppError::Transcription)?;
+    
+    let Some(state) = app_handle.try_state::<AppState>() else { return Ok(()) };
+    let mut recordings = state.recordings.lock().unwrap();
+    if let Some(recording) = recordings.iter_mut().find(|r| r.id == recording_id) {
+        recording.has_transcript = true;
+        save_recordings_metadata(app_handle, &recordings)?;
+    }
+    
+    Ok(())
+}
+
+// Load the saved transcript for a recording, if it has one
+pub async fn get_transcript(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Option<TranscriptionResponse>> {
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    
+    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    TranscriptionService::load_transcript(&file_path)
+        .map_err(AppError::Transcription)
+}
+
+// Load a recording with its transcript, cached waveform peaks and notes for the review view
+pub async fn get_recording_detail(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<RecordingDetail> {
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    
+    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    // An unreadable transcript shouldn't keep the rest of the recording from showing
+    let transcript = TranscriptionService::load_transcript(&file_path).unwrap_or_else(|e| {
+        warn!("Failed to load transcript for {}: {}", recording.id, e);
+        None
+    });
+    let waveform_peaks = Waveform::cached_peaks(&file_path);
+    let notes = recording.notes.clone();
+    
+    Ok(RecordingDetail { recording, transcript, waveform_peaks, notes })
+}
+
+// Case-insensitive substring search over recording titles and saved transcripts, best matches first
+pub async fn search_recordings(state: State<'_, AppState>, app_handle: AppHandle, query: String) -> Result<Vec<RecordingMatch>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let query = lowercase_chars(query);
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let recordings = state.recordings.lock().unwrap().clone();
+    
+    let mut matches = Vec::new();
+    for recording in recordings {
+        let title = recording.title.as_deref().unwrap_or(&recording.filename);
+        let title_matched = count_matches(&lowercase_chars(title), &query) > 0;
+        
+        let mut snippets = Vec::new();
+        let mut transcript_hits = 0;
+        if recording.has_transcript {
+            let transcript = AppPaths::resolve_recording_path(&recordings_dir, &recording.filename)
+                .ok()
+                .and_then(|path| TranscriptionService::load_transcript(&path).ok().flatten());
+            if let Some(transcript) = transcript {
+                (transcript_hits, snippets) = transcript_snippets(&transcript, &query);
+            }
+        }
+        
+        if title_matched || transcript_hits > 0 {
+            let score = u32::from(title_matched) + transcript_hits as u32;
+            matches.push(RecordingMatch { recording, title_matched, snippets, score });
+        }
+    }
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then(b.recording.timestamp.cmp(&a.recording.timestamp)));
+    
+    Ok(matches)
+}
+
+// Lowercase one char at a time so indices line up with the original text
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect()
+}
+
+// Char positions of non-overlapping occurrences of `query` in `text` (both from `lowercase_chars`)
+fn match_positions(text: &[char], query: &[char]) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut start = 0;
+    while start + query.len() <= text.len() {
+        if text[start..start + query.len()] == *query {
+            positions.push(start);
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+    positions
+}
+
+fn count_matches(text: &[char], query: &[char]) -> usize {
+    match_positions(text, query).len()
+}
+
+// Count the matches in a transcript and cut snippets around them: whole segments when the
+// transcript is timed, otherwise a window of the plain text
+fn transcript_snippets(transcript: &TranscriptionResponse, query: &[char]) -> (usize, Vec<SearchSnippet>) {
+    let mut hits = 0;
+    let mut snippets = Vec::new();
+    
+    if let Some(segments) = transcript.segments.as_ref().filter(|segments| !segments.is_empty()) {
+        for segment in segments {
+            let count = count_matches(&lowercase_chars(&segment.text), query);
+            if count == 0 {
+                continue;
+            }
+            hits += count;
+            if snippets.len() < MAX_SEARCH_SNIPPETS {
+                snippets.push(SearchSnippet {
+                    text: segment.text.trim().to_string(),
+                    start: Some(segment.start),
+                    end: Some(segment.end),
+                    speaker: segment.speaker.clone(),
+                });
+            }
+        }
+        return (hits, snippets);
+    }
+    
+    let chars: Vec<char> = transcript.transcript.chars().collect();
+    let positions = match_positions(&lowercase_chars(&transcript.transcript), query);
+    for &position in positions.iter().take(MAX_SEARCH_SNIPPETS) {
+        let from = position.saturating_sub(SEARCH_SNIPPET_CONTEXT_CHARS);
+        let to = (position + query.len() + SEARCH_SNIPPET_CONTEXT_CHARS).min(chars.len());
+        let text: String = chars[from..to].iter().collect();
+        let prefix = if from > 0 { "…" } else { "" };
+        let suffix = if to < chars.len() { "…" } else { "" };
+        snippets.push(SearchSnippet {
+            text: format!("{}{}{}", prefix, text.trim(), suffix),
+            start: None,
+            end: None,
+            speaker: None,
+        });
+    }
+    (positions.len(), snippets)
+}
+
+// Most recent recordings, limited to the configured `max_visible_recordings` (0 = unlimited)
+pub async fn get_recent_recordings(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Vec<Recording>> {
+    let max_visible = AppConfig::load(&app_handle).await
+        .map(|config| config.max_visible_recordings)
+        .unwrap_or_default();
+    
+    let recordings = state.recordings.lock().unwrap();
+    let limit = if max_visible == 0 { recordings.len() } else { max_visible };
+    Ok(recordings.iter().take(limit).cloned().collect())
+}
+
+// A page of recordings sorted by timestamp, with the total count for paging controls;
+// like `get_recent_recordings`, only the configured `max_visible_recordings` are included
+pub async fn get_recordings_page(state: State<'_, AppState>, app_handle: AppHandle, offset: usize, limit: usize, sort: SortOrder) -> Result<RecordingsPage> {
+    let max_visible = AppConfig::load(&app_handle).await
+        .map(|config| config.max_visible_recordings)
+        .unwrap_or_default();
+    
+    let recordings = state.recordings.lock().unwrap();
+    Ok(RecordingsPage::from_recordings(&recordings, offset, limit, sort, max_visible))
+}
+
+// Load recordings from persistent storage and populate the state
+pub async fn load_recordings_from_disk(state: State<'_, AppState>, app_handle: AppHandle) -> Result<()> {
+    let recordings = load_recordings_metadata(&app_handle)?;
+    
+    println!("Loading {} recordings from disk", recordings.len());
+    for recording in &recordings {
+        println!("Loaded recording: {} ({})", recording.filename, recording.timestamp);
+    }
+    
+    {
+        let mut state_recordings = state.recordings.lock().unwrap();
+        *state_recordings = recordings;
+    }
+    let _ = crate::tray::refresh_tray_menu(&app_handle);
+    
+    Ok(())
+}
+
+pub async fn get_recording_state(state: State<'_, AppState>) -> Result<String> {
+    let recording_state = state.recording_state.lock().unwrap();
+    let state_str = match *recording_state {
+        RecordingState::Idle => "idle",
+        RecordingState::Recording { .. } => "recording", 
+        RecordingState::Paused { .. } => "paused",
+    };
+    Ok(state_str.to_string())
+}
+
+// Current recording state with elapsed time computed from the stored start time
+pub async fn get_recording_info(state: State<'_, AppState>) -> Result<RecordingInfo> {
+    let recording_state = state.recording_state.lock().unwrap();
+    Ok(RecordingInfo::from_state(&recording_state, Utc::now()))
+}
+
+pub async fn toggle_recording(state: State<'_, AppState>, app_handle: AppHandle) -> Result<String> {
+    let current_state = {
+        let recording_state = state.recording_state.lock().unwrap();
+        match *recording_state {
+            RecordingState::Idle => "idle".to_string(),
+            RecordingState::Recording { .. } => "recording".to_string(),
+            RecordingState::Paused { .. } => "paused".to_string(),
+        }
+    };
+
+    match current_state.as_str() {
+        "idle" if cancel_recording_countdown(&state, &app_handle) => {
+            Ok("Cancelled recording countdown".to_string())
+        }
+        "idle" => {
+            start_recording(state, app_handle, None, RecordingSource::Manual).await?;
+            Ok("Started recording".to_string())
+        }
+        "recording" | "paused" => {
+            stop_recording(state, app_handle).await?;
+            Ok("Stopped recording".to_string())
         }
         _ => Err(AppError::Recording("Unknown state".to_string()))
     }
 }
 
-// Playback functions
-pub async fn play_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<()> {
-    // Find the recording by ID
+// Playback functions
+pub async fn play_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, options: PlaybackOptions) -> Result<()> {
+    // Find the recording by ID
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    
+    // Get the full path to the recording file
+    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    
+    // Check if file exists
+    if !file_path.exists() {
+        return Err(AppError::Recording("Recording file not found".to_string()));
+    }
+    
+    // Update playback state
+    {
+        let mut playback_state = state.playback_state.lock().unwrap();
+        *playback_state = PlaybackState::Playing {
+            recording_id: recording.id.clone(),
+            filename: recording.filename.clone(),
+            start_time: Utc::now(),
+        };
+    }
+    
+    // Send playback command to audio system
+    {
+        let mut audio_recorder = state.audio_recorder.lock().unwrap();
+        
+        // Initialize audio system if not already done
+        if !audio_recorder.is_initialized() {
+            audio_recorder.initialize().map_err(|e| format!("Failed to initialize audio system: {}", e))?;
+        }
+        
+        audio_recorder.send_command(AudioCommand::StartPlayback { 
+            file_path: file_path.clone(),
+            device_name: state.output_device.lock().unwrap().clone(),
+            app_handle: app_handle.clone(),
+            playback_rate: state.playback_rate.clone(),
+            options,
+        }).map_err(|e| format!("Failed to send playback command: {}", e))?;
+    }
+    
+    let _ = crate::tray::refresh_tray_menu(&app_handle);
+    println!("Started playback of recording: {}", recording.filename);
+    Ok(())
+}
+
+pub async fn stop_playback(state: State<'_, AppState>) -> Result<()> {
+    // Update playback state
+    {
+        let mut playback_state = state.playback_state.lock().unwrap();
+        *playback_state = PlaybackState::Idle;
+    }
+    
+    // Send stop playback command to audio system
+    {
+        let audio_recorder = state.audio_recorder.lock().unwrap();
+        audio_recorder.send_command(AudioCommand::StopPlayback)
+            .map_err(|e| format!("Failed to send stop playback command: {}", e))?;
+    }
+    
+    println!("Stopped audio playback");
+    Ok(())
+}
+
+pub async fn pause_playback(state: State<'_, AppState>) -> Result<()> {
+    {
+        let mut playback_state = state.playback_state.lock().unwrap();
+        let (recording_id, filename) = match &*playback_state {
+            PlaybackState::Playing { recording_id, filename, .. } => (recording_id.clone(), filename.clone()),
+            _ => return Err(AppError::Playback("Nothing is currently playing".to_string())),
+        };
+        *playback_state = PlaybackState::Paused { recording_id, filename };
+    }
+    
+    {
+        let audio_recorder = state.audio_recorder.lock().unwrap();
+        audio_recorder.send_command(AudioCommand::PausePlayback)
+            .map_err(|e| format!("Failed to send pause playback command: {}", e))?;
+    }
+    
+    println!("Paused audio playback");
+    Ok(())
+}
+
+pub async fn resume_playback(state: State<'_, AppState>) -> Result<()> {
+    {
+        let mut playback_state = state.playback_state.lock().unwrap();
+        let (recording_id, filename) = match &*playback_state {
+            PlaybackState::Paused { recording_id, filename } => (recording_id.clone(), filename.clone()),
+            _ => return Err(AppError::Playback("Playback is not paused".to_string())),
+        };
+        *playback_state = PlaybackState::Playing {
+            recording_id,
+            filename,
+            start_time: Utc::now(),
+        };
+    }
+    
+    {
+        let audio_recorder = state.audio_recorder.lock().unwrap();
+        audio_recorder.send_command(AudioCommand::ResumePlayback)
+            .map_err(|e| format!("Failed to send resume playback command: {}", e))?;
+    }
+    
+    println!("Resumed audio playback");
+    Ok(())
+}
+
+// Change the playback rate; takes effect immediately, including for the current playback
+pub async fn set_playback_speed(state: State<'_, AppState>, rate: f32) -> Result<()> {
+    if !rate.is_finite() || !(MIN_PLAYBACK_RATE..=MAX_PLAYBACK_RATE).contains(&rate) {
+        return Err(AppError::Playback(format!(
+            "Playback speed must be between {} and {}", MIN_PLAYBACK_RATE, MAX_PLAYBACK_RATE
+        )));
+    }
+    
+    state.playback_rate.store(rate.to_bits(), Ordering::Relaxed);
+    println!("Playback speed set to {}x", rate);
+    Ok(())
+}
+
+// Change the playback volume (clamped to 0.0-1.0); takes effect immediately and is persisted
+pub async fn set_playback_volume(state: State<'_, AppState>, app_handle: AppHandle, volume: f32) -> Result<()> {
+    if !volume.is_finite() {
+        return Err(AppError::Playback("Playback volume must be a number".to_string()));
+    }
+    let volume = volume.clamp(0.0, 1.0);
+    state.playback_volume.store(volume.to_bits(), Ordering::Relaxed);
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.playback_volume = volume;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+pub async fn get_playback_state(state: State<'_, AppState>) -> Result<String> {
+    let playback_state = state.playback_state.lock().unwrap();
+    let state_str = match *playback_state {
+        PlaybackState::Idle => "idle",
+        PlaybackState::Playing { .. } => "playing",
+        PlaybackState::Paused { .. } => "paused",
+    };
+    Ok(state_str.to_string())
+}
+
+// Current playback with its position, or None when nothing is loaded
+pub async fn get_playback_info(state: State<'_, AppState>) -> Result<Option<PlaybackInfo>> {
+    let (recording_id, filename, playback) = match &*state.playback_state.lock().unwrap() {
+        PlaybackState::Idle => return Ok(None),
+        PlaybackState::Playing { recording_id, filename, .. } => (recording_id.clone(), filename.clone(), "playing"),
+        PlaybackState::Paused { recording_id, filename } => (recording_id.clone(), filename.clone(), "paused"),
+    };
+    
+    Ok(Some(PlaybackInfo {
+        recording_id,
+        filename,
+        state: playback.to_string(),
+        position_ms: state.playback_progress.position_ms(),
+        duration_ms: state.playback_progress.duration_ms(),
+    }))
+}
+
+// Deletion function
+pub async fn delete_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<()> {
+    // Find the recording by ID
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    
+    // Get the full path to the recording file
+    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    
+    // Delete the file if it exists
+    if file_path.exists() {
+        std::fs::remove_file(&file_path)
+            .map_err(|e| format!("Failed to delete recording file: {}", e))?;
+    }
+    
+    // Remove cached waveform peaks and the saved transcript alongside the recording
+    let _ = std::fs::remove_file(Waveform::peaks_path(&file_path));
+    let _ = std::fs::remove_file(TranscriptionService::transcript_path(&file_path));
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    remove_original_wav(&recordings_dir, &recording);
+    remove_empty_subfolder(&recordings_dir, &file_path);
+    
+    // Remove from recordings list and save metadata
+    {
+        let mut recordings = state.recordings.lock().unwrap();
+        recordings.retain(|r| r.id != recording_id);
+        
+        // Save updated recordings metadata to disk
+        if let Err(e) = save_recordings_metadata(&app_handle, &recordings) {
+            eprintln!("Failed to save recordings metadata: {}", e);
+        }
+    }
+    
+    // Stop playback if this recording is currently playing
+    let should_stop_playback = {
+        let playback_state = state.playback_state.lock().unwrap();
+        match &*playback_state {
+            PlaybackState::Playing { recording_id: playing_id, .. } |
+            PlaybackState::Paused { recording_id: playing_id, .. } => playing_id == &recording_id,
+            PlaybackState::Idle => false,
+        }
+    };
+    
+    if should_stop_playback {
+        stop_playback(state).await?;
+    }
+    
+    println!("Deleted recording: {}", recording.filename);
+    Ok(())
+}
+
+// Delete the WAV kept alongside a converted recording, if any
+fn remove_original_wav(recordings_dir: &Path, recording: &Recording) {
+    let Some(original) = &recording.original_filename else { return };
+    if let Ok(original_path) = AppPaths::resolve_recording_path(recordings_dir, original) {
+        if let Err(e) = std::fs::remove_file(&original_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to delete original WAV {}: {}", original_path.display(), e);
+            }
+        }
+        let _ = std::fs::remove_file(Waveform::peaks_path(&original_path));
+    }
+}
+
+// Total bytes used by all known recordings
+pub async fn get_total_storage_used(state: State<'_, AppState>) -> Result<u64> {
+    let recordings = state.recordings.lock().unwrap();
+    Ok(recordings.iter().map(|r| r.file_size_bytes).sum())
+}
+
+// Delete several recordings, rewriting metadata once and reporting the outcome per id
+pub async fn delete_recordings(state: State<'_, AppState>, app_handle: AppHandle, recording_ids: Vec<String>) -> Result<Vec<DeleteOutcome>> {
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let mut outcomes = Vec::with_capacity(recording_ids.len());
+    let mut removed_ids = Vec::new();
+    
+    {
+        let mut recordings = state.recordings.lock().unwrap();
+        
+        for recording_id in recording_ids {
+            let Some(recording) = recordings.iter().find(|r| r.id == recording_id) else {
+                outcomes.push(DeleteOutcome { recording_id, deleted: false, error: Some("Recording not found".to_string()) });
+                continue;
+            };
+            
+            let file_path = match AppPaths::resolve_recording_path(&recordings_dir, &recording.filename) {
+                Ok(file_path) => file_path,
+                Err(e) => {
+                    outcomes.push(DeleteOutcome { recording_id, deleted: false, error: Some(e.to_string()) });
+                    continue;
+                }
+            };
+            let error = if file_path.exists() {
+                match std::fs::remove_file(&file_path) {
+                    Ok(()) => None,
+                    Err(e) => {
+                        // Keep the entry so the user can retry
+                        outcomes.push(DeleteOutcome { recording_id, deleted: false, error: Some(format!("Failed to delete recording file: {}", e)) });
+                        continue;
+                    }
+                }
+            } else {
+                Some("Recording file was already missing".to_string())
+            };
+            
+            let _ = std::fs::remove_file(Waveform::peaks_path(&file_path));
+            let _ = std::fs::remove_file(TranscriptionService::transcript_path(&file_path));
+            remove_original_wav(&recordings_dir, recording);
+            remove_empty_subfolder(&recordings_dir, &file_path);
+            
+            removed_ids.push(recording_id.clone());
+            outcomes.push(DeleteOutcome { recording_id, deleted: true, error });
+        }
+        
+        if !removed_ids.is_empty() {
+            recordings.retain(|r| !removed_ids.contains(&r.id));
+            save_recordings_metadata(&app_handle, &recordings)?;
+        }
+    }
+    
+    // Stop playback if any deleted recording is currently playing
+    let should_stop_playback = {
+        let playback_state = state.playback_state.lock().unwrap();
+        match &*playback_state {
+            PlaybackState::Playing { recording_id: playing_id, .. } |
+            PlaybackState::Paused { recording_id: playing_id, .. } => removed_ids.contains(playing_id),
+            PlaybackState::Idle => false,
+        }
+    };
+    
+    if should_stop_playback {
+        stop_playback(state).await?;
+    }
+    
+    info!("Deleted {} of {} recordings", removed_ids.len(), outcomes.len());
+    Ok(outcomes)
+}
+
+// Set a recording's display title; a blank title falls back to the filename
+pub async fn rename_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, title: String) -> Result<Recording> {
+    let title = title.trim();
+    let title = (!title.is_empty()).then(|| title.to_string());
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?;
+    recording.title = title;
+    let updated = recording.clone();
+    
+    save_recordings_metadata(&app_handle, &recordings)?;
+    
+    info!("Renamed recording {} to {:?}", updated.filename, updated.title);
+    Ok(updated)
+}
+
+// Copy a recording's file (and transcript) to a new entry titled "<title> (copy)", listed after the original
+pub async fn duplicate_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Recording> {
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    ensure_not_processing(&recording)?;
+    
+    let source_path = get_recording_path(&app_handle, &recording.filename)?;
+    if !source_path.exists() {
+        return Err(AppError::Recording("Audio file not found".to_string()));
+    }
+    let copy_path = (1..)
+        .map(|attempt| source_path.with_file_name(copy_file_name(&source_path, attempt)))
+        .find(|path| !path.exists())
+        .expect("unbounded range always yields a free name");
+    std::fs::copy(&source_path, &copy_path)
+        .map_err(|e| AppError::Recording(format!("Failed to copy recording file: {}", e)))?;
+    
+    let has_transcript = recording.has_transcript
+        && std::fs::copy(TranscriptionService::transcript_path(&source_path), TranscriptionService::transcript_path(&copy_path)).is_ok();
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let title = recording.title.clone()
+        .unwrap_or_else(|| source_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("Recording").to_string());
+    let duplicate = Recording {
+        id: Uuid::new_v4().to_string(),
+        filename: AppPaths::relative_recording_name(&recordings_dir, &copy_path)
+            .ok_or_else(|| AppError::Recording("Copy is outside the recordings directory".to_string()))?,
+        title: Some(format!("{} (copy)", title)),
+        status: RecordingStatus::Local,
+        has_transcript,
+        // The kept WAV stays with the original
+        original_filename: None,
+        ..recording
+    };
+    
+    {
+        let mut recordings = state.recordings.lock().unwrap();
+        let position = recordings.iter().position(|r| r.id == recording_id).map_or(0, |index| index + 1);
+        recordings.insert(position, duplicate.clone());
+        save_recordings_metadata(&app_handle, &recordings)?;
+    }
+    let _ = crate::tray::refresh_tray_menu(&app_handle);
+    
+    info!("Duplicated recording {} as {}", recording_id, duplicate.filename);
+    Ok(duplicate)
+}
+
+// File name for the `attempt`-th copy of a recording: "name_copy.opus", then "name_copy2.opus", ...
+fn copy_file_name(source_path: &Path, attempt: u32) -> String {
+    let stem = source_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("recording");
+    let suffix = if attempt > 1 { attempt.to_string() } else { String::new() };
+    match source_path.extension().and_then(|ext| ext.to_str()) {
+        Some(extension) => format!("{}_copy{}.{}", stem, suffix, extension),
+        None => format!("{}_copy{}", stem, suffix),
+    }
+}
+
+// Add a timestamped note to a recording; notes are kept in timestamp order
+pub async fn add_note(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, timestamp_ms: u64, text: String) -> Result<Recording> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::Recording("Note text cannot be empty".to_string()));
+    }
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?;
+    recording.add_note(Note { timestamp_ms, text: text.to_string() });
+    let updated = recording.clone();
+    
+    save_recordings_metadata(&app_handle, &recordings)?;
+    Ok(updated)
+}
+
+// Delete the note at `index` (in timestamp order) from a recording
+pub async fn delete_note(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, index: usize) -> Result<Recording> {
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = recordings.iter_mut()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?;
+    if index >= recording.notes.len() {
+        return Err(AppError::Recording(format!("Note {} not found", index)));
+    }
+    recording.notes.remove(index);
+    let updated = recording.clone();
+    
+    save_recordings_metadata(&app_handle, &recordings)?;
+    Ok(updated)
+}
+
+// Generate (or load cached) waveform peaks for a recording
+pub async fn generate_waveform(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, buckets: usize) -> Result<Vec<f32>> {
+    let buckets = Waveform::bucket_count(buckets).map_err(AppError::Recording)?;
+    
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    
+    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    if !file_path.exists() {
+        return Err(AppError::Recording("Recording file not found".to_string()));
+    }
+    
+    Waveform::get_peaks(&file_path, buckets, &app_handle).await
+        .map_err(AppError::Conversion)
+}
+
+// Change where recordings are stored (None = app data directory) and reload the list from there
+pub async fn set_recordings_dir(state: State<'_, AppState>, app_handle: AppHandle, dir: Option<PathBuf>) -> Result<()> {
+    if let Some(ref dir) = dir {
+        AppPaths::validate_recordings_dir(dir)?;
+    }
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.recordings_dir = dir.clone();
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    
+    *state.recordings_dir.lock().unwrap() = dir;
+    
+    let recordings = load_recordings_metadata(&app_handle)?;
+    *state.recordings.lock().unwrap() = recordings;
+    
+    EventEmitter::recording_state_changed(&app_handle);
+    Ok(())
+}
+
+// Choose the format new recordings are converted to, plus the MP3 bitrate
+pub async fn set_recording_format(app_handle: AppHandle, format: AudioFormat, mp3_bitrate_kbps: Option<u32>) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    if let Some(bitrate) = mp3_bitrate_kbps {
+        if !(MIN_MP3_BITRATE_KBPS..=MAX_MP3_BITRATE_KBPS).contains(&bitrate) {
+            return Err(AppError::Config(format!(
+                "MP3 bitrate must be between {} and {} kbps", MIN_MP3_BITRATE_KBPS, MAX_MP3_BITRATE_KBPS
+            )));
+        }
+        config.mp3_bitrate_kbps = bitrate;
+    }
+    config.default_recording_format = format;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Set the bitrate, sample rate and channel count used when converting recordings
+pub async fn set_conversion_settings(app_handle: AppHandle, bitrate_kbps: u32, sample_rate: u32, channels: u16) -> Result<()> {
+    let settings = ConversionSettings { bitrate_kbps, sample_rate, channels, ..ConversionSettings::default() };
+    settings.validate().map_err(AppError::Config)?;
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.conversion_bitrate = settings.bitrate_kbps;
+    config.conversion_sample_rate = settings.sample_rate;
+    config.conversion_channels = settings.channels;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Set the filename template for new recordings; a blank template restores the default
+pub async fn set_filename_template(app_handle: AppHandle, template: String) -> Result<()> {
+    let template = match template.trim() {
+        "" => DEFAULT_FILENAME_TEMPLATE.to_string(),
+        template => template.to_string(),
+    };
+    
+    // Reject typos such as "{tite}" up front instead of silently falling back later
+    if expand_filename_template(&template, Utc::now(), Some("app"), Some("title"), 1).is_none() {
+        return Err(AppError::Config(format!(
+            "Invalid filename template '{}': supported placeholders are {{date}}, {{time}}, {{app}}, {{title}} and {{counter}}",
+            template
+        )));
+    }
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.filename_template = template;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Choose how new recordings are sorted into subfolders
+pub async fn set_recording_layout(app_handle: AppHandle, layout: RecordingLayout) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.recording_layout = layout;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    info!("Recording layout set to: {:?}", layout);
+    Ok(())
+}
+
+// Enable or disable loudness normalization during conversion
+pub async fn set_auto_normalize(app_handle: AppHandle, enabled: bool) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.auto_normalize = enabled;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Keep or delete the WAV after converting new recordings
+pub async fn set_keep_original_wav(app_handle: AppHandle, enabled: bool) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.keep_original_wav = enabled;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Stop the transcription running for a recording; the run then fails, emitting `transcription_failed`
+// with `TRANSCRIPTION_CANCELLED_MESSAGE` as the reason
+pub async fn cancel_transcription(state: State<'_, AppState>, recording_id: String) -> Result<()> {
+    let cancel = state.transcriptions.lock().unwrap().get(&recording_id).cloned()
+        .ok_or_else(|| AppError::Transcription("No transcription in progress for this recording".to_string()))?;
+    cancel.cancel();
+    info!("Cancelled transcription of {}", recording_id);
+    Ok(())
+}
+
+// Group the IDs of recordings with identical audio for cleanup, hashing recordings saved
+// before content hashes were recorded
+pub async fn find_duplicates(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Vec<Vec<String>>> {
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let unhashed: Vec<(String, PathBuf)> = state.recordings.lock().unwrap().iter()
+        // Files still being converted are hashed once processing finishes
+        .filter(|r| r.content_hash.is_none() && r.status != RecordingStatus::Processing)
+        .filter_map(|r| {
+            // Prefer a kept original WAV, which is what new recordings are hashed by
+            let path = r.original_filename.as_deref()
+                .and_then(|name| AppPaths::resolve_recording_path(&recordings_dir, name).ok())
+                .filter(|path| path.exists())
+                .or_else(|| AppPaths::resolve_recording_path(&recordings_dir, &r.filename).ok())?;
+            Some((r.id.clone(), path))
+        })
+        .filter(|(_, path)| path.exists())
+        .collect();
+    
+    let mut hashes = Vec::new();
+    for (recording_id, path) in unhashed {
+        if let Some(hash) = content_hash(&path).await {
+            hashes.push((recording_id, hash));
+        }
+    }
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    if !hashes.is_empty() {
+        for (recording_id, hash) in hashes {
+            if let Some(recording) = recordings.iter_mut().find(|r| r.id == recording_id) {
+                recording.content_hash = Some(hash);
+            }
+        }
+        save_recordings_metadata(&app_handle, &recordings)?;
+    }
+    Ok(group_duplicates(&recordings))
+}
+
+// IDs of recordings sharing a content hash, in list order; unique and unhashed recordings are left out
+fn group_duplicates(recordings: &[Recording]) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let mut group_by_hash = std::collections::HashMap::new();
+    for recording in recordings {
+        let Some(hash) = recording.content_hash.as_deref() else { continue };
+        let index = *group_by_hash.entry(hash).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[index].push(recording.id.clone());
+    }
+    groups.retain(|ids| ids.len() > 1);
+    groups
+}
+
+// Discard new recordings whose audio matches a saved recording, or keep them
+pub async fn set_skip_duplicate_recordings(app_handle: AppHandle, enabled: bool) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.skip_duplicate_recordings = enabled;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Turn automatic transcription of new recordings on or off
+pub async fn set_auto_transcribe(app_handle: AppHandle, enabled: bool) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.auto_transcribe = enabled;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Set the chunk length used to transcribe long recordings piecewise (0 = send the whole file)
+pub async fn set_transcription_chunk_minutes(app_handle: AppHandle, minutes: u32) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.transcription_chunk_minutes = minutes;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Enable or disable silence trimming, optionally changing the threshold (dBFS)
+pub async fn set_silence_trim(app_handle: AppHandle, enabled: bool, threshold_db: Option<f32>) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    if let Some(threshold_db) = threshold_db {
+        if !(-100.0..=0.0).contains(&threshold_db) {
+            return Err(AppError::Config("Silence threshold must be between -100 and 0 dB".to_string()));
+        }
+        config.silence_threshold_db = threshold_db;
+    }
+    config.auto_trim_silence = enabled;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Set the default countdown before manually started recordings (0 = start immediately)
+pub async fn set_start_delay(app_handle: AppHandle, seconds: u32) -> Result<()> {
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.start_delay_secs = seconds;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Set (or clear) the endpoint recordings are uploaded to
+pub async fn set_upload_endpoint(app_handle: AppHandle, endpoint: Option<String>) -> Result<()> {
+    let endpoint = match endpoint.as_deref().map(str::trim) {
+        Some(endpoint) if !endpoint.is_empty() => Some(AppConfig::normalize_upload_url(endpoint)?),
+        _ => None,
+    };
+    
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.upload_endpoint = endpoint;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Store (or clear) the key sent to the upload endpoint; uploads are unauthenticated without one
+pub async fn set_upload_api_key(app_handle: AppHandle, api_key: Option<String>) -> Result<()> {
+    let api_key = api_key.map(|key| key.trim().to_string()).filter(|key| !key.is_empty());
+    let mut config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    config.set_upload_api_key(api_key).map_err(AppError::Config)?;
+    config.save(&app_handle).await.map_err(AppError::Config)?;
+    Ok(())
+}
+
+// Upload a recording to the configured endpoint, recording the outcome in its status
+pub async fn upload_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<Recording> {
+    let recording = {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter()
+            .find(|r| r.id == recording_id)
+            .cloned()
+            .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
+    };
+    ensure_not_processing(&recording)?;
+    
+    let config = AppConfig::load(&app_handle).await.map_err(AppError::Config)?;
+    let upload_url = config.upload_endpoint.clone()
+        .ok_or_else(|| AppError::Config("Upload endpoint is not configured".to_string()))?;
+    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    
+    let result = UploadService::upload_file(&file_path, &upload_url, config.upload_api_key.as_deref(), &recording_id, &app_handle).await;
+    let status = if result.is_ok() { RecordingStatus::Uploaded } else { RecordingStatus::Failed };
+    
+    // Saves the metadata and emits `recording-status-changed` like every other transition
+    let updated = set_recording_status(&app_handle, &recording_id, status).and_then(|_| {
+        let recordings = state.recordings.lock().unwrap();
+        recordings.iter().find(|r| r.id == recording_id).cloned()
+    });
+    
+    match result {
+        Ok(()) => {
+            EventEmitter::upload_success(&app_handle, &recording_id);
+            // The recording may have been deleted while uploading
+            updated.ok_or_else(|| AppError::Recording("Recording not found".to_string()))
+        }
+        Err(e) => {
+            EventEmitter::upload_failed(&app_handle, &recording_id, &e);
+            Err(AppError::Upload(e))
+        }
+    }
+}
+
+// Copy a recording (and optionally its transcript) into a user-chosen directory
+pub async fn export_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, destination: PathBuf, include_transcript: bool) -> Result<PathBuf> {
     let recording = {
         let recordings = state.recordings.lock().unwrap();
         recordings.iter()
@@ -363,116 +2245,417 @@ pub async fn play_recording(state: State<'_, AppState>, app_handle: AppHandle, r
             .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
     };
     
-    // Get the full path to the recording file
-    let file_path = get_recording_path(&app_handle, &recording.filename)?;
+    if !destination.is_dir() {
+        return Err(AppError::Recording(format!("Export destination is not a directory: {}", destination.display())));
+    }
     
-    // Check if file exists
-    if !file_path.exists() {
+    let source_path = get_recording_path(&app_handle, &recording.filename)?;
+    if !source_path.exists() {
         return Err(AppError::Recording("Recording file not found".to_string()));
     }
     
-    // Update playback state
-    {
-        let mut playback_state = state.playback_state.lock().unwrap();
-        *playback_state = PlaybackState::Playing {
-            recording_id: recording.id.clone(),
-            filename: recording.filename.clone(),
-            start_time: Utc::now(),
-        };
-    }
+    let export_name = source_path.file_name().and_then(|n| n.to_str()).unwrap_or(&recording.filename);
+    let export_path = unique_export_path(&destination, export_name);
+    std::fs::copy(&source_path, &export_path)
+        .map_err(|e| AppError::Recording(format!("Failed to export recording: {}", e)))?;
     
-    // Send playback command to audio system
-    {
-        let mut audio_recorder = state.audio_recorder.lock().unwrap();
-        
-        // Initialize audio system if not already done
-        if !audio_recorder.is_initialized() {
-            audio_recorder.initialize().map_err(|e| format!("Failed to initialize audio system: {}", e))?;
+    if include_transcript {
+        let transcript_path = TranscriptionService::transcript_path(&source_path);
+        if transcript_path.exists() {
+            // Name the transcript after the exported file so the pair stays together
+            std::fs::copy(&transcript_path, TranscriptionService::transcript_path(&export_path))
+                .map_err(|e| AppError::Recording(format!("Failed to export transcript: {}", e)))?;
         }
-        
-        audio_recorder.send_command(AudioCommand::StartPlayback { 
-            file_path: file_path.clone(),
-            app_handle: app_handle.clone()
-        }).map_err(|e| format!("Failed to send playback command: {}", e))?;
     }
     
-    println!("Started playback of recording: {}", recording.filename);
-    Ok(())
+    info!("Exported recording to: {}", export_path.display());
+    Ok(export_path)
 }
 
-pub async fn stop_playback(state: State<'_, AppState>) -> Result<()> {
-    // Update playback state
-    {
-        let mut playback_state = state.playback_state.lock().unwrap();
-        *playback_state = PlaybackState::Idle;
-    }
+// Zip the recordings directory (audio, recordings.json, transcripts and waveform peaks) into one archive.
+// `destination` is the archive path, or a directory to create a timestamped archive in.
+pub async fn export_all(app_handle: AppHandle, destination: PathBuf) -> Result<PathBuf> {
+    // The archive should contain the latest metadata
+    flush_recordings_metadata(&app_handle)?;
+    let recordings_dir = get_recordings_directory(&app_handle)?;
     
-    // Send stop playback command to audio system
-    {
-        let audio_recorder = state.audio_recorder.lock().unwrap();
-        audio_recorder.send_command(AudioCommand::StopPlayback)
-            .map_err(|e| format!("Failed to send stop playback command: {}", e))?;
-    }
+    let archive_path = if destination.is_dir() {
+        let filename = format!("recordings_backup_{}.zip", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+        unique_export_path(&destination, &filename)
+    } else {
+        destination
+    };
     
-    println!("Stopped audio playback");
-    Ok(())
+    let output_path = archive_path.clone();
+    let count = tokio::task::spawn_blocking(move || {
+        Archive::write(&recordings_dir, &output_path, |file_name| file_name == "recordings.json" || is_recording_artifact(file_name))
+    })
+        .await
+        .map_err(|e| AppError::Recording(format!("Export task failed: {}", e)))?
+        .map_err(AppError::Recording)?;
+    
+    info!("Exported {} files to: {}", count, archive_path.display());
+    Ok(archive_path)
 }
 
-pub async fn get_playback_state(state: State<'_, AppState>) -> Result<String> {
-    let playback_state = state.playback_state.lock().unwrap();
-    let state_str = match *playback_state {
-        PlaybackState::Idle => "idle",
-        PlaybackState::Playing { .. } => "playing",
+// Restore an archive created by `export_all`. Files already in the recordings directory are kept,
+// and recordings already in the library are not overwritten. Returns the number of recordings added.
+pub async fn import_archive(state: State<'_, AppState>, app_handle: AppHandle, source: PathBuf) -> Result<usize> {
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let archive_path = source.clone();
+    let (metadata, extracted) = tokio::task::spawn_blocking(move || {
+        let metadata = Archive::read_entry(&archive_path, "recordings.json")?
+            .ok_or_else(|| "Archive does not contain recordings.json".to_string())?;
+        // The archived metadata is merged below rather than written over the current file
+        let extracted = Archive::extract(&archive_path, &recordings_dir, is_importable_entry)?;
+        Ok::<_, String>((metadata, extracted))
+    })
+        .await
+        .map_err(|e| AppError::Recording(format!("Import task failed: {}", e)))?
+        .map_err(AppError::Recording)?;
+    
+    let mut imported: Vec<Recording> = serde_json::from_str(&metadata)
+        .map_err(|e| AppError::Recording(format!("Archive contains invalid recordings.json: {}", e)))?;
+    // Entries whose audio wasn't in the archive can't be played
+    imported.retain(|r| get_recording_path(&app_handle, &r.filename).map(|path| path.exists()).unwrap_or(false));
+    reset_interrupted_statuses(&mut imported);
+    
+    let added = {
+        let mut recordings = state.recordings.lock().unwrap();
+        let added = merge_imported_recordings(&mut recordings, imported);
+        if added > 0 {
+            save_recordings_metadata(&app_handle, &recordings)?;
+        }
+        added
     };
-    Ok(state_str.to_string())
+    
+    if added > 0 {
+        let _ = crate::tray::refresh_tray_menu(&app_handle);
+    }
+    info!("Imported {} recordings ({} files) from: {}", added, extracted.len(), source.display());
+    Ok(added)
 }
 
-// Deletion function
-pub async fn delete_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String) -> Result<()> {
-    // Find the recording by ID
-    let recording = {
+// Add imported recordings that aren't already in the library (by id or file), keeping the list
+// sorted newest first. Returns how many were added.
+fn merge_imported_recordings(recordings: &mut Vec<Recording>, imported: Vec<Recording>) -> usize {
+    let mut added = 0;
+    for recording in imported {
+        if recordings.iter().any(|r| r.id == recording.id || r.filename == recording.filename) {
+            continue;
+        }
+        recordings.push(recording);
+        added += 1;
+    }
+    if added > 0 {
+        recordings.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+    }
+    added
+}
+
+// Join recordings end to end (in the given order) into a new recording, optionally deleting the sources
+pub async fn merge_recordings(state: State<'_, AppState>, app_handle: AppHandle, recording_ids: Vec<String>, output_title: String, delete_sources: bool) -> Result<Recording> {
+    if recording_ids.len() < 2 {
+        return Err(AppError::Recording("Select at least two recordings to merge".to_string()));
+    }
+    
+    let sources: Vec<Recording> = {
+        let recordings = state.recordings.lock().unwrap();
+        recording_ids.iter()
+            .map(|id| recordings.iter()
+                .find(|r| &r.id == id)
+                .cloned()
+                .ok_or_else(|| AppError::Recording(format!("Recording not found: {}", id))))
+            .collect::<Result<_>>()?
+    };
+    sources.iter().try_for_each(ensure_not_processing)?;
+    
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let source_paths: Vec<PathBuf> = sources.iter()
+        .map(|r| AppPaths::resolve_recording_path(&recordings_dir, &r.filename))
+        .collect::<Result<_>>()?;
+    if let Some(missing) = source_paths.iter().find(|path| !path.exists()) {
+        return Err(AppError::Recording(format!("Recording file not found: {}", missing.display())));
+    }
+    let extension = check_mergeable(&source_paths, &app_handle).await?;
+    
+    let now = Utc::now();
+    let filename = format!("recording_{}.{}", now.format("%Y%m%d_%H%M%S"), extension);
+    let output_path = unique_export_path(&recordings_dir, &filename);
+    AudioConverter::concat_files(&source_paths, &output_path, &app_handle).await
+        .map_err(AppError::Recording)?;
+    
+    let duration_ms = sources.iter().map(|r| r.duration_ms).sum();
+    let title = output_title.trim();
+    let recording = Recording {
+        id: Uuid::new_v4().to_string(),
+        filename: output_path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&filename)
+            .to_string(),
+        duration: format_duration(duration_ms),
+        duration_ms,
+        file_size_bytes: std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0),
+        timestamp: sources.iter().map(|r| r.timestamp).max().unwrap_or(now),
+        status: RecordingStatus::Local,
+        title: (!title.is_empty()).then(|| title.to_string()),
+        has_transcript: false,
+        dropped_samples: sources.iter().map(|r| r.dropped_samples).sum(),
+        needs_conversion: AudioFormat::from_path(&output_path).is_none(),
+        original_filename: None,
+        content_hash: None,
+        notes: Vec::new(),
+    };
+    
+    {
+        let mut recordings = state.recordings.lock().unwrap();
+        recordings.insert(0, recording.clone());
+        save_recordings_metadata(&app_handle, &recordings)?;
+    }
+    info!("Merged {} recordings into {}", sources.len(), recording.filename);
+    
+    if delete_sources {
+        for outcome in delete_recordings(state, app_handle, recording_ids).await? {
+            if let Some(error) = outcome.error.filter(|_| !outcome.deleted) {
+                warn!("Failed to delete merged source {}: {}", outcome.recording_id, error);
+            }
+        }
+    }
+    
+    Ok(recording)
+}
+
+// Write the `start_seconds`..`end_seconds` range of a recording to a new recording, leaving the original intact
+pub async fn trim_recording(state: State<'_, AppState>, app_handle: AppHandle, recording_id: String, start_seconds: f64, end_seconds: f64, output_title: String) -> Result<Recording> {
+    let source = {
         let recordings = state.recordings.lock().unwrap();
         recordings.iter()
             .find(|r| r.id == recording_id)
             .cloned()
             .ok_or_else(|| AppError::Recording("Recording not found".to_string()))?
     };
+    ensure_not_processing(&source)?;
+    validate_trim_range(start_seconds, end_seconds, source.duration_ms)?;
     
-    // Get the full path to the recording file
-    let file_path = get_recording_path(&app_handle, &recording.filename)?;
-    
-    // Delete the file if it exists
-    if file_path.exists() {
-        std::fs::remove_file(&file_path)
-            .map_err(|e| format!("Failed to delete recording file: {}", e))?;
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let source_path = AppPaths::resolve_recording_path(&recordings_dir, &source.filename)?;
+    if !source_path.exists() {
+        return Err(AppError::Recording(format!("Recording file not found: {}", source_path.display())));
     }
+    let extension = source_path.extension().and_then(|e| e.to_str()).unwrap_or("opus").to_lowercase();
+    
+    let now = Utc::now();
+    let filename = format!("recording_{}.{}", now.format("%Y%m%d_%H%M%S"), extension);
+    let output_path = unique_export_path(&recordings_dir, &filename);
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    AudioConverter::extract_range(&source_path, &output_path, start_seconds, end_seconds, &config.conversion_settings(), &app_handle).await
+        .map_err(AppError::Recording)?;
+    
+    let start_ms = (start_seconds * 1000.0).round() as u64;
+    let end_ms = (end_seconds * 1000.0).round() as u64;
+    let duration_ms = end_ms - start_ms;
+    let title = output_title.trim();
+    let recording = Recording {
+        id: Uuid::new_v4().to_string(),
+        filename: output_path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&filename)
+            .to_string(),
+        duration: format_duration(duration_ms),
+        duration_ms,
+        file_size_bytes: std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0),
+        timestamp: source.timestamp,
+        status: RecordingStatus::Local,
+        title: (!title.is_empty()).then(|| title.to_string()),
+        has_transcript: false,
+        dropped_samples: 0,
+        needs_conversion: AudioFormat::from_path(&output_path).is_none(),
+        original_filename: None,
+        content_hash: None,
+        // Notes inside the range move with the audio
+        notes: source.notes.iter()
+            .filter(|note| (start_ms..=end_ms).contains(&note.timestamp_ms))
+            .map(|note| Note { timestamp_ms: note.timestamp_ms - start_ms, text: note.text.clone() })
+            .collect(),
+    };
     
-    // Remove from recordings list and save metadata
     {
         let mut recordings = state.recordings.lock().unwrap();
-        recordings.retain(|r| r.id != recording_id);
-        
-        // Save updated recordings metadata to disk
-        if let Err(e) = save_recordings_metadata(&app_handle, &recordings) {
-            eprintln!("Failed to save recordings metadata: {}", e);
+        recordings.insert(0, recording.clone());
+        save_recordings_metadata(&app_handle, &recordings)?;
+    }
+    let _ = crate::tray::refresh_tray_menu(&app_handle);
+    
+    info!("Trimmed {} to {:.1}s-{:.1}s as {}", source.filename, start_seconds, end_seconds, recording.filename);
+    Ok(recording)
+}
+
+// Check a trim range against the recording length (unknown lengths, stored as 0, only get the basic checks)
+fn validate_trim_range(start_seconds: f64, end_seconds: f64, duration_ms: u64) -> Result<()> {
+    if !start_seconds.is_finite() || !end_seconds.is_finite() || start_seconds < 0.0 {
+        return Err(AppError::Recording("Trim start must be zero or later".to_string()));
+    }
+    if end_seconds <= start_seconds {
+        return Err(AppError::Recording("Trim end must be after the start".to_string()));
+    }
+    let duration_seconds = duration_ms as f64 / 1000.0;
+    if duration_ms > 0 && end_seconds > duration_seconds {
+        return Err(AppError::Recording(format!(
+            "Trim end ({:.1}s) is past the end of the recording ({:.1}s)", end_seconds, duration_seconds
+        )));
+    }
+    Ok(())
+}
+
+// Check that files can be joined without re-encoding (same container, and the same WAV format
+// or codec, sample rate and channels), returning their shared extension
+async fn check_mergeable(paths: &[PathBuf], app_handle: &AppHandle) -> Result<String> {
+    let extension_of = |path: &PathBuf| path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    let extension = extension_of(&paths[0]);
+    if let Some(other) = paths.iter().find(|path| extension_of(path) != extension) {
+        return Err(AppError::Recording(format!(
+            "Cannot merge .{} and .{} recordings; all recordings must have the same format",
+            extension, extension_of(other)
+        )));
+    }
+    
+    if extension == "wav" {
+        let spec_of = |path: &PathBuf| hound::WavReader::open(path)
+            .map(|reader| reader.spec())
+            .map_err(|e| AppError::Recording(format!("Failed to read {}: {}", path.display(), e)));
+        let first_spec = spec_of(&paths[0])?;
+        for path in &paths[1..] {
+            let spec = spec_of(path)?;
+            if spec != first_spec {
+                return Err(AppError::Recording(format!(
+                    "Cannot merge WAV recordings with different formats ({} Hz/{} ch vs {} Hz/{} ch)",
+                    first_spec.sample_rate, first_spec.channels, spec.sample_rate, spec.channels
+                )));
+            }
+        }
+    } else {
+        // Compressed streams are joined without re-encoding, which needs identical encoder parameters
+        let first_stream = AudioConverter::probe_audio_stream(&paths[0], app_handle).await
+            .map_err(AppError::Recording)?;
+        for path in &paths[1..] {
+            let stream = AudioConverter::probe_audio_stream(path, app_handle).await
+                .map_err(AppError::Recording)?;
+            if stream != first_stream {
+                return Err(AppError::Recording(format!(
+                    "Cannot merge recordings with different encodings ({} vs {})", first_stream, stream
+                )));
+            }
         }
     }
     
-    // Stop playback if this recording is currently playing
-    let should_stop_playback = {
-        let playback_state = state.playback_state.lock().unwrap();
-        if let PlaybackState::Playing { recording_id: playing_id, .. } = &*playback_state {
-            playing_id == &recording_id
-        } else {
-            false
+    Ok(extension)
+}
+
+// Pick a path in `dir` for `filename`, appending " (n)" to the stem if it is taken
+fn unique_export_path(dir: &Path, filename: &str) -> PathBuf {
+    let candidate = dir.join(filename);
+    if !candidate.exists() {
+        return candidate;
+    }
+    
+    let path = Path::new(filename);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(filename);
+    let extension = path.extension().and_then(|e| e.to_str());
+    
+    (1..)
+        .map(|counter| match extension {
+            Some(ext) => dir.join(format!("{} ({}).{}", stem, counter, ext)),
+            None => dir.join(format!("{} ({})", stem, counter)),
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded counter always yields a free path")
+}
+
+// Compare the recordings directory with the metadata. Unreferenced files modified within
+// `ORPHAN_MIN_AGE_SECS` are left out, as they may belong to a recording still being saved.
+pub async fn audit_recordings(state: State<'_, AppState>, app_handle: AppHandle) -> Result<RecordingsAudit> {
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let recordings = known_recordings(&state, &app_handle)?;
+    audit_recordings_dir(&recordings_dir, &recordings)
+}
+
+// Recordings the metadata file and the in-memory list know about. The file is flushed and
+// reloaded so the audit doesn't depend on a list that may be stale; entries whose audio
+// is missing are dropped on load, so the in-memory list is kept to report them.
+fn known_recordings(state: &AppState, app_handle: &AppHandle) -> Result<Vec<Recording>> {
+    let mut recordings = load_recordings_metadata(app_handle)?;
+    recordings.extend(state.recordings.lock().unwrap().iter().cloned());
+    Ok(recordings)
+}
+
+fn audit_recordings_dir(recordings_dir: &Path, recordings: &[Recording]) -> Result<RecordingsAudit> {
+    let mut referenced = std::collections::HashSet::new();
+    let mut audit = RecordingsAudit::default();
+    for recording in recordings {
+        let Ok(file_path) = AppPaths::resolve_recording_path(recordings_dir, &recording.filename) else { continue };
+        if !file_path.exists() && !audit.missing_files.contains(&recording.filename) {
+            audit.missing_files.push(recording.filename.clone());
         }
-    };
+        referenced.insert(Waveform::peaks_path(&file_path));
+        referenced.insert(TranscriptionService::transcript_path(&file_path));
+        referenced.insert(file_path);
+        if let Some(original) = &recording.original_filename {
+            if let Ok(original_path) = AppPaths::resolve_recording_path(recordings_dir, original) {
+                referenced.insert(original_path);
+            }
+        }
+    }
     
-    if should_stop_playback {
-        stop_playback(state).await?;
+    let min_age = std::time::Duration::from_secs(ORPHAN_MIN_AGE_SECS);
+    for path in list_recordings_dir_files(recordings_dir)? {
+        let is_artifact = path.file_name().and_then(|name| name.to_str()).is_some_and(is_recording_artifact);
+        let is_recent = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| modified.elapsed().map_or(true, |age| age < min_age));
+        if !is_artifact || is_recent || referenced.contains(&path) {
+            continue;
+        }
+        if let Some(name) = AppPaths::relative_recording_name(recordings_dir, &path) {
+            audit.orphan_files.push(name);
+        }
     }
+    audit.orphan_files.sort();
     
-    println!("Deleted recording: {}", recording.filename);
+    Ok(audit)
+}
+
+// Delete the files `audit_recordings` reports as orphaned, returning the ones removed
+pub async fn cleanup_orphans(state: State<'_, AppState>, app_handle: AppHandle) -> Result<Vec<String>> {
+    let recordings_dir = get_recordings_directory(&app_handle)?;
+    let recordings = known_recordings(&state, &app_handle)?;
+    let audit = audit_recordings_dir(&recordings_dir, &recordings)?;
+    check_orphans_deletable(&recordings, &audit.orphan_files)?;
+    
+    let mut removed = Vec::new();
+    for name in audit.orphan_files {
+        let path = AppPaths::resolve_recording_path(&recordings_dir, &name)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                remove_empty_subfolder(&recordings_dir, &path);
+                removed.push(name);
+            }
+            Err(e) => warn!("Failed to delete orphaned file {}: {}", path.display(), e),
+        }
+    }
+    
+    info!("Deleted {} orphaned files", removed.len());
+    Ok(removed)
+}
+
+// Refuse to treat every recording file as orphaned when no metadata was found at all,
+// as that more likely means the metadata is unreadable than that nothing is referenced
+fn check_orphans_deletable(recordings: &[Recording], orphan_files: &[String]) -> Result<()> {
+    if recordings.is_empty() && !orphan_files.is_empty() {
+        return Err(AppError::Recording(format!(
+            "Refusing to delete {} files: no recordings metadata was found",
+            orphan_files.len()
+        )));
+    }
     Ok(())
 }
 
@@ -489,4 +2672,390 @@ pub async fn open_recordings_folder(app_handle: AppHandle) -> Result<()> {
     });
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::services::transcription_service::TranscriptSegment;
+
+    fn transcript(text: &str, segments: Option<Vec<TranscriptSegment>>) -> TranscriptionResponse {
+        TranscriptionResponse { transcript: text.to_string(), confidence: None, processing_time: None, word_count: None, segments }
+    }
+
+    #[test]
+    fn test_failed_start_rolls_back_to_idle() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("rec.wav");
+        std::fs::write(&file_path, b"RIFF").unwrap();
+        let recording_state = std::sync::Mutex::new(RecordingState::Recording { start_time: Utc::now(), paused_ms: 0, file_path: file_path.clone(), title: None, source: RecordingSource::Manual });
+        let audio_recorder = std::sync::Mutex::new(RecorderState::new());
+        
+        // Starts the audio thread and marks the recorder as recording, as for a real start
+        send_start_command(&audio_recorder, &file_path, AudioCommand::StopPlayback).unwrap();
+        assert!(audio_recorder.lock().unwrap().is_recording());
+        
+        // The stream never confirmed, so the start is undone
+        abort_recording_start(&recording_state, &audio_recorder, &file_path);
+        assert!(matches!(*recording_state.lock().unwrap(), RecordingState::Idle));
+        assert!(!audio_recorder.lock().unwrap().is_recording());
+        assert!(!file_path.exists(), "The header-only WAV should be removed");
+    }
+
+    #[test]
+    fn test_pause_and_resume_exclude_paused_time() {
+        let start_time = Utc::now();
+        let at = |secs: i64| start_time + chrono::Duration::seconds(secs);
+        let recording = RecordingState::Recording { start_time, paused_ms: 0, file_path: PathBuf::from("rec.wav"), title: None, source: RecordingSource::Manual };
+        
+        // Record 10s, pause for 20s, record another 5s
+        let paused = paused_state(&recording, at(10)).unwrap();
+        assert_eq!(RecordingInfo::from_state(&paused, at(30)).elapsed_ms, 10_000);
+        let resumed = resumed_state(&paused, at(30)).unwrap();
+        assert!(matches!(resumed, RecordingState::Recording { paused_ms: 20_000, .. }));
+        assert_eq!(RecordingInfo::from_state(&resumed, at(35)).elapsed_ms, 15_000);
+        
+        // A second pause keeps the earlier one excluded
+        let paused_again = paused_state(&resumed, at(35)).unwrap();
+        let resumed_again = resumed_state(&paused_again, at(95)).unwrap();
+        assert_eq!(RecordingInfo::from_state(&resumed_again, at(100)).elapsed_ms, 20_000);
+        
+        assert!(paused_state(&paused, at(40)).is_none());
+        assert!(resumed_state(&recording, at(40)).is_none());
+    }
+
+    #[test]
+    fn test_max_duration_counts_only_active_time() {
+        let start_time = Utc::now();
+        let at = |secs: i64| start_time + chrono::Duration::seconds(secs);
+        let recording = RecordingState::Recording { start_time, paused_ms: 0, file_path: PathBuf::from("rec.wav"), title: None, source: RecordingSource::Manual };
+        assert_eq!(max_duration_remaining_ms(&recording, start_time, 60_000, at(10)), Some(50_000));
+        
+        // A minute-long pause leaves the remaining time where it was
+        let paused = paused_state(&recording, at(10)).unwrap();
+        assert_eq!(max_duration_remaining_ms(&paused, start_time, 60_000, at(70)), Some(50_000));
+        let resumed = resumed_state(&paused, at(70)).unwrap();
+        assert_eq!(max_duration_remaining_ms(&resumed, start_time, 60_000, at(120)), Some(0));
+        
+        // Another recording (or none) ends the watchdog
+        assert_eq!(max_duration_remaining_ms(&resumed, at(1), 60_000, at(120)), None);
+        assert_eq!(max_duration_remaining_ms(&RecordingState::Idle, start_time, 60_000, at(120)), None);
+    }
+
+    #[test]
+    fn test_transcript_snippets_from_plain_text() {
+        let query = lowercase_chars("pricing");
+        let (hits, snippets) = transcript_snippets(&transcript("Then we covered Pricing. Pricing again.", None), &query);
+        assert_eq!(hits, 2);
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].text, "Then we covered Pricing. Pricing again.");
+        assert_eq!(snippets[0].start, None);
+        
+        let (hits, snippets) = transcript_snippets(&transcript("Nothing relevant", None), &query);
+        assert_eq!(hits, 0);
+        assert!(snippets.is_empty());
+    }
+
+    #[test]
+    fn test_transcript_snippets_use_matching_segments() {
+        let segment = |start: f64, text: &str| TranscriptSegment { speaker: Some("A".to_string()), start, end: start + 5.0, text: text.to_string() };
+        let segments = vec![segment(0.0, "Hello everyone"), segment(5.0, " Let's talk PRICING ")];
+        let (hits, snippets) = transcript_snippets(&transcript("ignored", Some(segments)), &lowercase_chars("pricing"));
+        assert_eq!(hits, 1);
+        assert_eq!(snippets, vec![SearchSnippet {
+            text: "Let's talk PRICING".to_string(),
+            start: Some(5.0),
+            end: Some(10.0),
+            speaker: Some("A".to_string()),
+        }]);
+    }
+
+    #[test]
+    fn test_expired_recordings_respects_cutoff_and_kept_id() {
+        let now = Utc.with_ymd_and_hms(2024, 9, 30, 12, 0, 0).unwrap();
+        let recording = |id: &str, days_old: i64| Recording {
+            timestamp: now - chrono::Duration::days(days_old),
+            ..Recording::test(id, &format!("{}.opus", id))
+        };
+        let recordings = vec![recording("new", 3), recording("old", 31), recording("playing", 40)];
+        
+        let expired = expired_recordings(&recordings, now, 30, Some("playing"));
+        let ids: Vec<_> = expired.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["old"]);
+    }
+
+    #[test]
+    fn test_reset_interrupted_statuses() {
+        let recording = |filename: &str, status: RecordingStatus| Recording { status, ..Recording::test(filename, filename) };
+        let mut recordings = vec![
+            recording("unconverted.wav", RecordingStatus::Processing),
+            recording("converted.opus", RecordingStatus::Processing),
+            recording("transcribing.opus", RecordingStatus::Transcribing),
+            recording("uploaded.opus", RecordingStatus::Uploaded),
+        ];
+        
+        assert!(reset_interrupted_statuses(&mut recordings));
+        let statuses: Vec<_> = recordings.iter().map(|r| (r.status.clone(), r.needs_conversion)).collect();
+        assert_eq!(statuses, vec![
+            (RecordingStatus::Local, true),
+            (RecordingStatus::Local, false),
+            (RecordingStatus::Local, false),
+            (RecordingStatus::Uploaded, false),
+        ]);
+        assert!(!reset_interrupted_statuses(&mut recordings));
+    }
+
+    #[test]
+    fn test_status_restore_keeps_status_changed_meanwhile() {
+        let recording = |id: &str, status: RecordingStatus| Recording { status, ..Recording::test(id, &format!("{}.opus", id)) };
+        let mut recordings = vec![
+            recording("transcribing", RecordingStatus::Transcribing),
+            recording("uploaded", RecordingStatus::Uploaded),
+        ];
+        
+        let (previous, updated) = replace_status(&mut recordings, "transcribing", Some(RecordingStatus::Transcribing), RecordingStatus::Local).unwrap();
+        assert_eq!((previous, updated.status), (RecordingStatus::Transcribing, RecordingStatus::Local));
+        // An upload finished while transcribing; restoring must not overwrite its result
+        assert!(replace_status(&mut recordings, "uploaded", Some(RecordingStatus::Transcribing), RecordingStatus::Local).is_none());
+        assert_eq!(recordings[1].status, RecordingStatus::Uploaded);
+        assert!(replace_status(&mut recordings, "missing", None, RecordingStatus::Local).is_none());
+        
+        assert!(ensure_not_processing(&recording("busy", RecordingStatus::Processing)).is_err());
+        assert!(ensure_not_processing(&recordings[0]).is_ok());
+    }
+
+    #[test]
+    fn test_group_duplicates_by_content_hash() {
+        let recording = |id: &str, hash: Option<&str>| Recording {
+            content_hash: hash.map(str::to_string),
+            ..Recording::test(id, &format!("{}.opus", id))
+        };
+        let recordings = vec![
+            recording("a", Some("1")),
+            recording("b", Some("2")),
+            recording("c", None),
+            recording("d", Some("1")),
+            recording("e", None),
+            recording("f", Some("2")),
+            recording("g", Some("3")),
+        ];
+        
+        assert_eq!(group_duplicates(&recordings), vec![vec!["a", "d"], vec!["b", "f"]]);
+    }
+
+    #[test]
+    fn test_merge_imported_recordings_keeps_existing_entries() {
+        let recording = |id: &str, filename: &str, minutes_ago: i64| Recording {
+            timestamp: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            ..Recording::test(id, filename)
+        };
+        let mut recordings = vec![recording("a", "a.opus", 10), recording("b", "b.opus", 30)];
+        let mut imported_a = recording("a", "renamed.opus", 5);
+        imported_a.title = Some("Imported".to_string());
+        let imported = vec![
+            imported_a,
+            recording("c", "c.opus", 20),
+            recording("d", "b.opus", 1),
+            recording("e", "e.opus", 40),
+        ];
+        
+        assert_eq!(merge_imported_recordings(&mut recordings, imported), 2);
+        let ids: Vec<&str> = recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c", "b", "e"]);
+        assert_eq!(recordings[0].title, None);
+        assert_eq!(recordings[0].filename, "a.opus");
+    }
+
+    #[test]
+    fn test_hash_file_matches_identical_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, second, other) = (dir.path().join("a.opus"), dir.path().join("b.opus"), dir.path().join("c.opus"));
+        std::fs::write(&first, b"same audio").unwrap();
+        std::fs::write(&second, b"same audio").unwrap();
+        std::fs::write(&other, b"other audio").unwrap();
+        
+        let hash = hash_file(&first).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_file(&second).unwrap());
+        assert_ne!(hash, hash_file(&other).unwrap());
+    }
+
+    #[test]
+    fn test_identical_captures_dedup_by_wav_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = hound::WavSpec { channels: 1, sample_rate: 16000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let capture = |name: &str| {
+            let path = dir.path().join(name);
+            let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+            (0..1600).for_each(|i| writer.write_sample((i % 100) as i16 * 100).unwrap());
+            writer.finalize().unwrap();
+            path
+        };
+        let (first, second) = (capture("first.wav"), capture("second.wav"));
+        
+        // Hashes are taken before conversion, so encoder differences between runs can't hide the duplicate
+        let first_hash = hash_file(&first).unwrap();
+        let second_hash = hash_file(&second).unwrap();
+        assert_eq!(first_hash, second_hash);
+        
+        let existing = Recording {
+            duration_ms: 100,
+            content_hash: Some(first_hash),
+            ..Recording::test("first", "first.opus")
+        };
+        let second_recording = Recording { id: "second".to_string(), content_hash: None, ..existing.clone() };
+        let recordings = vec![second_recording, existing];
+        assert_eq!(find_duplicate(&recordings, "second", &second_hash), Some("first".to_string()));
+        assert_eq!(find_duplicate(&recordings, "first", &second_hash), None);
+    }
+
+    #[test]
+    fn test_copy_file_name_numbers_later_copies() {
+        let path = Path::new("/rec/2024-08-26/standup.opus");
+        assert_eq!(copy_file_name(path, 1), "standup_copy.opus");
+        assert_eq!(copy_file_name(path, 3), "standup_copy3.opus");
+    }
+
+    #[test]
+    fn test_validate_trim_range() {
+        assert!(validate_trim_range(0.0, 10.0, 10_000).is_ok());
+        assert!(validate_trim_range(5.0, 600.0, 0).is_ok());
+        assert!(validate_trim_range(-1.0, 5.0, 10_000).is_err());
+        assert!(validate_trim_range(5.0, 5.0, 10_000).is_err());
+        assert!(validate_trim_range(2.0, 10.5, 10_000).is_err());
+        assert!(validate_trim_range(f64::NAN, 5.0, 10_000).is_err());
+    }
+
+    #[test]
+    fn test_cleanup_refuses_when_no_metadata_was_found() {
+        let orphans = vec!["recording_20240826_093000.opus".to_string()];
+        assert!(check_orphans_deletable(&[], &orphans).is_err());
+        assert!(check_orphans_deletable(&[], &[]).is_ok());
+        
+        assert!(check_orphans_deletable(&[Recording::test("kept", "kept.opus")], &orphans).is_ok());
+    }
+
+    #[test]
+    fn test_conversion_claims_the_recording() {
+        let mut recordings = vec![Recording {
+            status: RecordingStatus::Failed,
+            needs_conversion: true,
+            ..Recording::test("pending", "pending.wav")
+        }];
+        
+        let (claimed, previous_status) = claim_for_processing(&mut recordings, "pending").unwrap();
+        assert_eq!(claimed.id, "pending");
+        assert_eq!(previous_status, RecordingStatus::Failed);
+        assert_eq!(recordings[0].status, RecordingStatus::Processing);
+        
+        // A second conversion started meanwhile is turned away
+        assert!(claim_for_processing(&mut recordings, "pending").is_err());
+        assert!(claim_for_processing(&mut recordings, "missing").is_err());
+    }
+
+    #[test]
+    fn test_is_importable_entry() {
+        assert!(is_importable_entry(Path::new("recording_20240826_093000.opus")));
+        assert!(is_importable_entry(&Path::new("2024-08-26").join("standup.wav.peaks.json")));
+        assert!(!is_importable_entry(Path::new("recordings.json")));
+        assert!(!is_importable_entry(Path::new("config.json")));
+        assert!(!is_importable_entry(Path::new("launch.sh")));
+        assert!(!is_importable_entry(&Path::new("a").join("b").join("c.opus")));
+    }
+
+    #[test]
+    fn test_is_recording_artifact() {
+        assert!(is_recording_artifact("recording_20240826_093000.opus"));
+        assert!(is_recording_artifact("recording_20240826_093000.wav.peaks.json"));
+        assert!(is_recording_artifact("recording_20240826_093000.opus.transcript.json"));
+        assert!(!is_recording_artifact("recordings.json"));
+        assert!(!is_recording_artifact("recordings.json.corrupt-20240826_093000"));
+        assert!(!is_recording_artifact(".DS_Store"));
+    }
+
+    #[test]
+    fn test_recording_subfolder_by_layout() {
+        let start = Utc.with_ymd_and_hms(2024, 8, 26, 9, 30, 0).unwrap();
+        assert_eq!(recording_subfolder(RecordingLayout::Flat, start, Some("Zoom"), None), None);
+        assert_eq!(recording_subfolder(RecordingLayout::PerDay, start, None, None).as_deref(), Some("2024-08-26"));
+        assert_eq!(
+            recording_subfolder(RecordingLayout::PerMeeting, start, Some("Zoom"), Some("Standup / Sync")).as_deref(),
+            Some("2024-08-26 Zoom - Standup _ Sync")
+        );
+        assert_eq!(recording_subfolder(RecordingLayout::PerMeeting, start, None, Some(" ")), None);
+    }
+
+    #[test]
+    fn test_expand_filename_template() {
+        let start_time = Utc.with_ymd_and_hms(2024, 8, 26, 14, 30, 22).unwrap();
+        let expand = |template, app, title| expand_filename_template(template, start_time, app, title, 2);
+        
+        assert_eq!(expand(DEFAULT_FILENAME_TEMPLATE, None, None).as_deref(), Some("recording_20240826_143022"));
+        assert_eq!(
+            expand("{date}_{app}_{title}-{counter}", Some("Zoom"), Some("Q3: Planning / Review")).as_deref(),
+            Some("20240826_Zoom_Q3_ Planning _ Review-2")
+        );
+        // Missing values and unknown placeholders can't be resolved
+        assert_eq!(expand("{date}_{title}", Some("Zoom"), None), None);
+        assert_eq!(expand("{date}_{tite}", None, None), None);
+        assert_eq!(expand("{date", None, None), None);
+        assert_eq!(expand("{title}", None, Some(" .. ")), None);
+    }
+
+    #[test]
+    fn test_recording_file_name_falls_back_and_avoids_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 8, 26, 14, 30, 22).unwrap();
+        
+        let name = recording_file_name(dir.path(), "{app}_{title}", start_time, None, None);
+        assert_eq!(name, "recording_20240826_143022.wav");
+        
+        std::fs::write(dir.path().join("standup_1.wav"), b"").unwrap();
+        let name = recording_file_name(dir.path(), "standup_{counter}", start_time, None, None);
+        assert_eq!(name, "standup_2.wav");
+        
+        std::fs::write(dir.path().join("standup.wav"), b"").unwrap();
+        let name = recording_file_name(dir.path(), "standup", start_time, None, None);
+        assert_eq!(name, "standup (1).wav");
+    }
+    
+    #[test]
+    fn test_recording_file_name_avoids_converted_recordings() {
+        let dir = tempfile::tempdir().unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 8, 26, 14, 30, 22).unwrap();
+        
+        // The WAV of an earlier recording is gone after conversion; only the Opus file remains
+        std::fs::write(dir.path().join("Zoom.opus"), b"").unwrap();
+        let name = recording_file_name(dir.path(), "{app}", start_time, Some("Zoom"), None);
+        assert_eq!(name, "Zoom (1).wav");
+        
+        std::fs::write(dir.path().join("Zoom (1).mp3.transcript.json"), b"").unwrap();
+        let name = recording_file_name(dir.path(), "{app}", start_time, Some("Zoom"), None);
+        assert_eq!(name, "Zoom (2).wav");
+        
+        std::fs::write(dir.path().join("standup_1.m4a"), b"").unwrap();
+        let name = recording_file_name(dir.path(), "standup_{counter}", start_time, None, None);
+        assert_eq!(name, "standup_2.wav");
+    }
+
+    #[test]
+    fn test_parse_recording_timestamp_from_filename() {
+        assert_eq!(
+            parse_recording_timestamp("recording_20240826_143022.opus"),
+            Some(Utc.with_ymd_and_hms(2024, 8, 26, 14, 30, 22).unwrap())
+        );
+        assert_eq!(parse_recording_timestamp("notes.opus"), None);
+        assert_eq!(parse_recording_timestamp("recording_2024.wav"), None);
+    }
+
+    #[test]
+    fn test_unique_export_path_appends_counter_on_collision() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(unique_export_path(dir.path(), "a.opus"), dir.path().join("a.opus"));
+        
+        std::fs::write(dir.path().join("a.opus"), b"").unwrap();
+        std::fs::write(dir.path().join("a (1).opus"), b"").unwrap();
+        assert_eq!(unique_export_path(dir.path(), "a.opus"), dir.path().join("a (2).opus"));
+    }
+}
@@ -0,0 +1,240 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use tokio::task;
+use crate::AppState;
+use crate::app_config::AppConfig;
+use crate::audio_system;
+use crate::constants::*;
+use crate::error::{AppError, Result};
+use crate::path_manager::AppPaths;
+use super::audio_converter::{AudioConverter, AudioFormat};
+use super::transcription_service::TranscriptionBackendKind;
+
+/// Result of one health check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub ok: bool,
+    /// What was found (e.g. the device name or FFmpeg path), or why the check failed
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn from_result(result: std::result::Result<String, String>) -> Self {
+        match result {
+            Ok(detail) => Self { ok: true, detail },
+            Err(detail) => Self { ok: false, detail },
+        }
+    }
+}
+
+/// Health of the pieces a recording depends on
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemStatus {
+    pub input_device: CheckResult,
+    pub ffmpeg: CheckResult,
+    /// Only checked on macOS, where missing permission yields silent recordings
+    pub microphone_permission: Option<CheckResult>,
+    /// Only checked when the remote transcription backend is selected
+    pub transcription_endpoint: Option<CheckResult>,
+    /// What meeting detection looks at, including whether browser tabs are read
+    pub meeting_detection: String,
+}
+
+/// App and audio setup details for support reports
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioDiagnostics {
+    pub app_version: String,
+    pub os: String,
+    /// Audio backend, e.g. "CoreAudio" or "WASAPI"
+    pub host: String,
+    /// Input device selected in settings (None = system default)
+    pub selected_input_device: Option<String>,
+    /// Device recordings would actually use, with its default sample rate and channels
+    pub input_device: Option<audio_system::AudioDeviceInfo>,
+    /// Why the input device couldn't be opened, if it couldn't
+    pub input_device_error: Option<String>,
+    /// Rate audio is captured at (the input device's default rate)
+    pub captured_sample_rate: Option<u32>,
+    /// Rate recordings are written at; differs from the captured rate when resampling is configured
+    pub target_sample_rate: Option<u32>,
+    pub recording_format: AudioFormat,
+    /// First virtual loopback input found (e.g. BlackHole), for capturing system audio
+    pub loopback_device: Option<String>,
+}
+
+// Plain description of the signals meeting detection uses with this config
+fn describe_meeting_detection(config: &AppConfig) -> String {
+    if !config.meeting_detection_enabled {
+        return "Off: meetings are not detected".to_string();
+    }
+    if !config.scan_browser_urls {
+        return "Meeting app processes and microphone use only; browser tabs are not read".to_string();
+    }
+    if cfg!(target_os = "macos") {
+        "Meeting app processes, microphone use and the URL of each browser window's active tab".to_string()
+    } else {
+        "Meeting app processes and browser window titles".to_string()
+    }
+}
+
+// Collect device and format details into one report the user can copy into a support ticket
+pub async fn get_audio_diagnostics(state: State<'_, AppState>, app_handle: AppHandle) -> Result<AudioDiagnostics> {
+    let selected_input_device = state.input_device.lock().unwrap().clone();
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    
+    let device_name = selected_input_device.clone();
+    let (input_device, input_devices) = task::spawn_blocking(move || {
+        (audio_system::describe_input_device(device_name.as_deref()), audio_system::list_input_devices())
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("Failed to spawn device inspection: {}", e)))?;
+    
+    let loopback_device = input_devices.unwrap_or_default().into_iter()
+        .map(|device| device.name)
+        .find(|name| audio_system::is_loopback_device_name(name));
+    let (input_device, input_device_error) = match input_device {
+        Ok(device) => (Some(device), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let captured_sample_rate = input_device.as_ref().map(|device| device.default_sample_rate);
+    let target_sample_rate = (*state.record_sample_rate.lock().unwrap()).or(captured_sample_rate);
+    
+    Ok(AudioDiagnostics {
+        app_version: app_handle.package_info().version.to_string(),
+        os: format!("{} ({})", std::env::consts::OS, std::env::consts::ARCH),
+        host: audio_system::host_name(),
+        selected_input_device,
+        input_device,
+        input_device_error,
+        captured_sample_rate,
+        target_sample_rate,
+        recording_format: config.default_recording_format,
+        loopback_device,
+    })
+}
+
+// Run every health check and report the results together
+pub async fn get_system_status(state: State<'_, AppState>, app_handle: AppHandle) -> Result<SystemStatus> {
+    let device_name = state.input_device.lock().unwrap().clone();
+    let config = AppConfig::load(&app_handle).await.unwrap_or_default();
+    
+    let input_device = {
+        let device_name = device_name.clone();
+        task::spawn_blocking(move || audio_system::resolve_input_device_name(device_name.as_deref()))
+            .await
+            .map_err(|e| format!("Failed to spawn device check: {}", e))?
+            .map_err(|e| e.to_string())
+    };
+    
+    let ffmpeg = {
+        let app_handle = app_handle.clone();
+        task::spawn_blocking(move || AudioConverter::verify_ffmpeg(&app_handle))
+            .await
+            .map_err(|e| format!("Failed to spawn FFmpeg check: {}", e))?
+            .map(|path| path.display().to_string())
+    };
+    
+    let microphone_permission = if cfg!(target_os = "macos") && input_device.is_ok() {
+        let probe = task::spawn_blocking(move || audio_system::probe_input_signal(device_name.as_deref()))
+            .await
+            .map_err(|e| format!("Failed to spawn microphone probe: {}", e))?;
+        Some(CheckResult::from_result(match probe {
+            Ok(true) => Ok("Microphone is delivering audio".to_string()),
+            Ok(false) => Err("Microphone delivered only silence; permission may be denied".to_string()),
+            Err(e) => Err(e.to_string()),
+        }))
+    } else {
+        None
+    };
+    
+    let transcription_endpoint = match config.transcription_backend {
+        TranscriptionBackendKind::Remote => Some(CheckResult::from_result(
+            check_endpoint_reachable(&config.transcribe_endpoint()).await
+        )),
+        TranscriptionBackendKind::LocalWhisper => None,
+    };
+    
+    Ok(SystemStatus {
+        input_device: CheckResult::from_result(input_device),
+        ffmpeg: CheckResult::from_result(ffmpeg),
+        microphone_permission,
+        transcription_endpoint,
+        meeting_detection: describe_meeting_detection(&config),
+    })
+}
+
+// Open the log file for appending, moving an oversized previous log aside first
+pub fn open_log_file(app_handle: &AppHandle) -> Result<std::fs::File> {
+    let log_file = AppPaths::log_file(app_handle)?;
+    if let Some(log_dir) = log_file.parent() {
+        std::fs::create_dir_all(log_dir)?;
+    }
+    
+    if std::fs::metadata(&log_file).is_ok_and(|meta| meta.len() > MAX_LOG_FILE_BYTES) {
+        std::fs::rename(&log_file, log_file.with_extension("log.old"))?;
+    }
+    
+    Ok(std::fs::OpenOptions::new().create(true).append(true).open(&log_file)?)
+}
+
+// Last `lines` lines of the log file, oldest first; empty when nothing has been logged yet
+pub async fn get_recent_logs(app_handle: AppHandle, lines: usize) -> Result<Vec<String>> {
+    let log_file = AppPaths::log_file(&app_handle)?;
+    let content = match tokio::fs::read(&log_file).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    
+    Ok(tail_lines(&content, lines))
+}
+
+fn tail_lines(content: &str, lines: usize) -> Vec<String> {
+    let all: Vec<&str> = content.lines().collect();
+    all[all.len().saturating_sub(lines)..].iter().map(|line| line.to_string()).collect()
+}
+
+// Open the log directory in the file explorer
+pub async fn open_log_folder(app_handle: AppHandle) -> Result<()> {
+    let log_file = AppPaths::log_file(&app_handle)?;
+    let log_dir = log_file.parent()
+        .ok_or_else(|| AppError::Path("Log file has no parent directory".to_string()))?
+        .to_path_buf();
+    // The directory only exists once something was logged; create it so the explorer has something to show
+    std::fs::create_dir_all(&log_dir)?;
+    
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = tauri_plugin_opener::open_path(log_dir, None::<String>) {
+            eprintln!("Failed to open log folder: {}", e);
+        }
+    });
+    
+    Ok(())
+}
+
+// Any HTTP response counts as reachable; only connection failures and timeouts do not
+async fn check_endpoint_reachable(url: &str) -> std::result::Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(ENDPOINT_CHECK_TIMEOUT_MS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    
+    let response = client.head(url).send().await
+        .map_err(|e| format!("{} is unreachable: {}", url, e))?;
+    Ok(format!("{} responded with {}", url, response.status()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_lines() {
+        let content = "one\ntwo\nthree\n";
+        assert_eq!(tail_lines(content, 2), vec!["two", "three"]);
+        assert_eq!(tail_lines(content, 10), vec!["one", "two", "three"]);
+        assert!(tail_lines(content, 0).is_empty());
+        assert!(tail_lines("", 5).is_empty());
+    }
+}
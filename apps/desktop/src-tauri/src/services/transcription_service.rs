@@ -1,9 +1,19 @@
 use reqwest;
 use serde::{Serialize, Deserialize};
-use std::path::Path;
+use futures_util::StreamExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Instant;
+use tauri::Manager;
 use tokio_util::codec::{BytesCodec, FramedRead};
+use tokio_util::sync::CancellationToken;
 use tokio::fs::File;
+use tokio::task;
+use crate::AppState;
+use crate::app_config::AppConfig;
 use crate::constants::*;
+use crate::events::EventEmitter;
+use super::audio_converter::{AudioChunk, AudioConverter, AudioFormat, ConversionSettings};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionResponse {
@@ -11,6 +21,91 @@ pub struct TranscriptionResponse {
     pub confidence: Option<f64>,
     pub processing_time: Option<f64>,
     pub word_count: Option<i32>,
+    /// Timed (and, when the backend diarizes, speaker-labelled) transcript segments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<TranscriptSegment>>,
+}
+
+/// A span of the transcript with its timing in seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    #[serde(default)]
+    pub speaker: Option<String>,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Payload of the `transcription_progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionProgress {
+    pub recording_id: String,
+    /// "uploading" (remote backend) or "transcribing" (local Whisper)
+    pub stage: String,
+    /// Overall completion, 0-100; chunked transcriptions count each chunk equally
+    pub percent: u8,
+    /// Chunk being transcribed (1-based; always 1 when the recording is sent whole)
+    pub chunk: u32,
+    pub chunk_count: u32,
+}
+
+impl TranscriptionProgress {
+    /// Progress callback for chunk `chunk` (0-based) of `chunk_count`, taking that chunk's percentage
+    fn reporter(app_handle: &tauri::AppHandle, recording_id: &str, stage: &'static str, chunk: u32, chunk_count: u32) -> impl Fn(u8) + Send + Sync + 'static {
+        let app_handle = app_handle.clone();
+        let recording_id = recording_id.to_string();
+        move |percent| {
+            EventEmitter::transcription_progress(&app_handle, &Self {
+                recording_id: recording_id.clone(),
+                stage: stage.to_string(),
+                percent: Self::overall_percent(chunk, chunk_count, percent),
+                chunk: chunk + 1,
+                chunk_count,
+            });
+        }
+    }
+
+    fn overall_percent(chunk: u32, chunk_count: u32, percent: u8) -> u8 {
+        ((chunk * 100 + u32::from(percent.min(100))) / chunk_count.max(1)).min(100) as u8
+    }
+}
+
+/// Backend kind selected in `AppConfig`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionBackendKind {
+    #[default]
+    Remote,
+    LocalWhisper,
+}
+
+/// Fully resolved transcription backend for a single request
+#[derive(Debug, Clone)]
+pub enum TranscriptionBackend {
+    /// Stream the audio to the web app's transcription endpoint
+    Remote { url: String, api_key: Option<String> },
+    /// Run the bundled whisper.cpp binary against a local GGML model
+    LocalWhisper { model_path: PathBuf },
+}
+
+/// Subset of whisper.cpp's `--output-json` format
+#[derive(Debug, Deserialize)]
+struct WhisperOutput {
+    transcription: Vec<WhisperSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperSegment {
+    #[serde(default)]
+    offsets: Option<WhisperOffsets>,
+    text: String,
+}
+
+/// Segment bounds in milliseconds
+#[derive(Debug, Deserialize)]
+struct WhisperOffsets {
+    from: u64,
+    to: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,6 +118,357 @@ pub struct TranscriptionError {
 pub struct TranscriptionService;
 
 impl TranscriptionService {
+    /// Get the path of the transcript stored next to a recording as `<filename>.transcript.json`
+    pub fn transcript_path(recording_path: &Path) -> PathBuf {
+        let mut file_name = recording_path.file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        file_name.push(".transcript.json");
+        recording_path.with_file_name(file_name)
+    }
+
+    /// Save a transcript next to its recording so it survives restarts
+    pub fn save_transcript(recording_path: &Path, transcript: &TranscriptionResponse) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(transcript)
+            .map_err(|e| format!("Failed to serialize transcript: {}", e))?;
+        std::fs::write(Self::transcript_path(recording_path), json)
+            .map_err(|e| format!("Failed to write transcript: {}", e))
+    }
+
+    /// Load the saved transcript for a recording, if there is one
+    pub fn load_transcript(recording_path: &Path) -> Result<Option<TranscriptionResponse>, String> {
+        let transcript_path = Self::transcript_path(recording_path);
+        if !transcript_path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&transcript_path)
+            .map_err(|e| format!("Failed to read transcript: {}", e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse transcript: {}", e))
+    }
+
+    /// Transcribe a recording with the given backend, emitting `transcription_progress` events
+    ///
+    /// `language` is an ISO code such as "en"; `None` or "auto" lets the backend detect it.
+    /// With `chunk_minutes` > 0 the recording is split into pieces of that length which are
+    /// transcribed one after another and joined, so long recordings don't need one huge request.
+    /// The run can be stopped with `cancel_transcription`, which makes it fail with
+    /// `TRANSCRIPTION_CANCELLED_MESSAGE`; only one run per recording is allowed at a time.
+    pub async fn transcribe(
+        file_path: &Path,
+        backend: &TranscriptionBackend,
+        language: Option<&str>,
+        chunk_minutes: u32,
+        recording_id: &str,
+        app_handle: &tauri::AppHandle
+    ) -> Result<TranscriptionResponse, String> {
+        let cancel = CancellationToken::new();
+        let state = app_handle.try_state::<AppState>();
+        if let Some(state) = &state {
+            let mut transcriptions = state.transcriptions.lock().unwrap();
+            if transcriptions.contains_key(recording_id) {
+                return Err("A transcription of this recording is already in progress".to_string());
+            }
+            transcriptions.insert(recording_id.to_string(), cancel.clone());
+        }
+        
+        let result = Self::transcribe_cancellable(file_path, backend, language, chunk_minutes, recording_id, app_handle, &cancel).await;
+        if let Some(state) = &state {
+            state.transcriptions.lock().unwrap().remove(recording_id);
+        }
+        result
+    }
+
+    async fn transcribe_cancellable(
+        file_path: &Path,
+        backend: &TranscriptionBackend,
+        language: Option<&str>,
+        chunk_minutes: u32,
+        recording_id: &str,
+        app_handle: &tauri::AppHandle,
+        cancel: &CancellationToken
+    ) -> Result<TranscriptionResponse, String> {
+        let language = Self::normalize_language(language);
+        if chunk_minutes == 0 {
+            return Self::transcribe_with_backend(file_path, backend, language, (0, 1), recording_id, app_handle, cancel).await;
+        }
+        
+        let started = Instant::now();
+        // Unique per run, so a re-transcription can't delete chunks an earlier run is still reading
+        let chunk_dir = std::env::temp_dir().join(format!("transcription-{}-{}", recording_id, uuid::Uuid::new_v4().simple()));
+        let result = async {
+            let chunks = AudioConverter::split_into_chunks(file_path, chunk_minutes.saturating_mul(60), &chunk_dir, app_handle).await?;
+            let chunk_count = chunks.len() as u32;
+            println!("Transcribing {} in {} chunks of up to {} minutes", file_path.display(), chunk_count, chunk_minutes);
+            
+            let mut responses = Vec::with_capacity(chunks.len());
+            for (index, chunk) in chunks.into_iter().enumerate() {
+                let response = Self::transcribe_with_backend(&chunk.path, backend, language, (index as u32, chunk_count), recording_id, app_handle, cancel)
+                    .await
+                    .map_err(|e| if cancel.is_cancelled() { e } else { format!("Chunk {}/{} failed: {}", index + 1, chunk_count, e) })?;
+                responses.push((chunk, response));
+            }
+            Ok(Self::merge_chunk_responses(responses, started.elapsed().as_secs_f64()))
+        }.await;
+        let _ = std::fs::remove_dir_all(&chunk_dir);
+        result
+    }
+
+    /// Send one file to the backend; `chunk` is its (0-based index, count) for progress events
+    async fn transcribe_with_backend(
+        file_path: &Path,
+        backend: &TranscriptionBackend,
+        language: Option<&str>,
+        chunk: (u32, u32),
+        recording_id: &str,
+        app_handle: &tauri::AppHandle,
+        cancel: &CancellationToken
+    ) -> Result<TranscriptionResponse, String> {
+        let (index, count) = chunk;
+        match backend {
+            TranscriptionBackend::Remote { url, api_key } => {
+                let on_progress = TranscriptionProgress::reporter(app_handle, recording_id, "uploading", index, count);
+                let config = AppConfig::load(app_handle).await.unwrap_or_default();
+                let audio_params = Self::audio_params(file_path, &config.conversion_settings());
+                Self::transcribe_audio_stream(file_path, url, api_key.as_deref(), language, audio_params, on_progress, cancel).await
+            }
+            TranscriptionBackend::LocalWhisper { model_path } => {
+                let on_progress = TranscriptionProgress::reporter(app_handle, recording_id, "transcribing", index, count);
+                Self::transcribe_local_whisper(file_path, model_path, language, app_handle, on_progress, cancel).await
+            }
+        }
+    }
+
+    /// Join per-chunk transcripts, shifting segment times by each chunk's start.
+    /// Speaker labels come from each chunk's own diarization and may not match across chunks.
+    fn merge_chunk_responses(responses: Vec<(AudioChunk, TranscriptionResponse)>, processing_time: f64) -> TranscriptionResponse {
+        let transcript = responses.iter()
+            .map(|(_, response)| response.transcript.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let confidences: Vec<f64> = responses.iter().filter_map(|(_, response)| response.confidence).collect();
+        let confidence = (!confidences.is_empty()).then(|| confidences.iter().sum::<f64>() / confidences.len() as f64);
+        let word_count = responses.iter()
+            .map(|(_, response)| response.word_count.unwrap_or_else(|| response.transcript.split_whitespace().count() as i32))
+            .sum();
+        let has_segments = responses.iter().any(|(_, response)| response.segments.is_some());
+        let segments = responses.into_iter()
+            .flat_map(|(chunk, response)| {
+                response.segments.unwrap_or_default().into_iter().map(move |segment| TranscriptSegment {
+                    start: segment.start + chunk.start_secs,
+                    end: segment.end + chunk.start_secs,
+                    ..segment
+                })
+            })
+            .collect::<Vec<_>>();
+
+        TranscriptionResponse {
+            transcript,
+            confidence,
+            processing_time: Some(processing_time),
+            word_count: Some(word_count),
+            segments: has_segments.then_some(segments),
+        }
+    }
+
+    /// Treat blank and "auto" languages as unset so the backend falls back to its default
+    fn normalize_language(language: Option<&str>) -> Option<&str> {
+        language
+            .map(str::trim)
+            .filter(|language| !language.is_empty() && !language.eq_ignore_ascii_case("auto"))
+    }
+
+    /// Transcribe a recording offline with the bundled whisper.cpp binary
+    ///
+    /// whisper.cpp only reads 16kHz WAV, so the recording is first decoded with FFmpeg
+    /// into a temporary file, then whisper's JSON output is mapped onto `TranscriptionResponse`.
+    /// Cancelling `cancel` kills whisper right away.
+    pub async fn transcribe_local_whisper(
+        file_path: &Path,
+        model_path: &Path,
+        language: Option<&str>,
+        app_handle: &tauri::AppHandle,
+        on_progress: impl Fn(u8) + Send + 'static,
+        cancel: &CancellationToken
+    ) -> Result<TranscriptionResponse, String> {
+        println!("Starting local Whisper transcription for file: {}", file_path.display());
+
+        if !file_path.exists() {
+            return Err(format!("Audio file does not exist: {}", file_path.display()));
+        }
+        if !model_path.exists() {
+            return Err(format!("Whisper model does not exist: {}", model_path.display()));
+        }
+
+        let ffmpeg_path = AudioConverter::find_ffmpeg(app_handle)?;
+        let whisper_path = AudioConverter::find_bundled_binary(app_handle, "whisper", "Whisper")?;
+
+        let file_path = file_path.to_owned();
+        let model_path = model_path.to_owned();
+        let language = language.map(str::to_string);
+        let cancel = cancel.clone();
+        task::spawn_blocking(move || {
+            Self::run_whisper(&file_path, &model_path, language.as_deref(), &ffmpeg_path, &whisper_path, on_progress, &cancel)
+        }).await
+        .map_err(|e| format!("Failed to spawn transcription task: {}", e))?
+    }
+
+    fn run_whisper(
+        file_path: &Path,
+        model_path: &Path,
+        language: Option<&str>,
+        ffmpeg_path: &Path,
+        whisper_path: &Path,
+        on_progress: impl Fn(u8),
+        cancel: &CancellationToken
+    ) -> Result<TranscriptionResponse, String> {
+        let started = Instant::now();
+        let output_base = AudioConverter::unique_temp_path(&std::env::temp_dir(), file_path, ".whisper");
+        let wav_path = output_base.with_extension("whisper.wav");
+        let json_path = output_base.with_extension("whisper.json");
+
+        let decode = Command::new(ffmpeg_path)
+            .args([
+                "-i", file_path.to_str().ok_or("Invalid file path")?,
+                "-ar", AUDIO_SAMPLE_RATE_STR,   // whisper.cpp expects 16kHz
+                "-ac", "1",                     // Mono
+                "-c:a", "pcm_s16le",
+                "-y",                           // Overwrite output file
+                wav_path.to_str().ok_or("Invalid temporary path")?,
+            ])
+            .output()
+            .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+
+        if !decode.status.success() {
+            return Err(format!("FFmpeg decoding failed: {}", String::from_utf8_lossy(&decode.stderr)));
+        }
+
+        let mut whisper_command = Command::new(whisper_path);
+        whisper_command.args([
+            "-m", model_path.to_str().ok_or("Invalid model path")?,
+            "-f", wav_path.to_str().ok_or("Invalid temporary path")?,
+            "--output-json",
+            "--output-file", output_base.to_str().ok_or("Invalid temporary path")?,
+            "--no-prints",
+            "--print-progress",
+        ]);
+        if let Some(language) = language {
+            whisper_command.args(["--language", language]);
+        }
+        let whisper = Self::run_with_progress(whisper_command, on_progress, cancel);
+        let _ = std::fs::remove_file(&wav_path);
+        let (status, stderr) = whisper.map_err(|e| format!("Failed to run Whisper: {}", e))?;
+        if cancel.is_cancelled() {
+            let _ = std::fs::remove_file(&json_path);
+            return Err(TRANSCRIPTION_CANCELLED_MESSAGE.to_string());
+        }
+
+        if !status.success() {
+            let _ = std::fs::remove_file(&json_path);
+            return Err(format!("Whisper transcription failed: {}", stderr));
+        }
+
+        let json = std::fs::read_to_string(&json_path)
+            .map_err(|e| format!("Failed to read Whisper output: {}", e));
+        let _ = std::fs::remove_file(&json_path);
+        let output: WhisperOutput = serde_json::from_str(&json?)
+            .map_err(|e| format!("Failed to parse Whisper output: {}", e))?;
+
+        let response = Self::response_from_whisper(output, started.elapsed().as_secs_f64());
+        println!("Local Whisper transcription completed: {} words", response.word_count.unwrap_or(0));
+        Ok(response)
+    }
+
+    /// Run whisper.cpp, reporting the progress lines it prints to stderr and returning the rest of stderr.
+    /// The process is killed as soon as `cancel` is cancelled, even while it prints nothing.
+    /// Must be called from a blocking task on the async runtime.
+    fn run_with_progress(command: Command, on_progress: impl Fn(u8), cancel: &CancellationToken) -> std::io::Result<(std::process::ExitStatus, String)> {
+        use tokio::io::AsyncBufReadExt;
+        
+        let mut command = tokio::process::Command::from(command);
+        command.stdout(Stdio::null()).stderr(Stdio::piped()).kill_on_drop(true);
+        tokio::runtime::Handle::current().block_on(async {
+            let mut child = command.spawn()?;
+            let mut stderr = String::new();
+            if let Some(pipe) = child.stderr.take() {
+                let mut lines = tokio::io::BufReader::new(pipe).lines();
+                loop {
+                    tokio::select! {
+                        _ = cancel.cancelled() => break,
+                        line = lines.next_line() => match line? {
+                            Some(line) => match Self::parse_whisper_progress(&line) {
+                                Some(percent) => on_progress(percent),
+                                None => {
+                                    stderr.push_str(&line);
+                                    stderr.push('\n');
+                                }
+                            },
+                            None => break,
+                        },
+                    }
+                }
+            }
+            
+            // Whisper may still be writing its output after closing stderr
+            let status = tokio::select! {
+                _ = cancel.cancelled() => {
+                    child.kill().await?;
+                    child.wait().await?
+                }
+                status = child.wait() => status?,
+            };
+            Ok((status, stderr))
+        })
+    }
+
+    /// Percentage from a whisper.cpp `--print-progress` line such as
+    /// "whisper_print_progress_callback: progress =  40%"
+    fn parse_whisper_progress(line: &str) -> Option<u8> {
+        let (_, value) = line.split_once("progress =")?;
+        value.trim().trim_end_matches('%').trim().parse::<u8>().ok().map(|percent| percent.min(100))
+    }
+
+    fn response_from_whisper(output: WhisperOutput, processing_time: f64) -> TranscriptionResponse {
+        let transcript = output.transcription.iter()
+            .map(|segment| segment.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let word_count = transcript.split_whitespace().count() as i32;
+        let segments = output.transcription.iter()
+            .filter_map(|segment| {
+                let offsets = segment.offsets.as_ref()?;
+                let text = segment.text.trim();
+                (!text.is_empty()).then(|| TranscriptSegment {
+                    speaker: None,
+                    start: offsets.from as f64 / 1000.0,
+                    end: offsets.to as f64 / 1000.0,
+                    text: text.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        TranscriptionResponse {
+            transcript,
+            confidence: None,
+            processing_time: Some(processing_time),
+            word_count: Some(word_count),
+            segments: (!segments.is_empty()).then_some(segments),
+        }
+    }
+
+    /// Sample rate and channel count of the audio in `file_path`: read from the header for WAV files,
+    /// otherwise the settings converted recordings (and chunks copied from them) were encoded with
+    fn audio_params(file_path: &Path, settings: &ConversionSettings) -> (u32, u16) {
+        match hound::WavReader::open(file_path) {
+            Ok(reader) => (reader.spec().sample_rate, reader.spec().channels),
+            Err(_) => (settings.sample_rate, settings.channels),
+        }
+    }
+
     /// Stream audio file directly to Next.js API without loading into memory
     /// 
     /// This function:
@@ -30,10 +476,18 @@ impl TranscriptionService {
     /// 2. Creates multipart form data with the audio stream
     /// 3. Streams to your Next.js transcription endpoint
     /// 4. Returns success/error status (transcription data stays on server)
+    ///
+    /// `audio_params` is the file's (sample rate, channels), sent alongside it.
+    /// `on_progress` receives the upload completion percentage as the file is streamed.
+    /// Cancelling `cancel` drops the request, stopping the upload.
     pub async fn transcribe_audio_stream(
         file_path: &Path,
         api_url: &str, 
-        api_key: Option<&str>
+        api_key: Option<&str>,
+        language: Option<&str>,
+        audio_params: (u32, u16),
+        on_progress: impl Fn(u8) + Send + Sync + 'static,
+        cancel: &CancellationToken
     ) -> Result<TranscriptionResponse, String> {
         println!("Starting streaming transcription for file: {}", file_path.display());
         
@@ -58,25 +512,40 @@ impl TranscriptionService {
         let file = File::open(file_path).await
             .map_err(|e| format!("Failed to open audio file: {}", e))?;
         
-        // Create async stream from file
-        let stream = FramedRead::new(file, BytesCodec::new());
+        // Create async stream from file, reporting upload progress at most once per percent
+        let mut bytes_sent = 0u64;
+        let mut last_percent = None;
+        let stream = FramedRead::new(file, BytesCodec::new()).inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                bytes_sent += chunk.len() as u64;
+                let percent = (bytes_sent * 100 / file_size.max(1)).min(100) as u8;
+                if last_percent != Some(percent) {
+                    last_percent = Some(percent);
+                    on_progress(percent);
+                }
+            }
+        });
         let file_body = reqwest::Body::wrap_stream(stream);
 
         // Create HTTP client
         let client = reqwest::Client::new();
 
         // Create multipart form with streaming file
-        let form = reqwest::multipart::Form::new()
+        let format = AudioFormat::from_path(file_path).unwrap_or_default();
+        let mut form = reqwest::multipart::Form::new()
             .part(
                 "audio",
                 reqwest::multipart::Part::stream(file_body)
                     .file_name(file_name.clone())
-                    .mime_str("audio/opus")
+                    .mime_str(format.mime_type())
                     .map_err(|e| format!("Failed to set MIME type: {}", e))?
             )
-            .text("format", "opus")
-            .text("sample_rate", AUDIO_SAMPLE_RATE_STR)
-            .text("channels", "1");
+            .text("format", format.extension())
+            .text("sample_rate", audio_params.0.to_string())
+            .text("channels", audio_params.1.to_string());
+        if let Some(language) = language {
+            form = form.text("language", language.to_string());
+        }
 
         // Build request
         let mut request_builder = client
@@ -92,10 +561,11 @@ impl TranscriptionService {
         println!("File: {} ({} bytes)", file_name, file_size);
 
         // Send request
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send streaming transcription request: {}", e))?;
+        let response = tokio::select! {
+            response = request_builder.send() => response
+                .map_err(|e| format!("Failed to send streaming transcription request: {}", e))?,
+            _ = cancel.cancelled() => return Err(TRANSCRIPTION_CANCELLED_MESSAGE.to_string()),
+        };
 
         let status = response.status();
         println!("Streaming transcription API response status: {}", status);
@@ -120,4 +590,119 @@ impl TranscriptionService {
             Err(format!("Streaming transcription failed with status {}: {}", status, error_text))
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_and_blank_languages_are_omitted() {
+        assert_eq!(TranscriptionService::normalize_language(None), None);
+        assert_eq!(TranscriptionService::normalize_language(Some("auto")), None);
+        assert_eq!(TranscriptionService::normalize_language(Some("  ")), None);
+        assert_eq!(TranscriptionService::normalize_language(Some(" de ")), Some("de"));
+    }
+
+    #[test]
+    fn test_parse_whisper_progress_lines() {
+        assert_eq!(TranscriptionService::parse_whisper_progress("whisper_print_progress_callback: progress =  40%"), Some(40));
+        assert_eq!(TranscriptionService::parse_whisper_progress("progress = 100%"), Some(100));
+        assert_eq!(TranscriptionService::parse_whisper_progress("whisper_init_from_file: loading model"), None);
+    }
+
+    #[test]
+    fn test_whisper_segments_are_joined_into_transcript() {
+        let json = r#"{
+            "result": {"language": "en"},
+            "transcription": [
+                {"timestamps": {"from": "00:00:00,000", "to": "00:00:02,000"}, "offsets": {"from": 0, "to": 2000}, "text": " Hello there."},
+                {"timestamps": {"from": "00:00:02,000", "to": "00:00:04,000"}, "offsets": {"from": 2000, "to": 4500}, "text": " General Kenobi."}
+            ]
+        }"#;
+        let output: WhisperOutput = serde_json::from_str(json).unwrap();
+        let response = TranscriptionService::response_from_whisper(output, 1.5);
+
+        assert_eq!(response.transcript, "Hello there. General Kenobi.");
+        assert_eq!(response.word_count, Some(4));
+        assert_eq!(response.processing_time, Some(1.5));
+        let segments = response.segments.unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].start, 2.0);
+        assert_eq!(segments[1].end, 4.5);
+        assert_eq!(segments[1].text, "General Kenobi.");
+    }
+
+    #[test]
+    fn test_chunk_responses_are_joined_with_offset_segments() {
+        let chunk = |start_secs: f64| AudioChunk { path: PathBuf::from("chunk.opus"), start_secs };
+        let response = |transcript: &str, segments: Option<Vec<TranscriptSegment>>| TranscriptionResponse {
+            transcript: transcript.to_string(),
+            confidence: Some(0.8),
+            processing_time: Some(1.0),
+            word_count: None,
+            segments,
+        };
+        let segment = TranscriptSegment { speaker: Some("A".to_string()), start: 1.0, end: 2.5, text: "Next part.".to_string() };
+        let merged = TranscriptionService::merge_chunk_responses(vec![
+            (chunk(0.0), response(" First part. ", None)),
+            (chunk(600.0), response("Next part.", Some(vec![segment]))),
+        ], 4.0);
+
+        assert_eq!(merged.transcript, "First part. Next part.");
+        assert_eq!(merged.word_count, Some(4));
+        assert_eq!(merged.processing_time, Some(4.0));
+        assert_eq!(merged.confidence, Some(0.8));
+        let segments = merged.segments.unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start, 601.0);
+        assert_eq!(segments[0].end, 602.5);
+        assert_eq!(segments[0].speaker.as_deref(), Some("A"));
+    }
+
+    #[test]
+    fn test_overall_percent_spreads_chunks_evenly() {
+        assert_eq!(TranscriptionProgress::overall_percent(0, 1, 40), 40);
+        assert_eq!(TranscriptionProgress::overall_percent(1, 4, 0), 25);
+        assert_eq!(TranscriptionProgress::overall_percent(3, 4, 100), 100);
+    }
+
+    #[test]
+    fn test_audio_params_come_from_wav_header_or_conversion_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("stereo.wav");
+        let spec = hound::WavSpec { channels: 2, sample_rate: 44100, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        hound::WavWriter::create(&wav_path, spec).unwrap().finalize().unwrap();
+        let settings = ConversionSettings { sample_rate: 24000, channels: 1, ..ConversionSettings::default() };
+        assert_eq!(TranscriptionService::audio_params(&wav_path, &settings), (44100, 2));
+        
+        let opus_path = dir.path().join("converted.opus");
+        std::fs::write(&opus_path, b"OggS").unwrap();
+        assert_eq!(TranscriptionService::audio_params(&opus_path, &settings), (24000, 1));
+    }
+
+    #[test]
+    fn test_segments_are_optional_and_round_trip() {
+        let legacy: TranscriptionResponse = serde_json::from_str(
+            r#"{"transcript": "hi", "confidence": null, "processing_time": null, "word_count": 1}"#
+        ).unwrap();
+        assert!(legacy.segments.is_none());
+
+        let diarized: TranscriptionResponse = serde_json::from_str(r#"{
+            "transcript": "Hi. Hello.",
+            "confidence": 0.9,
+            "processing_time": 2.0,
+            "word_count": 2,
+            "segments": [
+                {"speaker": "A", "start": 0.0, "end": 1.0, "text": "Hi."},
+                {"start": 1.0, "end": 2.0, "text": "Hello."}
+            ]
+        }"#).unwrap();
+        let segments = diarized.segments.as_ref().unwrap();
+        assert_eq!(segments[0].speaker.as_deref(), Some("A"));
+        assert_eq!(segments[1].speaker, None);
+
+        let round_trip: TranscriptionResponse = serde_json::from_str(&serde_json::to_string(&diarized).unwrap()).unwrap();
+        assert_eq!(round_trip.segments, diarized.segments);
+    }
+}
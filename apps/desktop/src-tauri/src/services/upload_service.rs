@@ -0,0 +1,103 @@
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+use tokio::fs::File;
+use tokio_util::codec::{BytesCodec, FramedRead};
+use crate::events::EventEmitter;
+use super::audio_converter::AudioFormat;
+
+/// Progress payload emitted while a recording is uploading
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadProgress {
+    pub recording_id: String,
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+pub struct UploadService;
+
+impl UploadService {
+    /// Stream a recording to `upload_url` as multipart form data, emitting progress events
+    pub async fn upload_file(
+        file_path: &Path,
+        upload_url: &str,
+        api_key: Option<&str>,
+        recording_id: &str,
+        app_handle: &AppHandle
+    ) -> Result<(), String> {
+        if !file_path.exists() {
+            return Err(format!("Audio file does not exist: {}", file_path.display()));
+        }
+        
+        let total_bytes = std::fs::metadata(file_path)
+            .map_err(|e| format!("Failed to get file metadata: {}", e))?
+            .len();
+        
+        let file_name = file_path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("recording.opus")
+            .to_string();
+        let mime_type = match AudioFormat::from_path(file_path) {
+            Some(format) => format.mime_type(),
+            None => "audio/wav",
+        };
+        
+        let file = File::open(file_path).await
+            .map_err(|e| format!("Failed to open audio file: {}", e))?;
+        
+        // Count bytes as reqwest pulls them from the file, reporting at most once per percent
+        let progress_handle = app_handle.clone();
+        let progress_id = recording_id.to_string();
+        let mut bytes_sent = 0u64;
+        let mut last_percent = 0u64;
+        let stream = FramedRead::new(file, BytesCodec::new()).inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                bytes_sent += chunk.len() as u64;
+                let percent = bytes_sent * 100 / total_bytes.max(1);
+                if percent > last_percent || bytes_sent == total_bytes {
+                    last_percent = percent;
+                    EventEmitter::upload_progress(&progress_handle, &UploadProgress {
+                        recording_id: progress_id.clone(),
+                        bytes_sent,
+                        total_bytes,
+                    });
+                }
+            }
+        });
+        
+        let part = reqwest::multipart::Part::stream_with_length(reqwest::Body::wrap_stream(stream), total_bytes)
+            .file_name(file_name.clone())
+            .mime_str(mime_type)
+            .map_err(|e| format!("Failed to set MIME type: {}", e))?;
+        let form = reqwest::multipart::Form::new()
+            .part("audio", part)
+            .text("recording_id", recording_id.to_string());
+        
+        let mut request_builder = reqwest::Client::new()
+            .post(upload_url)
+            .multipart(form);
+        if let Some(key) = api_key {
+            request_builder = request_builder.header("Authorization", format!("Bearer {}", key));
+        }
+        
+        println!("Uploading {} ({} bytes) to: {}", file_name, total_bytes, upload_url);
+        
+        let response = request_builder
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send upload request: {}", e))?;
+        
+        let status = response.status();
+        if status.is_success() {
+            println!("Upload completed: {}", file_name);
+            Ok(())
+        } else {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| format!("HTTP {}", status));
+            Err(format!("Upload failed with status {}: {}", status, error_text))
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use tokio::task;
+use crate::constants::*;
+use super::audio_converter::AudioConverter;
+
+/// Cached waveform peaks stored next to a recording as `<filename>.peaks.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PeaksCache {
+    buckets: usize,
+    peaks: Vec<f32>,
+}
+
+/// Waveform generation for drawing recordings in the UI
+pub struct Waveform;
+
+impl Waveform {
+    /// Get the cache file path for a recording's peaks
+    pub fn peaks_path(recording_path: &Path) -> PathBuf {
+        let mut file_name = recording_path.file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        file_name.push(".peaks.json");
+        recording_path.with_file_name(file_name)
+    }
+
+    /// Get the cached peaks for a recording at whatever bucket count they were generated with,
+    /// without decoding the audio
+    pub fn cached_peaks(recording_path: &Path) -> Option<Vec<f32>> {
+        let content = fs::read_to_string(Self::peaks_path(recording_path)).ok()?;
+        serde_json::from_str::<PeaksCache>(&content).ok().map(|cache| cache.peaks)
+    }
+
+    /// Validate a requested bucket count, clamping it to `MAX_WAVEFORM_BUCKETS`
+    pub fn bucket_count(buckets: usize) -> Result<usize, String> {
+        if buckets == 0 {
+            return Err("Bucket count must be greater than zero".to_string());
+        }
+        Ok(buckets.min(MAX_WAVEFORM_BUCKETS))
+    }
+
+    /// Get min/max peaks for a recording, reading from the cache when possible
+    ///
+    /// The result contains `buckets` interleaved `[min, max]` pairs in the range -1.0 to 1.0,
+    /// with `buckets` clamped to `MAX_WAVEFORM_BUCKETS`.
+    pub async fn get_peaks(recording_path: &Path, buckets: usize, app_handle: &tauri::AppHandle) -> Result<Vec<f32>, String> {
+        let buckets = Self::bucket_count(buckets)?;
+
+        let cache_path = Self::peaks_path(recording_path);
+        if let Ok(content) = fs::read_to_string(&cache_path) {
+            match serde_json::from_str::<PeaksCache>(&content) {
+                Ok(cache) if cache.buckets == buckets => return Ok(cache.peaks),
+                Ok(_) => println!("Cached peaks have a different bucket count, regenerating"),
+                Err(e) => eprintln!("Ignoring unreadable peaks cache {}: {}", cache_path.display(), e),
+            }
+        }
+
+        let recording_path_owned = recording_path.to_owned();
+        let ffmpeg_path = AudioConverter::find_ffmpeg(app_handle).ok();
+        let samples = task::spawn_blocking(move || {
+            Self::decode_mono_samples(&recording_path_owned, ffmpeg_path.as_deref())
+        }).await
+        .map_err(|e| format!("Failed to spawn waveform task: {}", e))??;
+
+        let peaks = Self::compute_peaks(&samples, buckets);
+
+        let cache = PeaksCache { buckets, peaks: peaks.clone() };
+        match serde_json::to_string(&cache) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&cache_path, json) {
+                    eprintln!("Failed to write peaks cache: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Failed to serialize peaks cache: {}", e),
+        }
+
+        Ok(peaks)
+    }
+
+    /// Decode a recording into mono f32 samples (WAV via hound, anything else via FFmpeg)
+    fn decode_mono_samples(path: &Path, ffmpeg_path: Option<&Path>) -> Result<Vec<f32>, String> {
+        let is_wav = path.extension().and_then(|ext| ext.to_str()) == Some("wav");
+        if is_wav {
+            return Self::decode_wav_mono(path);
+        }
+
+        let ffmpeg_path = ffmpeg_path.ok_or_else(|| "FFmpeg is required to decode this recording".to_string())?;
+        let output = Command::new(ffmpeg_path)
+            .args([
+                "-i", path.to_str().ok_or("Invalid recording path")?,
+                "-f", "f32le",              // Raw 32-bit float PCM
+                "-ac", "1",                 // Mono
+                "-ar", AUDIO_SAMPLE_RATE_STR,
+                "-",                        // Write to stdout
+            ])
+            .output()
+            .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("FFmpeg decoding failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+
+        Ok(output.stdout
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect())
+    }
+
+    /// Decode a WAV file and mix it down to mono f32 samples
+    fn decode_wav_mono(path: &Path) -> Result<Vec<f32>, String> {
+        let mut reader = hound::WavReader::open(path)
+            .map_err(|e| format!("Failed to open WAV file: {}", e))?;
+        let spec = reader.spec();
+        let channels = usize::from(spec.channels.max(1));
+
+        let samples: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.samples::<f32>()
+                .map(|s| s.unwrap_or(0.0))
+                .collect(),
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader.samples::<i32>()
+                    .map(|s| s.unwrap_or(0) as f32 / scale)
+                    .collect()
+            }
+        };
+
+        Ok(samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect())
+    }
+
+    /// Reduce samples to `buckets` interleaved min/max pairs
+    fn compute_peaks(samples: &[f32], buckets: usize) -> Vec<f32> {
+        let mut peaks = Vec::with_capacity(buckets * 2);
+        if samples.is_empty() {
+            peaks.resize(buckets * 2, 0.0);
+            return peaks;
+        }
+
+        for bucket in 0..buckets {
+            let start = bucket * samples.len() / buckets;
+            let end = ((bucket + 1) * samples.len() / buckets).max(start + 1).min(samples.len());
+            let (min, max) = samples[start.min(end - 1)..end]
+                .iter()
+                .fold((0.0f32, 0.0f32), |(min, max), &s| (min.min(s), max.max(s)));
+            peaks.push(min.clamp(-1.0, 1.0));
+            peaks.push(max.clamp(-1.0, 1.0));
+        }
+
+        peaks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_peaks_min_max_per_bucket() {
+        let samples = vec![0.1, -0.5, 0.3, 0.9, -0.2, 0.0];
+        let peaks = Waveform::compute_peaks(&samples, 2);
+        assert_eq!(peaks, vec![-0.5, 0.3, -0.2, 0.9]);
+    }
+
+    #[test]
+    fn test_compute_peaks_more_buckets_than_samples() {
+        let peaks = Waveform::compute_peaks(&[0.5], 3);
+        assert_eq!(peaks.len(), 6, "Should always return two values per bucket");
+        assert!(peaks.iter().all(|p| (-1.0..=1.0).contains(p)));
+    }
+
+    #[test]
+    fn test_bucket_count_rejects_zero_and_clamps() {
+        assert!(Waveform::bucket_count(0).is_err());
+        assert_eq!(Waveform::bucket_count(200), Ok(200));
+        assert_eq!(Waveform::bucket_count(usize::MAX), Ok(MAX_WAVEFORM_BUCKETS));
+    }
+
+    #[test]
+    fn test_peaks_path_sits_next_to_recording() {
+        let path = Path::new("/tmp/recordings/recording_20240826_143022.opus");
+        assert_eq!(
+            Waveform::peaks_path(path),
+            PathBuf::from("/tmp/recordings/recording_20240826_143022.opus.peaks.json")
+        );
+    }
+}
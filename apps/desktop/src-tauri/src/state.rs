@@ -1,26 +1,208 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::path::PathBuf;
 use chrono::{DateTime, Utc};
-use crate::meeting_detector::MeetingDetector;
+use crate::meeting_detector::{MeetingAction, MeetingDetector};
+use crate::constants::{DEFAULT_INPUT_GAIN, DEFAULT_PLAYBACK_RATE, DEFAULT_PLAYBACK_VOLUME, SECONDS_PER_MINUTE};
 
 /// Represents a single audio recording with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recording {
     pub id: String,
     pub filename: String,
+    /// Display duration such as "1:05", derived from `duration_ms`
     pub duration: String,
+    /// Recorded audio length in milliseconds
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Size of the recording file on disk
+    #[serde(default)]
+    pub file_size_bytes: u64,
     pub timestamp: DateTime<Utc>,
     pub status: RecordingStatus,
+    /// Human-friendly label, e.g. the title of the meeting that was recorded
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Whether a transcript has been saved next to the recording
+    #[serde(default)]
+    pub has_transcript: bool,
+    /// Samples lost while recording because the disk writer fell behind (0 = no gaps)
+    #[serde(default)]
+    pub dropped_samples: u64,
+    /// Still a WAV because converting it failed; retried with `convert_recording`
+    #[serde(default)]
+    pub needs_conversion: bool,
+    /// WAV kept alongside the converted file when `keep_original_wav` is on; `filename` stays the playable file
+    #[serde(default)]
+    pub original_filename: Option<String>,
+    /// SHA-256 of the captured WAV (taken before conversion), used to find duplicates (None until computed)
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Timestamped notes, kept in timestamp order
+    #[serde(default)]
+    pub notes: Vec<Note>,
+}
+
+/// A note pinned to a position in a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    /// Position in the recording the note refers to
+    pub timestamp_ms: u64,
+    pub text: String,
+}
+
+impl Recording {
+    /// Insert a note after any existing notes at or before its timestamp, returning its index
+    pub fn add_note(&mut self, note: Note) -> usize {
+        let index = self.notes.partition_point(|n| n.timestamp_ms <= note.timestamp_ms);
+        self.notes.insert(index, note);
+        index
+    }
+    
+    /// A local recording with empty metadata, for tests to adjust with struct update syntax
+    #[cfg(test)]
+    pub fn test(id: &str, filename: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            filename: filename.to_string(),
+            duration: "0:00".to_string(),
+            duration_ms: 0,
+            file_size_bytes: 0,
+            timestamp: Utc::now(),
+            status: RecordingStatus::Local,
+            title: None,
+            has_transcript: false,
+            dropped_samples: 0,
+            needs_conversion: false,
+            original_filename: None,
+            content_hash: None,
+            notes: Vec::new(),
+        }
+    }
+}
+
+/// Latest recordings list waiting to be written to recordings.json; writes are debounced
+#[derive(Debug, Default)]
+pub struct PendingMetadata {
+    /// Held while writing so flushes land on disk in order
+    pub recordings: Mutex<Option<Vec<Recording>>>,
+    pub flush_scheduled: AtomicBool,
+}
+
+/// Format a duration in milliseconds as "m:ss"
+pub fn format_duration(duration_ms: u64) -> String {
+    let seconds = duration_ms / 1000;
+    format!("{}:{:02}", seconds / SECONDS_PER_MINUTE, seconds % SECONDS_PER_MINUTE)
+}
+
+/// Sort order for paged recording listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Ascending,
+    #[default]
+    Descending,
+}
+
+/// One page of recordings plus the total number available
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingsPage {
+    pub recordings: Vec<Recording>,
+    pub total_count: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl RecordingsPage {
+    /// Sort recordings by timestamp and take the requested slice
+    ///
+    /// Only the `max_visible` most recent recordings are paged through (0 = unlimited).
+    pub fn from_recordings(recordings: &[Recording], offset: usize, limit: usize, sort: SortOrder, max_visible: usize) -> Self {
+        let mut sorted: Vec<&Recording> = recordings.iter().collect();
+        sorted.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+        if max_visible > 0 {
+            sorted.truncate(max_visible);
+        }
+        if sort == SortOrder::Ascending {
+            sorted.reverse();
+        }
+        
+        Self {
+            total_count: sorted.len(),
+            recordings: sorted.into_iter().skip(offset).take(limit).cloned().collect(),
+            offset,
+            limit,
+        }
+    }
+}
+
+/// Outcome of deleting a single recording in a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteOutcome {
+    pub recording_id: String,
+    pub deleted: bool,
+    pub error: Option<String>,
+}
+
+/// Files in the recordings directory that don't match the metadata, and vice versa
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecordingsAudit {
+    /// Recording, waveform and transcript files no recording references (relative to the recordings directory)
+    pub orphan_files: Vec<String>,
+    /// Filenames of recordings whose audio file is gone
+    pub missing_files: Vec<String>,
+}
+
+/// Everything the review view shows for one recording, fetched in a single call
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingDetail {
+    pub recording: Recording,
+    pub transcript: Option<crate::services::transcription_service::TranscriptionResponse>,
+    /// Interleaved `[min, max]` pairs, only when peaks were already generated and cached
+    pub waveform_peaks: Option<Vec<f32>>,
+    pub notes: Vec<Note>,
+}
+
+/// A recording matching a search query, with the transcript passages that matched
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingMatch {
+    pub recording: Recording,
+    pub title_matched: bool,
+    pub snippets: Vec<SearchSnippet>,
+    /// Relevance used to order results (currently the number of matches)
+    pub score: u32,
+}
+
+/// Transcript text around a match; timed when the transcript has segments
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchSnippet {
+    pub text: String,
+    /// Segment start/end in seconds
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub speaker: Option<String>,
 }
 
 /// Status of a recording in the system
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RecordingStatus {
-    Local,     // Recorded locally, not yet uploaded
-    Uploaded,  // Successfully uploaded/processed
-    Failed,    // Upload or processing failed
+    Local,        // Recorded locally, not yet uploaded
+    Uploaded,     // Successfully uploaded/processed
+    Failed,       // Upload or processing failed
+    Processing,   // Just stopped; being trimmed and converted
+    Transcribing, // Transcription in progress
+}
+
+/// What started a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingSource {
+    /// The user, from the UI, tray or local API
+    Manual,
+    /// The meeting watcher, for a detected meeting
+    Meeting,
 }
 
 /// Current state of the recording system
@@ -29,17 +211,119 @@ pub enum RecordingState {
     Idle,
     Recording { 
         start_time: DateTime<Utc>,
+        /// Time spent paused since `start_time`, excluded from the elapsed time
+        paused_ms: u64,
         #[allow(dead_code)]
         file_path: PathBuf,
+        title: Option<String>,
+        source: RecordingSource,
     },
     Paused { 
         start_time: DateTime<Utc>, 
-        elapsed: u64,
+        /// Time spent recording (not paused) before this pause
+        elapsed_ms: u64,
         #[allow(dead_code)]
         file_path: PathBuf,
+        title: Option<String>,
+        source: RecordingSource,
     },
 }
 
+impl RecordingState {
+    /// What started the recording in progress, or None when idle
+    pub fn source(&self) -> Option<RecordingSource> {
+        match self {
+            RecordingState::Idle => None,
+            RecordingState::Recording { source, .. } | RecordingState::Paused { source, .. } => Some(*source),
+        }
+    }
+    
+
+    /// Time spent recording as of `now`, leaving out pauses
+    pub fn active_ms(&self, now: DateTime<Utc>) -> u64 {
+        match self {
+            RecordingState::Idle => 0,
+            RecordingState::Recording { start_time, paused_ms, .. } => {
+                ((now - *start_time).num_milliseconds().max(0) as u64).saturating_sub(*paused_ms)
+            }
+            RecordingState::Paused { elapsed_ms, .. } => *elapsed_ms,
+        }
+    }
+}
+
+/// Payload of the `recording-countdown` event, emitted once per second before recording starts
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingCountdown {
+    pub remaining_secs: u32,
+    /// True when the countdown was cancelled (recording will not start)
+    pub cancelled: bool,
+}
+
+/// Payload of the `recordings-pruned` event, emitted when the retention sweep deletes recordings
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingsPruned {
+    pub recording_ids: Vec<String>,
+    /// Combined size of the deleted recordings
+    pub freed_bytes: u64,
+    pub retention_days: u32,
+}
+
+/// Payload of the `recording-deduplicated` event, emitted when a new recording is discarded
+/// because its audio is identical to a saved one
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingDeduplicated {
+    pub recording_id: String,
+    pub duplicate_of: String,
+}
+
+/// Snapshot of the current recording for the UI, with an authoritative elapsed time
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingInfo {
+    /// "idle", "recording" or "paused"
+    pub state: String,
+    /// Time recorded so far excluding pauses, matching the audio in the file
+    pub elapsed_ms: u64,
+    pub file_path: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl RecordingInfo {
+    /// Describe `recording_state` as of `now`
+    pub fn from_state(recording_state: &RecordingState, now: DateTime<Utc>) -> Self {
+        match recording_state {
+            RecordingState::Idle => Self {
+                state: "idle".to_string(),
+                elapsed_ms: 0,
+                file_path: None,
+                started_at: None,
+            },
+            RecordingState::Recording { start_time, file_path, .. } => Self {
+                state: "recording".to_string(),
+                elapsed_ms: recording_state.active_ms(now),
+                file_path: Some(file_path.display().to_string()),
+                started_at: Some(*start_time),
+            },
+            RecordingState::Paused { start_time, elapsed_ms, file_path, .. } => Self {
+                state: "paused".to_string(),
+                elapsed_ms: *elapsed_ms,
+                file_path: Some(file_path.display().to_string()),
+                started_at: Some(*start_time),
+            },
+        }
+    }
+}
+
+/// Snapshot of the current playback for the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackInfo {
+    pub recording_id: String,
+    pub filename: String,
+    /// "playing" or "paused"
+    pub state: String,
+    pub position_ms: u64,
+    pub duration_ms: u64,
+}
+
 /// Current state of audio playback
 #[derive(Debug, Clone)]
 pub enum PlaybackState {
@@ -49,6 +333,10 @@ pub enum PlaybackState {
         filename: String,
         start_time: DateTime<Utc>,
     },
+    Paused {
+        recording_id: String,
+        filename: String,
+    },
 }
 
 /// Main application state containing all shared data
@@ -57,8 +345,49 @@ pub struct AppState {
     pub recording_state: Arc<Mutex<RecordingState>>,
     pub playback_state: Arc<Mutex<PlaybackState>>,
     pub recordings: Arc<Mutex<Vec<Recording>>>,
+    /// Metadata queued for the next debounced recordings.json write
+    pub pending_metadata: Arc<PendingMetadata>,
     pub audio_recorder: Arc<Mutex<crate::audio_system::RecorderState>>,
     pub meeting_detector: Arc<MeetingDetector>,
+    /// Thread reacting to meetings starting and ending while detection is on
+    pub meeting_watcher: Arc<Mutex<Option<crate::meeting_detector::MeetingWatcher>>>,
+    pub input_device: Arc<Mutex<Option<String>>>,
+    /// Channel layout new recordings are written with
+    pub record_channels: Arc<Mutex<crate::audio_system::RecordChannels>>,
+    /// Sample format new recordings are written with
+    pub wav_bit_depth: Arc<Mutex<crate::audio_system::WavBitDepth>>,
+    /// Sample rate new recordings are resampled to (None = the input device's rate)
+    pub record_sample_rate: Arc<Mutex<Option<u32>>>,
+    /// Input gain multiplier, stored as `f32` bits so the input callback can read it lock-free
+    pub input_gain: Arc<AtomicU32>,
+    /// Set while a recording is paused; the input callback drops captured audio instead of writing it
+    pub capture_paused: Arc<AtomicBool>,
+    /// Noise gate threshold in dBFS applied while recording (None = gate off)
+    pub noise_gate_threshold_db: Arc<Mutex<Option<f32>>>,
+    /// Audio captured before recording starts, filled while pre-roll is enabled
+    pub preroll_buffer: Arc<crate::audio_system::PrerollBuffer>,
+    /// Output device used for playback (None = system default)
+    pub output_device: Arc<Mutex<Option<String>>>,
+    /// ID of the countdown running before a recording starts; cleared to cancel it
+    pub recording_countdown: Arc<Mutex<Option<String>>>,
+    /// `started_at` of the meeting being auto-recorded, guarding against starting it twice
+    pub recording_meeting: Arc<Mutex<Option<DateTime<Utc>>>>,
+    /// Playback rate multiplier, stored as `f32` bits so the output callback can read it lock-free
+    pub playback_rate: Arc<AtomicU32>,
+    /// Playback volume (0.0-1.0), stored as `f32` bits so the output callback can read it lock-free
+    pub playback_volume: Arc<AtomicU32>,
+    /// Position of the current playback, updated by the output callback
+    pub playback_progress: Arc<crate::audio_system::PlaybackProgress>,
+    /// What to do when a meeting starts (notify, record automatically, or nothing)
+    pub meeting_action: Arc<Mutex<MeetingAction>>,
+    /// User-configured recordings directory (None = app data directory)
+    pub recordings_dir: Arc<Mutex<Option<PathBuf>>>,
+    /// Whether the bundled FFmpeg binary was found and runs (otherwise recordings stay as WAV)
+    pub ffmpeg_available: Arc<AtomicBool>,
+    /// Cancellation tokens of in-flight transcriptions, by recording ID
+    pub transcriptions: Arc<Mutex<HashMap<String, tokio_util::sync::CancellationToken>>>,
+    /// The running local API server (None = not running)
+    pub local_api: Arc<Mutex<Option<crate::local_api::LocalApiServer>>>,
 }
 
 impl Default for AppState {
@@ -67,8 +396,29 @@ impl Default for AppState {
             recording_state: Arc::new(Mutex::new(RecordingState::Idle)),
             playback_state: Arc::new(Mutex::new(PlaybackState::Idle)),
             recordings: Arc::new(Mutex::new(Vec::new())),
+            pending_metadata: Arc::new(PendingMetadata::default()),
             audio_recorder: Arc::new(Mutex::new(crate::audio_system::RecorderState::new())),
             meeting_detector: Arc::new(MeetingDetector::new()),
+            meeting_watcher: Arc::new(Mutex::new(None)),
+            input_device: Arc::new(Mutex::new(None)),
+            record_channels: Arc::new(Mutex::new(crate::audio_system::RecordChannels::default())),
+            wav_bit_depth: Arc::new(Mutex::new(crate::audio_system::WavBitDepth::default())),
+            record_sample_rate: Arc::new(Mutex::new(None)),
+            input_gain: Arc::new(AtomicU32::new(DEFAULT_INPUT_GAIN.to_bits())),
+            capture_paused: Arc::new(AtomicBool::new(false)),
+            noise_gate_threshold_db: Arc::new(Mutex::new(None)),
+            preroll_buffer: Arc::new(crate::audio_system::PrerollBuffer::default()),
+            output_device: Arc::new(Mutex::new(None)),
+            recording_countdown: Arc::new(Mutex::new(None)),
+            recording_meeting: Arc::new(Mutex::new(None)),
+            playback_rate: Arc::new(AtomicU32::new(DEFAULT_PLAYBACK_RATE.to_bits())),
+            playback_volume: Arc::new(AtomicU32::new(DEFAULT_PLAYBACK_VOLUME.to_bits())),
+            playback_progress: Arc::new(crate::audio_system::PlaybackProgress::default()),
+            meeting_action: Arc::new(Mutex::new(MeetingAction::default())),
+            recordings_dir: Arc::new(Mutex::new(None)),
+            ffmpeg_available: Arc::new(AtomicBool::new(false)),
+            transcriptions: Arc::new(Mutex::new(HashMap::new())),
+            local_api: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -79,4 +429,98 @@ impl AppState {
         let mut audio_recorder = self.audio_recorder.lock().unwrap();
         audio_recorder.initialize().map_err(|e| e.to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn recordings(count: usize) -> Vec<Recording> {
+        let now = Utc::now();
+        (0..count)
+            .map(|i| Recording {
+                duration: "0:30".to_string(),
+                duration_ms: 30_000,
+                timestamp: now - Duration::minutes(i as i64),
+                ..Recording::test(&i.to_string(), &format!("recording_{}.opus", i))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_page_slices_newest_first_by_default() {
+        let page = RecordingsPage::from_recordings(&recordings(5), 1, 2, SortOrder::default(), 0);
+        let ids: Vec<_> = page.recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+        assert_eq!(page.total_count, 5);
+    }
+
+    #[test]
+    fn test_page_ascending_and_past_the_end() {
+        let all = recordings(3);
+        let page = RecordingsPage::from_recordings(&all, 0, 10, SortOrder::Ascending, 0);
+        let ids: Vec<_> = page.recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "1", "0"]);
+        
+        let empty = RecordingsPage::from_recordings(&all, 5, 10, SortOrder::Ascending, 0);
+        assert!(empty.recordings.is_empty());
+        assert_eq!(empty.total_count, 3);
+    }
+
+    #[test]
+    fn test_page_only_covers_max_visible_most_recent() {
+        let all = recordings(5);
+        let page = RecordingsPage::from_recordings(&all, 0, 10, SortOrder::Descending, 3);
+        let ids: Vec<_> = page.recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["0", "1", "2"]);
+        assert_eq!(page.total_count, 3);
+        
+        let page = RecordingsPage::from_recordings(&all, 1, 10, SortOrder::Ascending, 3);
+        let ids: Vec<_> = page.recordings.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "0"]);
+    }
+
+    #[test]
+    fn test_notes_stay_in_timestamp_order() {
+        let mut recording = recordings(1).remove(0);
+        let note = |timestamp_ms: u64, text: &str| Note { timestamp_ms, text: text.to_string() };
+        assert_eq!(recording.add_note(note(30_000, "b")), 0);
+        assert_eq!(recording.add_note(note(5_000, "a")), 0);
+        assert_eq!(recording.add_note(note(30_000, "c")), 2);
+        let texts: Vec<_> = recording.notes.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(65_999), "1:05");
+        assert_eq!(format_duration(3_600_000), "60:00");
+    }
+
+    #[test]
+    fn test_recording_info_elapsed_from_state() {
+        let now = Utc::now();
+        let start_time = now - Duration::seconds(90);
+        let file_path = PathBuf::from("/tmp/recording.wav");
+        
+        let recording = RecordingState::Recording { start_time, paused_ms: 0, file_path: file_path.clone(), title: None, source: RecordingSource::Manual };
+        let info = RecordingInfo::from_state(&recording, now);
+        assert_eq!(info.state, "recording");
+        assert_eq!(info.elapsed_ms, 90_000);
+        assert_eq!(info.started_at, Some(start_time));
+        
+        let resumed = RecordingState::Recording { start_time, paused_ms: 30_000, file_path: file_path.clone(), title: None, source: RecordingSource::Manual };
+        assert_eq!(RecordingInfo::from_state(&resumed, now).elapsed_ms, 60_000);
+        
+        let paused = RecordingState::Paused { start_time, elapsed_ms: 42_000, file_path, title: None, source: RecordingSource::Meeting };
+        assert_eq!(RecordingInfo::from_state(&paused, now).elapsed_ms, 42_000);
+        assert_eq!(paused.source(), Some(RecordingSource::Meeting));
+        assert_eq!(RecordingState::Idle.source(), None);
+        
+        let idle = RecordingInfo::from_state(&RecordingState::Idle, now);
+        assert_eq!(idle.elapsed_ms, 0);
+        assert!(idle.file_path.is_none());
+    }
+}
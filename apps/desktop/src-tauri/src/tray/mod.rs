@@ -1,9 +1,77 @@
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Listener, Manager};
+use crate::constants::{SECONDS_PER_MINUTE, TRAY_STATUS_INTERVAL_MS};
 use crate::events::EventEmitter;
-use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem};
+use crate::constants::TRAY_RECENT_RECORDINGS;
+use crate::state::{PlaybackState, Recording, RecordingInfo, RecordingState};
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
+use tauri::Wry;
 
-// Function to update tray menu based on recording state
-pub fn update_tray_menu(app: &AppHandle, is_recording: bool) -> Result<(), Box<dyn std::error::Error>> {
+const IDLE_TOOLTIP: &str = "Content Recorder";
+
+// Menu ids of recent recording items are this prefix followed by the recording id
+const RECENT_ITEM_PREFIX: &str = "recent:";
+
+// Tray icons (embedded in binary)
+const IDLE_ICON: &[u8] = include_bytes!("../../icons/icon.png");
+const RECORDING_ICON: &[u8] = include_bytes!("../../icons/tray-recording.png");
+
+// Swap the tray icon to show whether a recording is in progress
+pub fn set_tray_recording(app: &AppHandle, is_recording: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let icon_bytes = if is_recording { RECORDING_ICON } else { IDLE_ICON };
+    if let Some(tray) = app.tray_by_id("main") {
+        tray.set_icon(Some(tauri::image::Image::from_bytes(icon_bytes)?))?;
+    }
+    Ok(())
+}
+
+// Tooltip text for the current recording, e.g. "Recording 02:14"; `None` when idle
+fn recording_status_text(info: &RecordingInfo) -> Option<String> {
+    let label = match info.state.as_str() {
+        "recording" => "Recording",
+        "paused" => "Paused",
+        _ => return None,
+    };
+    let seconds = info.elapsed_ms / 1000;
+    Some(format!("{} {:02}:{:02}", label, seconds / SECONDS_PER_MINUTE, seconds % SECONDS_PER_MINUTE))
+}
+
+// Keep the tray tooltip (and the menu bar title on macOS) in sync with the recording state
+fn spawn_tray_status_updater(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_status: Option<Option<String>> = None;
+        loop {
+            std::thread::sleep(std::time::Duration::from_millis(TRAY_STATUS_INTERVAL_MS));
+            
+            let Some(state) = app.try_state::<crate::AppState>() else { continue };
+            let info = {
+                let recording_state = state.recording_state.lock().unwrap();
+                RecordingInfo::from_state(&recording_state, chrono::Utc::now())
+            };
+            let status = recording_status_text(&info);
+            if last_status.as_ref() == Some(&status) {
+                continue;
+            }
+            
+            if let Some(tray) = app.tray_by_id("main") {
+                let _ = tray.set_tooltip(Some(status.as_deref().unwrap_or(IDLE_TOOLTIP)));
+                #[cfg(target_os = "macos")]
+                let _ = tray.set_title(status.as_deref());
+            }
+            last_status = Some(status);
+        }
+    });
+}
+
+// Build the tray menu for the current recording state, recent recordings and playback
+fn build_tray_menu(app: &AppHandle, is_recording: bool) -> Result<Menu<Wry>, Box<dyn std::error::Error>> {
+    let (recent, is_playing) = match app.try_state::<crate::AppState>() {
+        Some(state) => {
+            let recent = recent_recordings(&state.recordings.lock().unwrap(), TRAY_RECENT_RECORDINGS);
+            let is_playing = !matches!(*state.playback_state.lock().unwrap(), PlaybackState::Idle);
+            (recent, is_playing)
+        }
+        None => (Vec::new(), false),
+    };
 
     // Create tray menu items with dynamic text
     let open_window = MenuItemBuilder::with_id("open_window", "Open App Window").build(app)?;
@@ -13,17 +81,52 @@ pub fn update_tray_menu(app: &AppHandle, is_recording: bool) -> Result<(), Box<d
     let separator2 = PredefinedMenuItem::separator(app)?;
     let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
     
-    let menu = MenuBuilder::new(app)
+    let mut recent_menu = SubmenuBuilder::new(app, "Recent Recordings");
+    for (id, label) in &recent {
+        recent_menu = recent_menu.text(format!("{}{}", RECENT_ITEM_PREFIX, id), label);
+    }
+    let recent_menu = recent_menu.enabled(!recent.is_empty()).build()?;
+    
+    let mut menu = MenuBuilder::new(app)
         .items(&[
             &open_window,
             &separator1, 
             &start_stop_recording,
+            &recent_menu,
+        ]);
+    if is_playing {
+        menu = menu.text("stop_playback", "Stop Playback");
+    }
+    let menu = menu
+        .items(&[
             &separator2,
             &quit
         ])
         .build()?;
     
-    // Update the tray icon's menu
+    Ok(menu)
+}
+
+// Most recent recordings as (id, label) pairs, labelled by title or filename
+fn recent_recordings(recordings: &[Recording], limit: usize) -> Vec<(String, String)> {
+    let mut recent: Vec<&Recording> = recordings.iter().collect();
+    recent.sort_by_key(|recording| std::cmp::Reverse(recording.timestamp));
+    recent.into_iter()
+        .take(limit)
+        .map(|recording| {
+            let label = recording.title.clone()
+                .filter(|title| !title.trim().is_empty())
+                .unwrap_or_else(|| recording.filename.clone());
+            (recording.id.clone(), label)
+        })
+        .collect()
+}
+
+// Function to update tray menu based on recording state
+pub fn update_tray_menu(app: &AppHandle, is_recording: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app, is_recording)?;
+    
+    // Update the tray icon's menu (the icon itself is left as is, see `set_tray_recording`)
     if let Some(tray) = app.tray_by_id("main") {
         tray.set_menu(Some(menu))?;
     }
@@ -31,35 +134,52 @@ pub fn update_tray_menu(app: &AppHandle, is_recording: bool) -> Result<(), Box<d
     Ok(())
 }
 
+// Rebuild the tray menu from the current app state, e.g. after the recordings list or playback changed
+pub fn refresh_tray_menu(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let is_recording = app.try_state::<crate::AppState>()
+        .map(|state| !matches!(*state.recording_state.lock().unwrap(), RecordingState::Idle))
+        .unwrap_or(false);
+    update_tray_menu(app, is_recording)
+}
+
+// Refresh the tray menu off the caller's thread, for callers that may hold app state locks
+pub fn schedule_tray_refresh(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = refresh_tray_menu(&app);
+    });
+}
+
+// Drop "Stop Playback" from the menu once a recording plays to the end on its own.
+// The event is emitted from the audio callback, so the work happens off that thread.
+fn watch_playback_finished(app: &AppHandle) {
+    let app_handle = app.clone();
+    app.listen(crate::events::Events::PLAYBACK_FINISHED, move |_| {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Some(state) = app_handle.try_state::<crate::AppState>() {
+                let mut playback_state = state.playback_state.lock().unwrap();
+                if matches!(*playback_state, PlaybackState::Playing { .. }) {
+                    *playback_state = PlaybackState::Idle;
+                }
+            }
+            let _ = refresh_tray_menu(&app_handle);
+        });
+    });
+}
+
 // System tray setup
 pub fn setup_system_tray(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     use tauri::tray::{TrayIconBuilder, TrayIconEvent, MouseButtonState};
-    use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem};
 
-    // Create tray menu items
-    let open_window = MenuItemBuilder::with_id("open_window", "Open App Window").build(app)?;
-    let separator1 = PredefinedMenuItem::separator(app)?;
-    let start_stop_recording = MenuItemBuilder::with_id("start_stop_recording", "Start Recording").build(app)?;
-    let separator2 = PredefinedMenuItem::separator(app)?;
-    let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
-    
-    let menu = MenuBuilder::new(app)
-        .items(&[
-            &open_window,
-            &separator1, 
-            &start_stop_recording,
-            &separator2,
-            &quit
-        ])
-        .build()?;
+    let menu = build_tray_menu(app, false)?;
 
-    // Load tray icon from bytes (embedded in binary)
-    let icon_bytes = include_bytes!("../../icons/icon.png");
-    let icon = tauri::image::Image::from_bytes(icon_bytes)?;
+    let icon = tauri::image::Image::from_bytes(IDLE_ICON)?;
 
     let _tray = TrayIconBuilder::with_id("main")
         .menu(&menu)
         .icon(icon)
+        .tooltip(IDLE_TOOLTIP)
         .on_tray_icon_event(|_tray, event| {
             match event {
                 TrayIconEvent::Click { button_state, .. } => {
@@ -93,13 +213,81 @@ pub fn setup_system_tray(app: &tauri::AppHandle) -> Result<(), Box<dyn std::erro
                         }
                     });
                 }
+                "stop_playback" => {
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Some(state) = app_handle.try_state::<crate::AppState>() {
+                            if let Err(e) = crate::services::stop_playback(state).await {
+                                println!("Playback error: {}", e);
+                            }
+                            let _ = refresh_tray_menu(&app_handle);
+                        }
+                    });
+                }
                 "quit" => {
-                    std::process::exit(0);
+                    // Finish writing an in-progress recording so its WAV isn't cut off mid-write
+                    tauri::async_runtime::block_on(crate::services::shutdown(app_handle.clone()));
+                    app_handle.exit(0);
+                }
+                id => {
+                    if let Some(recording_id) = id.strip_prefix(RECENT_ITEM_PREFIX) {
+                        let recording_id = recording_id.to_string();
+                        let app_handle = app_handle.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Some(state) = app_handle.try_state::<crate::AppState>() {
+                                let options = crate::audio_system::PlaybackOptions::default();
+                                if let Err(e) = crate::services::play_recording(state, app_handle.clone(), recording_id, options).await {
+                                    println!("Playback error: {}", e);
+                                }
+                            }
+                        });
+                    }
                 }
-                _ => {}
             }
         })
         .build(app)?;
 
+    spawn_tray_status_updater(app.clone());
+    watch_playback_finished(app);
+
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(state: &str, elapsed_ms: u64) -> RecordingInfo {
+        RecordingInfo { state: state.to_string(), elapsed_ms, file_path: None, started_at: None }
+    }
+
+    #[test]
+    fn test_recent_recordings_are_newest_first_and_labelled() {
+        use chrono::{Duration, Utc};
+
+        let now = Utc::now();
+        let recording = |id: &str, minutes_ago: i64, title: Option<&str>| Recording {
+            timestamp: now - Duration::minutes(minutes_ago),
+            title: title.map(str::to_string),
+            ..Recording::test(id, &format!("{}.wav", id))
+        };
+        let recordings = vec![
+            recording("old", 30, Some("Standup")),
+            recording("new", 1, None),
+            recording("mid", 10, Some(" ")),
+        ];
+
+        assert_eq!(recent_recordings(&recordings, 2), vec![
+            ("new".to_string(), "new.wav".to_string()),
+            ("mid".to_string(), "mid.wav".to_string()),
+        ]);
+        assert_eq!(recent_recordings(&recordings, 5)[2].1, "Standup");
+    }
+
+    #[test]
+    fn test_recording_status_text() {
+        assert_eq!(recording_status_text(&info("recording", 134_500)).as_deref(), Some("Recording 02:14"));
+        assert_eq!(recording_status_text(&info("paused", 5_000)).as_deref(), Some("Paused 00:05"));
+        assert_eq!(recording_status_text(&info("idle", 0)), None);
+    }
 }
\ No newline at end of file
@@ -69,6 +69,9 @@ pub fn create_test_meeting_state(is_active: bool, app: Option<MeetingApp>) -> Me
         is_in_meeting: is_active,
         detected_app: app,
         started_at: if is_active { Some(Utc::now()) } else { None },
+        meeting_title: None,
+        detection_method: None,
+        confidence: 0.0,
     }
 }
 
@@ -243,7 +243,7 @@ impl MockMeetingEnvironment {
     /// Set up a Google Meet scenario
     pub fn setup_google_meet_scenario(&self) {
         self.browser_checker.set_running_browsers(vec!["Google Chrome".to_string()]);
-        self.browser_checker.add_browser_url("Google Chrome", "https://meet.google.com/abc-def-ghi".to_string());
+        self.browser_checker.add_browser_url("Google Chrome", "https://meet.google.com/abc-defg-hij".to_string());
     }
     
     /// Set up a Zoom scenario
@@ -269,6 +269,12 @@ impl MockMeetingEnvironment {
         self.process_checker.set_process_running("Discord", true);
     }
     
+    /// Set up a Cisco Webex scenario
+    pub fn setup_webex_scenario(&self) {
+        self.process_checker.set_process_running("Cisco Webex Meetings", true);
+        self.browser_checker.add_browser_url("Google Chrome", "https://mycompany.webex.com/meet/jdoe".to_string());
+    }
+    
     /// Set up no meeting scenario
     pub fn setup_no_meeting_scenario(&self) {
         // Clear all data to simulate no meetings
@@ -295,10 +301,9 @@ impl Default for MockMeetingEnvironment {
 pub mod fixtures {
     /// Common Google Meet URLs for testing
     pub const GOOGLE_MEET_ROOM_URLS: &[&str] = &[
-        "https://meet.google.com/abc-def-ghi",
-        "https://meet.google.com/xyz-123-456?authuser=0",
+        "https://meet.google.com/abc-defg-hij",
+        "https://meet.google.com/xyz-abcd-uvw?authuser=0",
         "https://meet.google.com/lookup/abc123def456",
-        "https://meet.google.com/new-meeting-room",
     ];
     
     /// Google Meet URLs that should NOT be detected as meetings
@@ -308,6 +313,10 @@ pub mod fixtures {
         "https://meet.google.com/_meet",
         "https://meet.google.com/?authuser=0",
         "https://meet.google.com/settings",
+        "https://meet.google.com/a-b",
+        "https://meet.google.com/abc-def-ghi",
+        "https://meet.google.com/new-meeting-room",
+        "https://meet.google.com/landing?pli=1&x=a-b-c",
     ];
     
     /// Zoom meeting URLs
@@ -329,11 +338,25 @@ pub mod fixtures {
         "https://slack.com/huddle/T12345",
     ];
     
+    /// Webex meeting URLs
+    pub const WEBEX_MEETING_URLS: &[&str] = &[
+        "https://mycompany.webex.com/meet/jdoe",
+        "https://mycompany.webex.com/wbxmjs/joinservice/sites/mycompany/meeting/download/abc123",
+    ];
+    
+    /// Webex URLs that should NOT be detected as meetings
+    pub const WEBEX_NON_MEETING_URLS: &[&str] = &[
+        "https://www.webex.com/",
+        "https://mycompany.webex.com/webappng/sites/mycompany/dashboard",
+        "https://www.webex.com/pricing/index.html",
+    ];
+    
     /// Process output samples for testing
     pub const ZOOM_PROCESS_OUTPUT: &str = "user    1234  zoom.us CptHost meeting";
     pub const TEAMS_PROCESS_OUTPUT: &str = "user    5678  Microsoft Teams --type=renderer";
     pub const SLACK_PROCESS_OUTPUT: &str = "user    9012  Slack Helper --type=gpu-process";
     pub const DISCORD_PROCESS_OUTPUT: &str = "user    3456  Discord --no-sandbox";
+    pub const WEBEX_PROCESS_OUTPUT: &str = "user    7890  Cisco Webex Meetings --meeting";
     
     /// Microphone usage lsof output samples
     pub const MICROPHONE_LSOF_OUTPUT: &str = r#"
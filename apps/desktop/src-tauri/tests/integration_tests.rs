@@ -214,7 +214,7 @@ mod meeting_recording_integration {
                 thread::spawn(move || {
                     // Simulate rapid meeting detection cycles
                     for cycle in 0..5 {
-                        let app_name = match cycle % 3 {
+                        let app_name = match cycle % 4 {
                             0 => {
                                 env.setup_google_meet_scenario();
                                 "GoogleMeet"
@@ -227,6 +227,10 @@ mod meeting_recording_integration {
                                 env.setup_teams_scenario();
                                 "MicrosoftTeams"
                             },
+                            3 => {
+                                env.setup_webex_scenario();
+                                "Webex"
+                            },
                             _ => unreachable!(),
                         };
                         
@@ -344,10 +348,20 @@ mod end_to_end_workflows {
         assert!(chrome_urls.is_none() || chrome_urls.unwrap().is_empty(), 
                "Should detect no meeting");
         
+        // Meet pages that aren't rooms (e.g. meet.google.com/a-b) keep the phase meeting-free
+        meeting_env.browser_checker.set_running_browsers(vec!["Google Chrome".to_string()]);
+        meeting_env.browser_checker.set_browser_tabs("Google Chrome", 
+            GOOGLE_MEET_NON_ROOM_URLS.iter().map(|url| url.to_string()).collect());
+        let chrome_urls = meeting_env.browser_checker.get_browser_urls("Google Chrome").unwrap();
+        assert!(chrome_urls.contains("meet.google.com/a-b"), "Should list the non-room tabs");
+        assert!(!GOOGLE_MEET_ROOM_URLS.iter().any(|room| chrome_urls.contains(room)), 
+               "Should detect no meeting");
+        meeting_env.setup_no_meeting_scenario();
+        
         // Phase 2: Meeting detected
         meeting_env.setup_google_meet_scenario();
         let chrome_urls = meeting_env.browser_checker.get_browser_urls("Google Chrome").unwrap();
-        assert!(chrome_urls.contains("meet.google.com/abc-def-ghi"), 
+        assert!(chrome_urls.contains("meet.google.com/abc-defg-hij"), 
                "Should detect Google Meet");
         
         // Start recording
@@ -111,27 +111,50 @@ mod url_pattern_tests {
         }
     }
     
+    #[test]
+    fn test_webex_url_patterns() {
+        for &url in WEBEX_MEETING_URLS {
+            let urls = format!("{} ", url);
+            assert!(is_webex_meeting_url(&urls), 
+                   "Should detect Webex meeting: {}", url);
+            assert!(matches!(detect_meeting_from_urls(&urls), Some(TestMeetingApp::Webex)),
+                   "Should resolve to Webex: {}", url);
+        }
+        
+        // Test non-meeting Webex URLs
+        for &url in WEBEX_NON_MEETING_URLS {
+            let urls = format!("{} ", url);
+            assert!(!is_webex_meeting_url(&urls), 
+                   "Should NOT detect as Webex meeting: {}", url);
+        }
+    }
+    
     #[test]
     fn test_multiple_urls_in_browser() {
         // Test when multiple tabs are open, but only one is a meeting
-        let mixed_urls = "https://github.com https://meet.google.com/abc-def-ghi https://news.ycombinator.com ";
+        let mixed_urls = "https://github.com https://meet.google.com/abc-defg-hij https://news.ycombinator.com ";
         assert!(is_google_meet_room_mock(mixed_urls), 
                "Should detect meeting among multiple URLs");
         
         // Test when multiple meeting URLs are present (should still detect)
-        let multiple_meetings = "https://meet.google.com/abc-def-ghi https://zoom.us/j/1234567890 ";
+        let multiple_meetings = "https://meet.google.com/abc-defg-hij https://zoom.us/j/1234567890 ";
         assert!(is_google_meet_room_mock(multiple_meetings), 
                "Should detect Google Meet when multiple meetings present");
         assert!(is_zoom_meeting_url(multiple_meetings), 
                "Should detect Zoom when multiple meetings present");
+        
+        // Short dashed paths and dashed query strings must not pass as room codes
+        let misleading_tabs = "https://meet.google.com/a-b https://meet.google.com/landing?pli=1&x=a-b-c ";
+        assert!(!is_google_meet_room_mock(misleading_tabs), 
+               "Should NOT detect a meeting from dashed non-room URLs");
     }
     
     #[test]
     fn test_edge_case_url_formats() {
         // Test URLs with various parameters and formats
         let edge_cases = [
-            "https://meet.google.com/abc-def-ghi?authuser=0&hl=en",
-            "https://meet.google.com/xyz-123-456#settings",
+            "https://meet.google.com/abc-defg-hij?authuser=0&hl=en",
+            "https://meet.google.com/xyz-abcd-uvw#settings",
             "https://zoom.us/j/1234567890?pwd=abc123&from=addon",
             "https://teams.microsoft.com/l/meetup-join/12345?context=something"
         ];
@@ -147,7 +170,7 @@ mod url_pattern_tests {
     #[test]
     fn test_malformed_urls() {
         let malformed_urls = [
-            "meet.google.com/abc-def-ghi",  // Missing protocol
+            "meet.google.com/abc-defg-hij", // Missing protocol
             "https://meet.google.com/",      // Just domain
             "https://meet.google.com/abc",   // Too short
             "not-a-url-at-all",
@@ -225,6 +248,7 @@ mod meeting_state_tests {
             create_test_meeting_app("MicrosoftTeams"),
             create_test_meeting_app("SlackHuddle"),
             create_test_meeting_app("Discord"),
+            create_test_meeting_app("Webex"),
         ];
         
         for app in apps {
@@ -261,9 +285,9 @@ mod mock_environment_tests {
         assert!(checker.get_browser_urls("Google Chrome").is_some(), "Chrome should be running by default");
         
         // Test adding URLs
-        checker.add_browser_url("Google Chrome", "https://meet.google.com/abc-def-ghi".to_string());
+        checker.add_browser_url("Google Chrome", "https://meet.google.com/abc-defg-hij".to_string());
         let urls = checker.get_browser_urls("Google Chrome").unwrap();
-        assert!(urls.contains("meet.google.com/abc-def-ghi"), "Should contain added URL");
+        assert!(urls.contains("meet.google.com/abc-defg-hij"), "Should contain added URL");
         
         // Test setting multiple URLs
         checker.set_browser_tabs("Safari", vec![
@@ -348,7 +372,7 @@ mod mock_environment_tests {
         // Test Google Meet scenario
         env.setup_google_meet_scenario();
         let chrome_urls = env.browser_checker.get_browser_urls("Google Chrome").unwrap();
-        assert!(chrome_urls.contains("meet.google.com/abc-def-ghi"), 
+        assert!(chrome_urls.contains("meet.google.com/abc-defg-hij"), 
                "Should set up Google Meet scenario");
         
         // Test Zoom scenario
@@ -373,6 +397,11 @@ mod mock_environment_tests {
         assert!(env.process_checker.is_process_running("Discord").unwrap(), 
                "Should set up Discord scenario");
         
+        // Test Webex scenario
+        env.setup_webex_scenario();
+        assert!(env.process_checker.is_process_running("Cisco Webex Meetings").unwrap(), 
+               "Should set up Webex scenario");
+        
         // Test no meeting scenario
         env.setup_no_meeting_scenario();
         // All checkers should be cleared - verify a few key indicators
@@ -414,7 +443,7 @@ mod integration_scenarios {
         
         // Set up conflicting signals - process says Teams, browser says Google Meet
         env.process_checker.set_process_running("Microsoft Teams", true);
-        env.browser_checker.add_browser_url("Google Chrome", "https://meet.google.com/abc-def-ghi".to_string());
+        env.browser_checker.add_browser_url("Google Chrome", "https://meet.google.com/abc-defg-hij".to_string());
         
         // Both should be detectable independently
         assert!(env.process_checker.is_process_running("Microsoft Teams").unwrap(), 
@@ -443,7 +472,7 @@ mod integration_scenarios {
         
         // Restore browser, break process detection
         env.browser_checker.set_applescript_failure(false);
-        env.browser_checker.add_browser_url("Google Chrome", "https://meet.google.com/test-room".to_string());
+        env.browser_checker.add_browser_url("Google Chrome", "https://meet.google.com/tst-room-abc".to_string());
         env.process_checker.set_command_failure(true);
         
         // Browser detection should work when process fails
@@ -459,32 +488,23 @@ mod integration_scenarios {
 // These would normally be in the main codebase, but we're testing them here
 
 fn is_google_meet_room_mock(urls: &str) -> bool {
-    if !urls.contains("meet.google.com/") {
-        return false;
-    }
-    
-    // Exclude landing pages and general pages
-    if urls.contains("meet.google.com/landing") || 
-       urls.contains("meet.google.com/_meet") ||
-       urls.contains("meet.google.com/?") {
-        return false;
-    }
-    
-    // Check for actual meeting room patterns
-    for line in urls.lines() {
-        if line.contains("meet.google.com/") {
-            if let Some(start) = line.find("meet.google.com/") {
-                let after_domain = &line[start + 16..]; // "meet.google.com/".len() = 16
-                
-                // Check for room code patterns (dashes) or lookup URLs
-                if after_domain.contains('-') || after_domain.starts_with("lookup/") {
-                    return true;
-                }
-            }
+    urls.split_whitespace().any(|url| {
+        let Some(start) = url.find("meet.google.com/") else {
+            return false;
+        };
+        let path = &url[start + "meet.google.com/".len()..];
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        
+        // Lookup URLs or room codes shaped like xxx-xxxx-xxx
+        if path.starts_with("lookup/") {
+            return true;
         }
-    }
-    
-    false
+        let groups: Vec<&str> = path.split('/').next().unwrap_or_default().split('-').collect();
+        groups.len() == 3
+            && groups.iter().zip([3, 4, 3]).all(|(group, len)| {
+                group.len() == len && group.chars().all(|c| c.is_ascii_lowercase())
+            })
+    })
 }
 
 fn is_zoom_meeting_url(urls: &str) -> bool {
@@ -499,6 +519,10 @@ fn is_slack_huddle_url(urls: &str) -> bool {
     urls.contains("app.slack.com") && (urls.contains("/huddle/") || urls.contains("huddle"))
 }
 
+fn is_webex_meeting_url(urls: &str) -> bool {
+    urls.contains(".webex.com/meet") || urls.contains(".webex.com/wbxmjs")
+}
+
 fn detect_meeting_from_urls(urls: &str) -> Option<TestMeetingApp> {
     if is_google_meet_room_mock(urls) {
         Some(TestMeetingApp::GoogleMeet)
@@ -508,6 +532,8 @@ fn detect_meeting_from_urls(urls: &str) -> Option<TestMeetingApp> {
         Some(TestMeetingApp::MicrosoftTeams)
     } else if is_slack_huddle_url(urls) {
         Some(TestMeetingApp::SlackHuddle)
+    } else if is_webex_meeting_url(urls) {
+        Some(TestMeetingApp::Webex)
     } else {
         None
     }
@@ -521,6 +547,7 @@ enum TestMeetingApp {
     GoogleMeet,
     MicrosoftTeams,
     Discord,
+    Webex,
     Unknown(String),
 }
 
@@ -546,6 +573,7 @@ fn create_test_meeting_app(app_type: &str) -> TestMeetingApp {
         "MicrosoftTeams" => TestMeetingApp::MicrosoftTeams,
         "SlackHuddle" => TestMeetingApp::SlackHuddle,
         "Discord" => TestMeetingApp::Discord,
+        "Webex" => TestMeetingApp::Webex,
         name => TestMeetingApp::Unknown(name.to_string()),
     }
 }
\ No newline at end of file
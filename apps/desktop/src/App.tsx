@@ -14,6 +14,7 @@ function App() {
 		recordingState,
 		recordingDuration,
 		recentRecordings,
+		hasMoreRecordings,
 		transcribingIds,
 		handleStartRecording,
 		handlePauseRecording,
@@ -21,6 +22,7 @@ function App() {
 		handleResumeRecording,
 		formatDuration,
 		refreshAppState,
+		loadMoreRecordings,
 	} = useRecordingState();
 
 
@@ -71,6 +73,8 @@ function App() {
 				recordings={recentRecordings} 
 				transcribingIds={transcribingIds}
 				onRecordingsChange={refreshAppState} 
+				hasMore={hasMoreRecordings}
+				onLoadMore={loadMoreRecordings}
 			/>
 		</div>
 	);
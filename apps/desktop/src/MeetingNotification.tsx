@@ -59,6 +59,7 @@ export default function MeetingNotification() {
         GoogleMeet: 'Google Meet',
         MicrosoftTeams: 'Microsoft Teams',
         Discord: 'Discord',
+        Webex: 'Webex',
       };
       return appNames[detectedApp] || 'Meeting';
     } else if (detectedApp.Unknown) {
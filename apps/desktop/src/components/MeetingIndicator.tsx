@@ -39,6 +39,7 @@ export function MeetingIndicator() {
     GoogleMeet: 'Google Meet',
     MicrosoftTeams: 'Microsoft Teams',
     Discord: 'Discord',
+    Webex: 'Webex',
   };
 
   const getAppName = (detectedApp?: string | { Unknown: string }): string => {
@@ -8,9 +8,14 @@ interface RecordingsListProps {
 	recordings: Recording[];
 	transcribingIds: Set<string>;
 	onRecordingsChange?: () => void;
+	hasMore?: boolean;
+	onLoadMore?: () => void;
 }
 
-export function RecordingsList({ recordings, transcribingIds, onRecordingsChange }: RecordingsListProps) {
+// Distance from the bottom of the list at which the next page is requested
+const LOAD_MORE_THRESHOLD_PX = 40;
+
+export function RecordingsList({ recordings, transcribingIds, onRecordingsChange, hasMore, onLoadMore }: RecordingsListProps) {
 	const [playingId, setPlayingId] = useState<string | null>(null);
 	const [deletingId, setDeletingId] = useState<string | null>(null);
 	const [confirmDialog, setConfirmDialog] = useState<{
@@ -99,7 +104,15 @@ export function RecordingsList({ recordings, transcribingIds, onRecordingsChange
 				</button>
 			</div>
 			{recordings.length > 0 ? (
-				<div className="space-y-2 max-h-40 overflow-y-auto scrollbar-thin pr-1">
+				<div
+					className="space-y-2 max-h-40 overflow-y-auto scrollbar-thin pr-1"
+					onScroll={(event) => {
+						const list = event.currentTarget;
+						if (hasMore && list.scrollHeight - list.scrollTop - list.clientHeight < LOAD_MORE_THRESHOLD_PX) {
+							onLoadMore?.();
+						}
+					}}
+				>
 					{recordings.map((recording, index) => (
 						<div
 							key={recording.id}
@@ -107,7 +120,7 @@ export function RecordingsList({ recordings, transcribingIds, onRecordingsChange
 						>
 							<div className="flex-1 min-w-0">
 								<p className="text-xs font-medium text-gray-900 truncate">
-									{recording.filename}
+									{recording.title ?? recording.filename}
 								</p>
 								<p className="text-xs text-gray-500 mt-0.5">
 									{recording.duration}
@@ -1,14 +1,35 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import type { Recording, RecordingState } from "../types";
+import type { Recording, RecordingState, RecordingsPage } from "../types";
+
+const RECORDINGS_PAGE_SIZE = 50;
+
+const fetchRecordings = async (offset: number, limit: number): Promise<RecordingsPage> => {
+	return (await invoke("get_recordings_page", {
+		offset,
+		limit,
+		sort: "descending",
+	})) as RecordingsPage;
+};
 
 export function useRecordingState() {
 	const [recordingState, setRecordingState] = useState<RecordingState>("idle");
 	const [recordingDuration, setRecordingDuration] = useState(0);
 	const [recentRecordings, setRecentRecordings] = useState<Recording[]>([]);
+	const [totalRecordings, setTotalRecordings] = useState(0);
 	const [transcribingIds, setTranscribingIds] = useState<Set<string>>(new Set());
+	// Number of recordings loaded so far, so refreshes keep the pages the user scrolled to
+	const loadedCountRef = useRef(RECORDINGS_PAGE_SIZE);
+	const loadingMoreRef = useRef(false);
+
+	const reloadRecordings = async () => {
+		const page = await fetchRecordings(0, Math.max(loadedCountRef.current, RECORDINGS_PAGE_SIZE));
+		loadedCountRef.current = page.recordings.length;
+		setRecentRecordings(page.recordings);
+		setTotalRecordings(page.total_count);
+	};
 
 	// Function to refresh state and recordings
 	const refreshAppState = async () => {
@@ -16,13 +37,34 @@ export function useRecordingState() {
 			const currentState = (await invoke("get_recording_state")) as string;
 			setRecordingState(currentState as RecordingState);
 			
-			const recordings = (await invoke("get_recent_recordings")) as Recording[];
-			setRecentRecordings(recordings);
+			await reloadRecordings();
 		} catch (error) {
 			console.error("Failed to refresh app state:", error);
 		}
 	};
 
+	// Append the next page of recordings (e.g. when the list is scrolled to the bottom)
+	const loadMoreRecordings = async () => {
+		if (loadingMoreRef.current || recentRecordings.length >= totalRecordings) return;
+		loadingMoreRef.current = true;
+		try {
+			const page = await fetchRecordings(recentRecordings.length, RECORDINGS_PAGE_SIZE);
+			setRecentRecordings((prev) => {
+				const known = new Set(prev.map((recording) => recording.id));
+				const next = [...prev, ...page.recordings.filter((recording) => !known.has(recording.id))];
+				loadedCountRef.current = next.length;
+				return next;
+			});
+			setTotalRecordings(page.total_count);
+		} catch (error) {
+			console.error("Failed to load more recordings:", error);
+		} finally {
+			loadingMoreRef.current = false;
+		}
+	};
+
+	const hasMoreRecordings = recentRecordings.length < totalRecordings;
+
 	// Initialize app state on mount
 	useEffect(() => {
 		const initializeState = async () => {
@@ -34,9 +76,8 @@ export function useRecordingState() {
 				const currentState = (await invoke("get_recording_state")) as string;
 				setRecordingState(currentState as RecordingState);
 
-				// Load recent recordings
-				const recordings = (await invoke("get_recent_recordings")) as Recording[];
-				setRecentRecordings(recordings);
+				// Load the first page of recordings
+				await reloadRecordings();
 			} catch (error) {
 				console.error("Failed to initialize app state:", error);
 			}
@@ -165,7 +206,9 @@ export function useRecordingState() {
 			const recording = (await invoke("stop_recording")) as Recording;
 			setRecordingState("idle");
 			setRecordingDuration(0);
-			setRecentRecordings((prev) => [recording, ...prev.slice(0, 4)]);
+			setRecentRecordings((prev) => [recording, ...prev.filter((r) => r.id !== recording.id)]);
+			setTotalRecordings((prev) => prev + 1);
+			loadedCountRef.current += 1;
 		} catch (error) {
 			console.error("Failed to stop recording:", error);
 			setRecordingState("idle");
@@ -192,6 +235,7 @@ export function useRecordingState() {
 		recordingState,
 		recordingDuration,
 		recentRecordings,
+		hasMoreRecordings,
 		transcribingIds,
 		
 		// Actions
@@ -200,6 +244,7 @@ export function useRecordingState() {
 		handleStopRecording,
 		handleResumeRecording,
 		refreshAppState,
+		loadMoreRecordings,
 		
 		// Utils
 		formatDuration,
@@ -4,18 +4,177 @@ export interface Recording {
 	id: string;
 	filename: string;
 	duration: string;
+	duration_ms?: number;
+	file_size_bytes?: number;
 	timestamp: Date;
-	status: "local" | "uploaded" | "failed";
+	status: "local" | "uploaded" | "failed" | "processing" | "transcribing";
+	title?: string | null;
+	has_transcript?: boolean;
+	dropped_samples?: number;
+	needs_conversion?: boolean;
+	original_filename?: string | null;
+	content_hash?: string | null;
+	notes?: Note[];
+}
+
+export interface Note {
+	timestamp_ms: number;
+	text: string;
+}
+
+export type SortOrder = "ascending" | "descending";
+
+export interface RecordingsPage {
+	recordings: Recording[];
+	total_count: number;
+	offset: number;
+	limit: number;
+}
+
+export interface DeleteOutcome {
+	recording_id: string;
+	deleted: boolean;
+	error?: string | null;
+}
+
+export interface RecordingsAudit {
+	orphan_files: string[];
+	missing_files: string[];
+}
+
+export interface SearchSnippet {
+	text: string;
+	start?: number | null;
+	end?: number | null;
+	speaker?: string | null;
+}
+
+export interface RecordingDetail {
+	recording: Recording;
+	transcript?: TranscriptionResponse | null;
+	waveform_peaks?: number[] | null;
+	notes: Note[];
+}
+
+export interface RecordingMatch {
+	recording: Recording;
+	title_matched: boolean;
+	snippets: SearchSnippet[];
+	score: number;
+}
+
+export interface RecordingInfo {
+	state: RecordingState;
+	elapsed_ms: number;
+	file_path?: string | null;
+	started_at?: string | null;
+}
+
+export interface PlaybackInfo {
+	recording_id: string;
+	filename: string;
+	state: "playing" | "paused";
+	position_ms: number;
+	duration_ms: number;
+}
+
+export interface RecordingDegraded {
+	dropped_samples: number;
+}
+
+export interface RecordingsPruned {
+	recording_ids: string[];
+	freed_bytes: number;
+	retention_days: number;
+}
+
+export interface RecordingDeduplicated {
+	recording_id: string;
+	duplicate_of: string;
+}
+
+export interface RecordingCountdown {
+	remaining_secs: number;
+	cancelled: boolean;
+}
+
+export interface TranscriptSegment {
+	speaker?: string | null;
+	start: number;
+	end: number;
+	text: string;
+}
+
+export interface TranscriptionResponse {
+	transcript: string;
+	confidence?: number | null;
+	processing_time?: number | null;
+	word_count?: number | null;
+	segments?: TranscriptSegment[] | null;
+}
+
+export interface TranscriptionProgress {
+	recording_id: string;
+	stage: "uploading" | "transcribing";
+	percent: number;
+	chunk: number;
+	chunk_count: number;
 }
 
 export interface MeetingState {
 	is_in_meeting: boolean;
 	detected_app?: string | { Unknown: string };
 	started_at?: string;
+	meeting_title?: string | null;
+	detection_method?: DetectionMethod | null;
+	confidence?: number;
+}
+
+export type DetectionMethod = "process" | "browser_url" | "window_title" | "microphone";
+
+export interface DetectionProbe {
+	meeting_processes: string[];
+	browser_urls: string[];
+	window_titles: string[];
+	matched_by?: DetectionMethod | null;
+	detected_app?: string | { Unknown: string } | null;
+}
+
+export interface AudioDeviceInfo {
+	name: string;
+	default_sample_rate: number;
+	channels: number;
+	is_default: boolean;
 }
 
+export interface AudioDiagnostics {
+	app_version: string;
+	os: string;
+	host: string;
+	selected_input_device?: string | null;
+	input_device?: AudioDeviceInfo | null;
+	input_device_error?: string | null;
+	captured_sample_rate?: number | null;
+	target_sample_rate?: number | null;
+	recording_format: "opus" | "mp3";
+	loopback_device?: string | null;
+}
+
+export type RecordingLayout = "flat" | "per_day" | "per_meeting";
+
+export type RecordChannels = "mono" | "stereo" | "device_native";
+
+export type WavBitDepth = "int16" | "int24" | "float32";
+
+export type MeetingAction = "prompt" | "auto_record" | "ignore";
+
 export interface AppState {
 	recordingState: RecordingState;
 	recordingDuration: number;
 	recentRecordings: Recording[];
-}
\ No newline at end of file
+}
+
+export interface ErrorPayload {
+	kind: string;
+	message: string;
+}